@@ -0,0 +1,228 @@
+use crate::{
+    behavior::{
+        movement::{drive_towards, simple_steer_towards},
+        strike::GroundedHit,
+    },
+    eeg::{Drawable, Event},
+    strategy::{Action, Behavior, Context, Goal},
+    utils::geometry::Plane,
+};
+use common::prelude::*;
+use nalgebra::{Point2, Point3, Unit, UnitComplex, Vector2, Vector3};
+use nameof::name_of_type;
+use ordered_float::NotNan;
+use std::f32::consts::PI;
+
+/// Instead of clearing a fast ball (and maybe clearing it right back into
+/// danger), park in its path and let it hit us. By facing it at the right
+/// angle and backing up as it arrives, we take most of the speed out of it.
+pub struct AbsorbBall;
+
+impl AbsorbBall {
+    /// Only bother with balls at least this fast. Slow balls are better off
+    /// hit normally.
+    const MIN_BALL_SPEED: f32 = 1500.0;
+
+    /// Once the ball is slower than this, it's been deadened.
+    const DEAD_BALL_SPEED: f32 = 800.0;
+
+    /// How fast we expect to be backing up at the moment of contact.
+    const REVERSE_SPEED: f32 = 1000.0;
+
+    /// Start reversing this long before the ball arrives.
+    const REVERSE_LEAD_TIME: f32 = 0.5;
+
+    /// The fraction of the ball's speed along the contact normal which
+    /// survives the bounce.
+    const RESTITUTION: f32 = 0.6;
+
+    /// Roughly the distance from the car's center to its front bumper, plus
+    /// the ball's radius.
+    const CONTACT_OFFSET: f32 = 160.0;
+
+    /// How close we need to be to our spot before we start lining up.
+    const SETUP_DIST: f32 = 250.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Is a fast, low ball coming at our goal, so that parking in its path
+    /// would do any good?
+    pub fn applicable(ctx: &mut Context<'_>) -> Result<(), &'static str> {
+        let intercept = some_or_else!(ctx.scenario.me_intercept(), {
+            return Err("no intercept");
+        });
+        if intercept.ball_loc.z >= GroundedHit::MAX_BALL_Z {
+            return Err("the ball is too high");
+        }
+        if intercept.ball_vel.norm() < Self::MIN_BALL_SPEED {
+            return Err("the ball is too slow to absorb");
+        }
+        let goal = ctx.game.own_goal();
+        if intercept.ball_vel.to_2d().dot(&goal.normal_2d) >= 0.0 {
+            return Err("the ball is not heading towards our goal");
+        }
+        Ok(())
+    }
+
+    /// Predict the ball's velocity after it bounces off our front bumper,
+    /// given which way the car is facing and how the car is moving.
+    fn rebound(
+        ball_vel: Vector3<f32>,
+        car_facing: Unit<Vector2<f32>>,
+        car_vel: Vector3<f32>,
+    ) -> Vector3<f32> {
+        let bumper = Plane::point_normal(Point3::origin(), car_facing.to_3d());
+        let relative = ball_vel - car_vel;
+        // Only bounce if the ball is actually moving into the bumper.
+        if bumper.normal.dot(&relative) >= 0.0 {
+            return ball_vel;
+        }
+        let reflected = bumper.reflect_vector(&relative);
+        let bounced = relative + (reflected - relative) * ((1.0 + Self::RESTITUTION) / 2.0);
+        car_vel + bounced
+    }
+
+    /// Choose which way to face the oncoming ball. Facing it head-on kills the
+    /// most speed, but never steer the rebound towards our own goal.
+    fn choose_facing(ball_vel: Vector3<f32>, own_goal: &Goal) -> Unit<Vector2<f32>> {
+        let head_on = Unit::new_normalize(-ball_vel.to_2d());
+        let towards_goal = -own_goal.normal_2d.to_3d().into_inner();
+
+        (-4..=4)
+            .map(|i| UnitComplex::new(i as f32 * PI / 24.0) * head_on)
+            .min_by_key(|&facing| {
+                let car_vel = -facing.to_3d().into_inner() * Self::REVERSE_SPEED;
+                let rebound = Self::rebound(ball_vel, facing, car_vel);
+                let goalward = rebound.dot(&towards_goal).max(0.0);
+                NotNan::new(rebound.norm() + goalward * 2.0).unwrap()
+            })
+            .unwrap()
+    }
+}
+
+impl Behavior for AbsorbBall {
+    fn name(&self) -> &str {
+        name_of_type!(AbsorbBall)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::AbsorbBall);
+
+        let ball_vel = ctx.packet.GameBall.Physics.vel();
+        if ball_vel.norm() < Self::DEAD_BALL_SPEED {
+            ctx.eeg.log(self.name(), "the ball is dead");
+            return Action::Return;
+        }
+
+        if let Err(reason) = Self::applicable(ctx) {
+            ctx.eeg.log(self.name(), reason);
+            return Action::Abort;
+        }
+        let intercept = ctx.scenario.me_intercept().unwrap();
+
+        let facing = Self::choose_facing(intercept.ball_vel, ctx.game.own_goal());
+        let spot = intercept.ball_loc.to_2d() - facing.into_inner() * Self::CONTACT_OFFSET;
+        ctx.eeg.draw(Drawable::Crosshair(spot));
+        ctx.eeg
+            .print_angle("facing", Vector2::x().angle_to(&facing));
+
+        let me = ctx.me();
+        if (spot - me.Physics.loc_2d()).norm() >= Self::SETUP_DIST {
+            return Action::Yield(drive_towards(ctx, spot));
+        }
+
+        // Line up the bumper, then back away as the ball arrives to soften the
+        // blow.
+        let nose_target: Point2<f32> = me.Physics.loc_2d() + facing.into_inner() * 1000.0;
+        let throttle = if intercept.time < Self::REVERSE_LEAD_TIME {
+            -1.0
+        } else {
+            0.0
+        };
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: throttle,
+            Steer: simple_steer_towards(&me.Physics, nose_target),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::behavior::defense::absorb_ball::AbsorbBall;
+    use common::prelude::*;
+    use nalgebra::{Unit, Vector2, Vector3};
+
+    #[test]
+    fn reversing_kills_speed() {
+        let ball_vel = Vector3::new(0.0, -2000.0, 0.0);
+        let facing = Unit::new_normalize(Vector2::new(0.0, 1.0));
+        let parked = AbsorbBall::rebound(ball_vel, facing, Vector3::zeros());
+        let reversing =
+            AbsorbBall::rebound(ball_vel, facing, -facing.to_3d().into_inner() * 1000.0);
+        assert!(parked.y > 0.0);
+        assert!(reversing.norm() < parked.norm());
+    }
+
+    #[test]
+    fn ball_moving_away_does_not_bounce() {
+        let ball_vel = Vector3::new(0.0, 2000.0, 0.0);
+        let facing = Unit::new_normalize(Vector2::new(0.0, 1.0));
+        assert_eq!(
+            AbsorbBall::rebound(ball_vel, facing, Vector3::zeros()),
+            ball_vel,
+        );
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::defense::{AbsorbBall, Defense},
+        eeg::Event,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point3, Vector3};
+
+    #[test]
+    fn deaden_fast_ball_near_goal() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, -500.0, 92.74),
+                ball_vel: Vector3::new(0.0, -2000.0, 0.0),
+                car_loc: Point3::new(0.0, -3500.0, 17.01),
+                ..Default::default()
+            })
+            .behavior(AbsorbBall::new())
+            .run_for_millis(2000);
+
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::AbsorbBall));
+        });
+        let packet = test.sniff_packet();
+        let ball_vel = packet.GameBall.Physics.vel();
+        println!("ball_vel = {:?}", ball_vel);
+        assert!(ball_vel.norm() < 1000.0);
+        assert!(!test.enemy_has_scored());
+    }
+
+    #[test]
+    fn defense_chooses_to_absorb() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, -500.0, 92.74),
+                ball_vel: Vector3::new(0.0, -2000.0, 0.0),
+                car_loc: Point3::new(0.0, -3500.0, 17.01),
+                ..Default::default()
+            })
+            .behavior(Defense::new())
+            .run_for_millis(200);
+
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::AbsorbBall));
+        });
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
     behavior::{
-        defense::{retreat::Retreat, retreating_save::RetreatingSave, PanicDefense},
+        defense::{
+            retreat::Retreat, retreating_save::RetreatingSave, AbsorbBall, Demo, FarPostCover,
+            PanicDefense, ResetToNet,
+        },
         offense::TepidHit,
         strike::{GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust},
     },
@@ -131,6 +134,19 @@ impl Behavior for Defense {
             return Action::tail_call(Retreat::new());
         }
 
+        // If a teammate is going for the ball, hang back and cover the far post.
+        if FarPostCover::applicable(ctx) {
+            ctx.eeg.log(self.name(), "covering the far post");
+            return Action::tail_call(FarPostCover::new());
+        }
+
+        // If a fast ball is coming right at us, soak it up rather than clearing
+        // it back into danger.
+        if AbsorbBall::applicable(ctx).is_ok() {
+            ctx.eeg.log(self.name(), "absorbing the ball");
+            return Action::tail_call(AbsorbBall::new());
+        }
+
         if Self::enemy_can_shoot(ctx) {
             ctx.eeg.log(self.name(), "enemy_can_shoot");
             return Action::tail_call(Retreat::new());
@@ -141,6 +157,18 @@ impl Behavior for Defense {
             return Action::tail_call(Retreat::new());
         }
 
+        // If they'll get to the ball first anyway, don't chase it out of the
+        // net. Fall back and wait for a better chance.
+        if ctx.scenario.possession() < -Scenario::POSSESSION_CONTESTABLE {
+            // Unless we can blow them up before they get there.
+            if let Ok(enemy_index) = Demo::applicable(ctx) {
+                ctx.eeg.log(self.name(), "out-possessed; demoing the enemy");
+                return Action::tail_call(Demo::new(enemy_index));
+            }
+            ctx.eeg.log(self.name(), "out-possessed; resetting to net");
+            return Action::tail_call(ResetToNet::new());
+        }
+
         // If we're already in goal, try to take control of the ball.
         Action::tail_call(TepidHit::new())
     }
@@ -187,6 +215,7 @@ mod integration_tests {
     use brain_test_data::recordings;
     use common::{prelude::*, rl};
     use nalgebra::{Point2, Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
 
     #[test]
     fn coming_in_hot_swat_away() {
@@ -810,4 +839,26 @@ mod integration_tests {
         println!("ball_loc = {:?}", ball_loc);
         assert!(ball_loc.x < -2500.0);
     }
+
+    #[test]
+    fn reset_to_net_when_out_possessed() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                // The enemy is about to pick the ball up from the side. We're
+                // goal-side, but too far away to contest it.
+                ball_loc: Point3::new(0.0, 0.0, 92.74),
+                car_loc: Point3::new(0.0, -3500.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                enemy_loc: Point3::new(-800.0, 0.0, 17.01),
+                enemy_rot: Rotation3::from_unreal_angles(0.0, 0.0, 0.0),
+                enemy_vel: Vector3::new(500.0, 0.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(Defense::new())
+            .run_for_millis(500);
+
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::ResetToNet));
+        });
+    }
 }
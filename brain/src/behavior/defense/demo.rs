@@ -0,0 +1,267 @@
+use crate::{
+    behavior::movement::{simple_steer_towards, BlitzToLocation},
+    eeg::Drawable,
+    helpers::drive::{rough_time_drive_to_loc, time_to_supersonic},
+    strategy::{Action, Behavior, Context},
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Vector2};
+use nameof::name_of_type;
+use std::{f32::consts::PI, ptr};
+
+/// Blow up an enemy car by ramming it at supersonic speed.
+pub struct Demo {
+    enemy_index: usize,
+    prediction: Option<Prediction>,
+}
+
+/// Where we expected the enemy to be at a given game time.
+struct Prediction {
+    t: f32,
+    loc: Point2<f32>,
+}
+
+impl Demo {
+    /// Don't bother chasing anything further out than this. The enemy has too
+    /// long to notice us coming.
+    const MAX_INTERCEPT_TIME: f32 = 3.0;
+
+    /// How far ahead to extrapolate the enemy's path when checking whether
+    /// they've changed course.
+    const PREDICTION_HORIZON: f32 = 0.25;
+
+    /// If the enemy strays this far from where we expected, they've turned or
+    /// dodged, and our intercept is no good.
+    const ERROR_THRESHOLD: f32 = 150.0;
+
+    pub fn new(enemy_index: usize) -> Self {
+        Self {
+            enemy_index,
+            prediction: None,
+        }
+    }
+
+    /// If we can demo the enemy who's about to win the ball before they get
+    /// there, returns their index in the packet.
+    pub fn applicable(ctx: &mut Context<'_>) -> Result<usize, &'static str> {
+        let &(enemy, ref enemy_intercept) = some_or_else!(ctx.scenario.enemy_intercept(), {
+            return Err("no enemy intercept");
+        });
+        let intercept = Self::plan(ctx.me(), enemy)?;
+        if intercept.time >= enemy_intercept.time {
+            return Err("the enemy will reach the ball first");
+        }
+        let enemy_index = ctx.packet.cars().position(|car| ptr::eq(car, enemy));
+        enemy_index.ok_or("the enemy is not in the packet")
+    }
+
+    fn plan(
+        me: &common::halfway_house::PlayerInfo,
+        enemy: &common::halfway_house::PlayerInfo,
+    ) -> Result<DemoIntercept, &'static str> {
+        if enemy.Demolished {
+            return Err("the enemy is already demolished");
+        }
+        if !me.OnGround {
+            return Err("not on ground");
+        }
+
+        let intercept = some_or_else!(
+            supersonic_intercept(me, enemy.Physics.loc_2d(), enemy.Physics.vel_2d()),
+            {
+                return Err("can't reach supersonic before contact");
+            }
+        );
+
+        let steer = simple_steer_towards(&me.Physics, intercept.loc);
+        if steer.abs() >= PI / 2.0 {
+            return Err("not facing the enemy");
+        }
+
+        Ok(intercept)
+    }
+}
+
+impl Behavior for Demo {
+    fn name(&self) -> &str {
+        name_of_type!(Demo)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let me = ctx.me();
+        let enemy = some_or_else!(ctx.packet.GameCars.get(self.enemy_index), {
+            ctx.eeg.log(self.name(), "enemy is gone");
+            return Action::Abort;
+        });
+
+        if enemy.Demolished {
+            ctx.eeg.log(self.name(), "got 'em");
+            return Action::Return;
+        }
+        if self.enemy_changed_course(ctx, enemy) {
+            return Action::Abort;
+        }
+
+        let intercept = match Self::plan(me, enemy) {
+            Ok(intercept) => intercept,
+            Err(reason) => {
+                ctx.eeg.log(self.name(), reason);
+                return Action::Abort;
+            }
+        };
+
+        ctx.eeg.draw(Drawable::ghost_car_ground(
+            intercept.loc,
+            enemy.Physics.rot(),
+        ));
+        ctx.eeg.print_time("intercept_time", intercept.time);
+
+        match BlitzToLocation::new(intercept.loc)
+            .urgent(true)
+            .execute_old(ctx)
+        {
+            // `BlitzToLocation` eases off the boost near the end, but we need to
+            // arrive supersonic.
+            Action::Yield(input) => Action::Yield(common::halfway_house::PlayerInput {
+                Boost: me.Physics.vel().norm() < rl::CAR_ALMOST_MAX_SPEED,
+                ..input
+            }),
+            action => action,
+        }
+    }
+}
+
+impl Demo {
+    /// This mirrors `SameBallTrajectory`, but for the enemy car.
+    fn enemy_changed_course(
+        &mut self,
+        ctx: &mut Context<'_>,
+        enemy: &common::halfway_house::PlayerInfo,
+    ) -> bool {
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let extrapolate =
+            |rel_time: f32| enemy.Physics.loc_2d() + enemy.Physics.vel_2d() * rel_time;
+
+        if let Some(ref prediction) = self.prediction {
+            let rel_time = prediction.t - now;
+            let error = (prediction.loc - extrapolate(rel_time)).norm();
+            if error >= Self::ERROR_THRESHOLD {
+                ctx.eeg.log(
+                    name_of_type!(Demo),
+                    format!("enemy changed course with error {:.2}", error),
+                );
+                return true;
+            }
+            if rel_time > 0.0 {
+                return false;
+            }
+        }
+
+        self.prediction = Some(Prediction {
+            t: now + Self::PREDICTION_HORIZON,
+            loc: extrapolate(Self::PREDICTION_HORIZON),
+        });
+        false
+    }
+}
+
+struct DemoIntercept {
+    time: f32,
+    loc: Point2<f32>,
+}
+
+/// Find when and where we'd ram an enemy coasting along at a constant
+/// velocity. Returns `None` unless we'd be supersonic by then.
+fn supersonic_intercept(
+    car: &common::halfway_house::PlayerInfo,
+    enemy_loc: Point2<f32>,
+    enemy_vel: Vector2<f32>,
+) -> Option<DemoIntercept> {
+    const STEP: f32 = 0.05;
+
+    let supersonic_time = time_to_supersonic(car)?;
+    let mut t = 0.0;
+    while t < Demo::MAX_INTERCEPT_TIME {
+        let loc = enemy_loc + enemy_vel * t;
+        if rough_time_drive_to_loc(car, loc) <= t {
+            if supersonic_time > t {
+                return None;
+            }
+            return Some(DemoIntercept { time: t, loc });
+        }
+        t += STEP;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::behavior::defense::demo::supersonic_intercept;
+    use common::halfway_house::PlayerInfo;
+    use nalgebra::{Point2, Vector2};
+
+    fn start(speed: f32, boost: i32) -> PlayerInfo {
+        let mut car = PlayerInfo::default();
+        car.Physics.Velocity.X = speed;
+        car.Boost = boost;
+        car
+    }
+
+    #[test]
+    fn ram_a_parked_car() {
+        let enemy_loc = Point2::new(3000.0, 0.0);
+        let intercept = supersonic_intercept(&start(0.0, 100), enemy_loc, Vector2::zeros());
+        let intercept = intercept.unwrap();
+        assert_eq!(intercept.loc, enemy_loc);
+        assert!(intercept.time < 2.5);
+    }
+
+    #[test]
+    fn too_close_to_get_up_to_speed() {
+        let enemy_loc = Point2::new(500.0, 0.0);
+        assert!(supersonic_intercept(&start(0.0, 100), enemy_loc, Vector2::zeros()).is_none());
+        // Already supersonic, so it's fine.
+        assert!(supersonic_intercept(&start(2250.0, 100), enemy_loc, Vector2::zeros()).is_some());
+    }
+
+    #[test]
+    fn no_boost_no_demo() {
+        let enemy_loc = Point2::new(3000.0, 0.0);
+        assert!(supersonic_intercept(&start(0.0, 0), enemy_loc, Vector2::zeros()).is_none());
+    }
+
+    #[test]
+    fn cant_catch_a_fleeing_car() {
+        let enemy_loc = Point2::new(1000.0, 0.0);
+        let enemy_vel = Vector2::new(2200.0, 0.0);
+        assert!(supersonic_intercept(&start(1000.0, 100), enemy_loc, enemy_vel).is_none());
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::defense::Demo,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use nalgebra::{Point3, Vector3};
+
+    #[test]
+    fn demo_stationary_enemy() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                // Keep the ball out of the way.
+                ball_loc: Point3::new(-3000.0, 0.0, 92.74),
+                car_loc: Point3::new(0.0, -2000.0, 17.01),
+                enemy_loc: Point3::new(0.0, 1500.0, 17.01),
+                enemy_vel: Vector3::zeros(),
+                boost: 100,
+                ..Default::default()
+            })
+            .behavior(Demo::new(1))
+            .run_for_millis(3000);
+
+        let packet = test.sniff_packet();
+        assert!(packet.GameCars[1].Demolished);
+    }
+}
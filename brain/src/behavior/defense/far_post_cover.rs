@@ -0,0 +1,105 @@
+use crate::{
+    behavior::movement::{drive_towards, simple_steer_towards},
+    eeg::{Drawable, Event},
+    strategy::{Action, Behavior, Context},
+};
+use common::prelude::*;
+use nalgebra::Point2;
+use nameof::name_of_type;
+
+/// As the second defender, hang back at the far post while a teammate
+/// challenges for the ball.
+pub struct FarPostCover;
+
+impl FarPostCover {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns true if a teammate is going for the ball, so there's a post
+    /// that needs covering.
+    pub fn applicable(ctx: &mut Context<'_>) -> bool {
+        ctx.scenario.teammate_intercept().is_some() && !ctx.scenario.i_am_first_man()
+    }
+
+    /// A spot just in front of the far post, tucked slightly towards the
+    /// center of the goal so we don't get hung up on the post itself.
+    fn cover_loc(ctx: &mut Context<'_>) -> Point2<f32> {
+        let goal = ctx.game.own_goal();
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let post = goal.far_post(ball_loc);
+        post + goal.normal_2d.into_inner() * 200.0 + (goal.center_2d - post).normalize() * 150.0
+    }
+}
+
+impl Behavior for FarPostCover {
+    fn name(&self) -> &str {
+        name_of_type!(FarPostCover)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::FarPostCover);
+
+        if ctx.scenario.i_am_first_man() {
+            ctx.eeg.log(self.name(), "I'm first man now");
+            return Action::Return;
+        }
+
+        let cover_loc = Self::cover_loc(ctx);
+        ctx.eeg.draw(Drawable::Crosshair(cover_loc));
+
+        let me = ctx.me();
+        if (cover_loc - me.Physics.loc_2d()).norm() >= 250.0 {
+            return Action::Yield(drive_towards(ctx, cover_loc));
+        }
+
+        // We're in position. Keep facing the ball so we're ready to react.
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: 0.0,
+            Steer: simple_steer_towards(&me.Physics, ball_loc),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::{defense::FarPostCover, strike::FiftyFifty},
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point3, Rotation3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn cover_far_post_while_teammate_challenges() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(1500.0, -1500.0, 92.74),
+                car_loc: Point3::new(-500.0, -1000.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, 0.0),
+                enemy_loc: Point3::new(1500.0, 500.0, 17.01),
+                enemy_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, 0.0),
+                ..Default::default()
+            })
+            .teammate(
+                Point3::new(1500.0, -3000.0, 17.01),
+                Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                FiftyFifty::new(),
+            )
+            .behavior(FarPostCover::new())
+            .run_for_millis(3000);
+
+        assert!(!test.enemy_has_scored());
+
+        // The ball is on the right, so we should be covering the left post.
+        let packet = test.sniff_packet();
+        let car_loc = packet.GameCars[0].Physics.loc_2d();
+        println!("car_loc = {:?}", car_loc);
+        assert!(car_loc.x < 0.0);
+        assert!(car_loc.x > -rl::GOALPOST_X - 200.0);
+        assert!(car_loc.y < -rl::FIELD_MAX_Y + 800.0);
+    }
+}
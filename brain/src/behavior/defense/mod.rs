@@ -1,15 +1,25 @@
 pub use self::{
+    absorb_ball::AbsorbBall,
     defense::{defensive_hit, Defense},
+    demo::Demo,
+    far_post_cover::FarPostCover,
     hit_to_own_corner::HitToOwnCorner,
     panic_defense::PanicDefense,
     push_to_own_corner::PushToOwnCorner,
+    reset_to_net::ResetToNet,
     retreat::Retreat,
+    retreat_facing_play::RetreatFacingPlay,
 };
 
+mod absorb_ball;
 #[allow(clippy::module_inception)]
 mod defense;
+mod demo;
+mod far_post_cover;
 mod hit_to_own_corner;
 mod panic_defense;
 mod push_to_own_corner;
+mod reset_to_net;
 mod retreat;
+mod retreat_facing_play;
 mod retreating_save;
@@ -167,7 +167,7 @@ impl PanicDefense {
             return Some(Phase::Rush {
                 // Powerslide towards the post opposite the one we're driving to.
                 aim_hint: Point2::new(blitz_loc.x.signum() * -2000.0, own_goal.center_2d.y),
-                child: BlitzToLocation::new(blitz_loc),
+                child: BlitzToLocation::new(blitz_loc).urgent(true),
             });
         }
 
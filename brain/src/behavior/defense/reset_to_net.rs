@@ -0,0 +1,114 @@
+use crate::{
+    behavior::{
+        defense::RetreatFacingPlay,
+        higher_order::{Chain, TryChoose},
+        movement::HoldPosition,
+    },
+    eeg::{Drawable, Event},
+    routing::{
+        behavior::FollowRoute,
+        plan::{GetDollar, GroundDrive},
+    },
+    strategy::{positioning::defensive_spot, Action, Behavior, Context, Priority},
+};
+use common::prelude::*;
+use nameof::name_of_type;
+use vec_box::vec_box;
+
+/// Give up on the ball for now. Head back to a spot in front of our net,
+/// either keeping an eye on the play or picking up boost on the way if we need
+/// it, then sit there facing the play.
+/// This is for when we've been out-possessed and chasing would only leave the
+/// net open.
+pub struct ResetToNet;
+
+impl ResetToNet {
+    /// Wait this far out from the center of the goal.
+    const NET_OFFSET: f32 = 800.0;
+
+    /// Below this much boost, grab some on the way home.
+    const WANT_BOOST: i32 = 50;
+
+    /// Drive home from the boost pad slow enough to park without having to
+    /// slam on the brakes.
+    const PARK_SPEED: f32 = 300.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Behavior for ResetToNet {
+    fn name(&self) -> &str {
+        name_of_type!(ResetToNet)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::ResetToNet);
+
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let spot = defensive_spot(ctx.game, ball_loc, Self::NET_OFFSET);
+        ctx.eeg.draw(Drawable::Crosshair(spot));
+
+        if ctx.me().Boost >= Self::WANT_BOOST {
+            return Action::tail_call(Chain::new(Priority::Idle, vec_box![
+                RetreatFacingPlay::new(),
+                HoldPosition::at(spot),
+            ]));
+        }
+
+        ctx.eeg.log(self.name(), "refueling on the way");
+        // If there's no boost to be had, don't let that stop us from going home.
+        let refuel = TryChoose::new(Priority::Idle, vec_box![
+            FollowRoute::new(GetDollar::new(spot).target_face(ball_loc)),
+            HoldPosition::at(spot),
+        ]);
+        Action::tail_call(Chain::new(Priority::Idle, vec_box![
+            refuel,
+            FollowRoute::new(GroundDrive::new(spot).target_end_speed(Self::PARK_SPEED)),
+            HoldPosition::at(spot),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::defense::ResetToNet,
+        eeg::Event,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point2, Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn fall_back_from_lost_midfield_challenge() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                // The enemy is already dribbling the ball upfield, and we're
+                // caught on the wrong side of it with an empty tank.
+                ball_loc: Point3::new(0.0, 200.0, 92.74),
+                ball_vel: Vector3::new(0.0, -300.0, 0.0),
+                car_loc: Point3::new(1500.0, 1000.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                enemy_loc: Point3::new(0.0, 600.0, 17.01),
+                enemy_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, 0.0),
+                enemy_vel: Vector3::new(0.0, -300.0, 0.0),
+                boost: 10,
+                ..Default::default()
+            })
+            .behavior(ResetToNet::new())
+            .run_for_millis(6000);
+
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::ResetToNet));
+        });
+        let packet = test.sniff_packet();
+        let car = &packet.GameCars[0];
+        println!("car_loc = {:?}", car.Physics.loc());
+        println!("boost = {}", car.Boost);
+        assert!((car.Physics.loc_2d() - Point2::new(0.0, -5120.0)).norm() < 1500.0);
+        assert!(car.Boost > 10);
+    }
+}
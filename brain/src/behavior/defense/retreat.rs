@@ -113,6 +113,27 @@ mod integration_tests {
         assert!(ball_loc.x >= 2000.0);
     }
 
+    /// The same play, but replaying the recorded ball path as the prediction,
+    /// so the decisions we make along the way don't depend on the ball sim.
+    #[test]
+    fn last_ditch_intercept_with_recorded_prediction() {
+        let test = TestRunner::new()
+            .one_v_one(&*recordings::LAST_DITCH_INTERCEPT, 275.0)
+            .recorded_ball_prediction(
+                "../brain-test-data/recordings/last_ditch_intercept.csv",
+                275.0,
+            )
+            .starting_boost(0.0)
+            .soccar()
+            .run_for_millis(4000);
+
+        assert!(!test.enemy_has_scored());
+
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::Retreat));
+        });
+    }
+
     #[test]
     fn no_infinite_loop() {
         let start_loc = Point3::new(517.51996, -3536.02, 17.01);
@@ -0,0 +1,154 @@
+use crate::{
+    behavior::{defense::Retreat, movement::simple_steer_towards_avoiding_walls},
+    eeg::{Drawable, Event},
+    strategy::{positioning::defensive_spot, Action, Behavior, Context},
+};
+use common::prelude::*;
+use nalgebra::{clamp, Point2, UnitComplex};
+use nameof::name_of_type;
+use std::f32::consts::PI;
+
+/// Drive back towards our goal without ever turning our back on the ball.
+/// Instead of spinning around and driving straight home, arc back with the
+/// nose angled towards the play, so a sudden shot doesn't catch us facing the
+/// wrong way.
+pub struct RetreatFacingPlay;
+
+impl RetreatFacingPlay {
+    /// Aim to stop this far out from the goal, on the line towards the ball.
+    const GOAL_OFFSET: f32 = 500.0;
+
+    /// We're home once we get this close to the retreat spot.
+    const ARRIVE_DIST: f32 = 300.0;
+
+    /// If a shot comes, we want to be able to face it within this long.
+    const REACT_TIME: f32 = 0.5;
+
+    /// Never point more than this far away from the direction of retreat, or
+    /// we'd hardly be retreating at all.
+    const MAX_FACING_ANGLE: f32 = PI / 2.0;
+
+    /// Turn rates are unreliable at a crawl, so assume at least this speed.
+    const MIN_TURNING_SPEED: f32 = 900.0;
+
+    /// Stop and deal with a shot that would score within this many seconds.
+    const SHOT_HORIZON: f32 = 3.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// How fast we can swing the nose around, in radians per second.
+    fn turn_rate(speed: f32) -> f32 {
+        let speed = speed.max(Self::MIN_TURNING_SPEED);
+        speed * chip::max_curvature(speed)
+    }
+
+    /// How far to angle our heading away from the direction of retreat and
+    /// towards the ball. `ball_angle` is the angle between the two. We want
+    /// to be able to swing the rest of the way around within `REACT_TIME`,
+    /// and otherwise make as much progress towards goal as we can.
+    fn facing_angle(ball_angle: f32, turn_rate: f32) -> f32 {
+        let needed = ball_angle.abs() - turn_rate * Self::REACT_TIME;
+        clamp(needed, 0.0, Self::MAX_FACING_ANGLE) * ball_angle.signum()
+    }
+}
+
+impl Behavior for RetreatFacingPlay {
+    fn name(&self) -> &str {
+        name_of_type!(RetreatFacingPlay)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::RetreatFacingPlay);
+
+        let shot_incoming = ctx
+            .scenario
+            .impending_concede()
+            .map(|f| f.t < Self::SHOT_HORIZON)
+            .unwrap_or_default();
+        if shot_incoming {
+            ctx.eeg.log(self.name(), "shot incoming");
+            return Action::tail_call(Retreat::new());
+        }
+
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let spot = defensive_spot(ctx.game, ball_loc, Self::GOAL_OFFSET);
+        let me = ctx.me();
+        let me_loc = me.Physics.loc_2d();
+        if (spot - me_loc).norm() < Self::ARRIVE_DIST {
+            ctx.eeg.log(self.name(), "made it back");
+            return Action::Return;
+        }
+
+        let retreat_dir = spot - me_loc;
+        let ball_angle = retreat_dir.angle_to(&(ball_loc - me_loc));
+        let turn_rate = Self::turn_rate(me.Physics.vel_2d().norm());
+        let facing_angle = Self::facing_angle(ball_angle, turn_rate);
+        ctx.eeg.print_angle("facing_angle", facing_angle);
+
+        let heading = UnitComplex::new(facing_angle) * retreat_dir.normalize();
+        let target_loc: Point2<f32> = me_loc + heading * 1000.0;
+        ctx.eeg.draw(Drawable::Crosshair(spot));
+        ctx.eeg.draw(Drawable::Crosshair(target_loc));
+
+        // Angling towards the ball can point us into a side wall, so steer clear
+        // of it.
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: 1.0,
+            Steer: simple_steer_towards_avoiding_walls(&me.Physics, target_loc),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::behavior::defense::retreat_facing_play::RetreatFacingPlay;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn head_straight_home_when_ball_is_behind_us() {
+        assert_eq!(RetreatFacingPlay::facing_angle(0.1, 2.0), 0.0);
+    }
+
+    #[test]
+    fn angle_towards_ball_in_front() {
+        let angle = RetreatFacingPlay::facing_angle(PI, 2.0);
+        assert!(angle > 0.0);
+        assert!(angle <= PI / 2.0);
+        let angle = RetreatFacingPlay::facing_angle(-PI * 0.9, 2.0);
+        assert!(angle < 0.0);
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::defense::RetreatFacingPlay,
+        eeg::Event,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use nalgebra::{Point3, Vector3};
+
+    #[test]
+    fn respond_to_shot_mid_retreat() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                // The ball starts out harmless, but it's drifting our way and
+                // it'll turn into a shot before we get home.
+                ball_loc: Point3::new(500.0, 2500.0, 92.74),
+                ball_vel: Vector3::new(0.0, -1300.0, 0.0),
+                car_loc: Point3::new(0.0, 0.0, 17.01),
+                ..Default::default()
+            })
+            .behavior(RetreatFacingPlay::new())
+            .run_for_millis(6000);
+
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::RetreatFacingPlay));
+            assert!(events.contains(&Event::Retreat));
+        });
+        assert!(!test.enemy_has_scored());
+    }
+}
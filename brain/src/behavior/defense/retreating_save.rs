@@ -286,7 +286,7 @@ impl RetreatingSave {
         }
 
         let drive = SimGroundDrive::new(plan.target_loc);
-        let jump = SimJump;
+        let jump = SimJump::new(ctx.game.gravity());
 
         let axis = (plan.target_steer_loc - ctx.me().Physics.loc_2d()).to_axis();
 
@@ -355,7 +355,11 @@ impl RetreatingSave {
             .unwrap();
 
         // Simulate the car motion.
-        let apex = SimJump.simulate(&ctx.me().into(), Self::JUMP_TIME, &ctx.me().Physics.quat());
+        let apex = SimJump::new(ctx.game.gravity()).simulate(
+            &ctx.me().into(),
+            Self::JUMP_TIME,
+            &ctx.me().Physics.quat(),
+        );
 
         (ball, apex)
     }
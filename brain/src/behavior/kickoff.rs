@@ -2,7 +2,9 @@ use crate::{
     behavior::{
         higher_order::{Chain, TimeLimit, While},
         movement::{drive_towards, QuickJumpAndDodge, Yielder},
+        strike::FiftyFifty,
     },
+    eeg::{Drawable, Event},
     routing::{
         behavior::FollowRoute,
         models::RoutePlanner,
@@ -76,6 +78,11 @@ fn wait_for_round_to_begin() -> impl Behavior {
 pub struct Kickoff;
 
 impl Kickoff {
+    /// After the first touch, keep contesting the ball for this long before
+    /// handing control back to the normal strategy. The ball is usually still
+    /// up for grabs right after a kickoff.
+    const FIFTY_FIFTY_TIME: f32 = 1.0;
+
     // This shouldn't be used without `PreKickoff` coming first, so make it private.
     fn new() -> Self {
         Self
@@ -132,35 +139,110 @@ impl Behavior for Kickoff {
 
         Action::tail_call(Chain::new(Priority::Idle, vec![
             Box::new(FollowRoute::new_boxed(approach)),
-            Box::new(KickoffStrike::new(rand4 * 25.0)),
+            Box::new(KickoffStrike::new(rand4 * 25.0).consult_plan(true)),
+            Box::new(TimeLimit::new(Self::FIFTY_FIFTY_TIME, FiftyFifty::new())),
         ]))
     }
 }
 
 fn is_off_center_kickoff(ctx: &mut Context<'_>) -> bool {
-    let car_x = ctx.me().Physics.loc().x;
-    (car_x.abs() - 256.0).abs() < 50.0
+    is_off_center_spawn(ctx.me().Physics.loc_2d())
 }
 
 fn is_diagonal_kickoff(ctx: &mut Context<'_>) -> bool {
-    let car_x = ctx.me().Physics.loc().x;
-    car_x.abs() >= 1000.0
+    is_diagonal_spawn(ctx.me().Physics.loc_2d())
+}
+
+fn is_off_center_spawn(loc: Point2<f32>) -> bool {
+    (loc.x.abs() - 256.0).abs() < 50.0
+}
+
+fn is_diagonal_spawn(loc: Point2<f32>) -> bool {
+    loc.x.abs() >= 1000.0
+}
+
+/// How to play the first touch of a kickoff.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KickoffAction {
+    /// Meet the enemy at the ball and dodge into it.
+    FiftyFifty,
+    /// The enemy will get there well before us, so hang back and pounce on
+    /// whatever comes out.
+    Cheat,
+}
+
+/// Kickoff strategy, as opposed to kickoff mechanics. This decides what to do
+/// with the first touch; `Kickoff` and friends decide how to do it.
+///
+/// Everyone is standing still when the countdown ends, so this only says
+/// anything useful once the enemy has started moving. `KickoffStrike` keeps
+/// asking until it commits.
+pub struct KickoffPlan;
+
+impl KickoffPlan {
+    /// If the enemy is this much closer to the ball than we are, they'll win
+    /// the race by a mile, so don't bother racing.
+    const CHEAT_HEAD_START: f32 = 1000.0;
+
+    pub fn decide(ctx: &mut Context<'_>) -> KickoffAction {
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let me_loc = ctx.me().Physics.loc_2d();
+        let enemy = ctx.scenario.primary_enemy().map(|enemy| {
+            let enemy_loc = enemy.Physics.loc_2d();
+            let closing_speed = enemy
+                .Physics
+                .vel_2d()
+                .dot(&(ball_loc - enemy_loc).to_axis());
+            (enemy_loc, closing_speed)
+        });
+        Self::classify(ball_loc, me_loc, enemy)
+    }
+
+    /// `enemy` is the enemy's location and how fast they're closing on the
+    /// ball, if there is an enemy.
+    fn classify(
+        ball_loc: Point2<f32>,
+        me_loc: Point2<f32>,
+        enemy: Option<(Point2<f32>, f32)>,
+    ) -> KickoffAction {
+        if let Some((enemy_loc, closing_speed)) = enemy {
+            // Unless they're charging at the ball, they're probably faking, so
+            // the ball is ours no matter how far away we are.
+            let committed = closing_speed >= KickoffFake::COMMIT_SPEED;
+            let head_start = (ball_loc - me_loc).norm() - (ball_loc - enemy_loc).norm();
+            if committed && head_start >= Self::CHEAT_HEAD_START {
+                return KickoffAction::Cheat;
+            }
+        }
+
+        KickoffAction::FiftyFifty
+    }
 }
 
 struct KickoffStrike {
     commit_offset: f32,
+    consult_plan: bool,
 }
 
 impl KickoffStrike {
     const JUMP_TIME: f32 = 0.1;
+    const DODGE_ANGLE: f32 = PI / 8.0;
 
     /// `slop` is a random component added to the dodge distance, to keep things
     /// unpredictable.
     pub fn new(slop: f32) -> Self {
         Self {
             commit_offset: slop,
+            consult_plan: false,
         }
     }
+
+    /// Until it's time to dodge, check with `KickoffPlan` each frame, and back
+    /// off into a fake if it says the enemy will beat us there.
+    pub fn consult_plan(mut self, consult_plan: bool) -> Self {
+        self.consult_plan = consult_plan;
+        self
+    }
 }
 
 impl Behavior for KickoffStrike {
@@ -174,6 +256,11 @@ impl Behavior for KickoffStrike {
             return Action::Abort;
         }
 
+        if self.consult_plan && KickoffPlan::decide(ctx) == KickoffAction::Cheat {
+            ctx.eeg.log(self.name(), "the enemy will get there first");
+            return Action::tail_call(KickoffFake::new());
+        }
+
         let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
         let me_loc = ctx.me().Physics.loc_2d();
         let me_to_ball = ball_loc - me_loc;
@@ -213,7 +300,7 @@ impl KickoffStrike {
                 Action::tail_call(
                     QuickJumpAndDodge::new()
                         .jump_time(Self::JUMP_TIME)
-                        .angle(PI / 8.0 * angle.signum()),
+                        .angle(Self::DODGE_ANGLE * angle.signum()),
                 )
             }
             CommitAction::Chip => Action::tail_call(TimeLimit::new(1.0, RoughAngledChip::new())),
@@ -240,6 +327,111 @@ impl KickoffStrike {
     }
 }
 
+/// Pretend to go for the kickoff, and watch what the enemy does. If they
+/// commit to the ball, pull up short and let them have it, so we're goal-side
+/// and ready to pounce on whatever comes out. If they don't commit, take the
+/// free ball.
+pub struct KickoffFake {
+    start_time: Option<f32>,
+    faking: bool,
+}
+
+impl KickoffFake {
+    /// Watch the enemy for this long before deciding whether they're
+    /// committed.
+    const READ_TIME: f32 = 0.4;
+
+    /// If the enemy is closing on the ball at least this fast, they're
+    /// committed.
+    const COMMIT_SPEED: f32 = 1000.0;
+
+    /// When faking, wait this far from the ball, on our side of it.
+    const FAKE_DIST: f32 = 1000.0;
+
+    /// Roughly how quickly the car slows down when braking.
+    const BRAKE_DECEL: f32 = 3500.0;
+
+    pub fn new() -> Self {
+        Self {
+            start_time: None,
+            faking: false,
+        }
+    }
+
+    /// Returns true if the enemy is driving hard at the ball.
+    fn enemy_is_committed(ctx: &mut Context<'_>) -> bool {
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        ctx.enemy_cars().any(|enemy| {
+            let enemy_to_ball = (ball_loc - enemy.Physics.loc_2d()).to_axis();
+            enemy.Physics.vel_2d().dot(&enemy_to_ball) >= Self::COMMIT_SPEED
+        })
+    }
+
+    fn fake(&self, ctx: &mut Context<'_>) -> Action {
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let own_side = ctx.game.own_goal().center_2d.y.signum();
+        let wait_loc = Point2::new(ball_loc.x, ball_loc.y + own_side * Self::FAKE_DIST);
+        ctx.eeg.draw(Drawable::Crosshair(wait_loc));
+
+        let me = ctx.me();
+        let me_to_wait = wait_loc - me.Physics.loc_2d();
+        let speed = me.Physics.vel_2d().dot(&me.Physics.forward_axis_2d());
+        let stopping_dist = speed.max(0.0).powi(2) / (2.0 * Self::BRAKE_DECEL);
+        let throttle = if me_to_wait.norm() < 100.0 {
+            if speed >= 100.0 {
+                -1.0
+            } else {
+                0.0
+            }
+        } else if stopping_dist >= me_to_wait.norm() {
+            -1.0
+        } else {
+            1.0
+        };
+
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: throttle,
+            ..drive_towards(ctx, wait_loc)
+        })
+    }
+}
+
+impl Behavior for KickoffFake {
+    fn name(&self) -> &str {
+        name_of_type!(KickoffFake)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        if !PreKickoff::is_kickoff(&ctx.packet.GameBall) {
+            // The enemy took the ball. We should be goal-side of it by now,
+            // so let the strategy take it from here.
+            return Action::Return;
+        }
+
+        if self.faking {
+            return self.fake(ctx);
+        }
+
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let start_time = *self.start_time.get_or_insert(now);
+        if now - start_time < Self::READ_TIME {
+            // Look like we're going for it.
+            return KickoffStrike::new(0.0).drive(ctx);
+        }
+
+        if Self::enemy_is_committed(ctx) {
+            ctx.eeg.log(self.name(), "enemy is committed; faking");
+            ctx.eeg.track(Event::KickoffFake);
+            self.faking = true;
+            return self.fake(ctx);
+        }
+
+        ctx.eeg
+            .log(self.name(), "enemy is not committed; taking the ball");
+        Action::tail_call(KickoffStrike::new(0.0))
+    }
+}
+
 #[derive(new)]
 struct RoughAngledChip;
 
@@ -263,15 +455,98 @@ enum CommitAction {
     Chip,
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::behavior::kickoff::{KickoffAction, KickoffPlan};
+    use nalgebra::Point2;
+
+    // Blue's spawn locations.
+    fn diagonal() -> Point2<f32> {
+        Point2::new(-2048.0, -2560.0)
+    }
+
+    fn off_center() -> Point2<f32> {
+        Point2::new(-256.0, -3840.0)
+    }
+
+    fn straight() -> Point2<f32> {
+        Point2::new(0.0, -4608.0)
+    }
+
+    /// Classify against an enemy driving straight at the ball.
+    fn classify(me_loc: Point2<f32>, enemy_loc: Point2<f32>) -> KickoffAction {
+        KickoffPlan::classify(Point2::origin(), me_loc, Some((enemy_loc, 1500.0)))
+    }
+
+    fn mirror(loc: Point2<f32>) -> Point2<f32> {
+        Point2::new(-loc.x, -loc.y)
+    }
+
+    #[test]
+    fn diagonal_spawns() {
+        assert_eq!(
+            classify(diagonal(), mirror(diagonal())),
+            KickoffAction::FiftyFifty,
+        );
+    }
+
+    #[test]
+    fn off_center_spawns() {
+        assert_eq!(
+            classify(off_center(), mirror(off_center())),
+            KickoffAction::FiftyFifty,
+        );
+        assert_eq!(
+            classify(off_center(), mirror(diagonal())),
+            KickoffAction::FiftyFifty,
+        );
+    }
+
+    #[test]
+    fn straight_spawns() {
+        assert_eq!(
+            classify(straight(), mirror(straight())),
+            KickoffAction::FiftyFifty,
+        );
+    }
+
+    #[test]
+    fn cheat_when_enemy_has_a_big_head_start() {
+        assert_eq!(
+            classify(straight(), mirror(diagonal())),
+            KickoffAction::Cheat,
+        );
+    }
+
+    #[test]
+    fn take_the_ball_from_an_idle_enemy() {
+        let enemy = Some((mirror(diagonal()), 0.0));
+        assert_eq!(
+            KickoffPlan::classify(Point2::origin(), straight(), enemy),
+            KickoffAction::FiftyFifty,
+        );
+    }
+
+    #[test]
+    fn take_the_ball_from_a_retreating_enemy() {
+        let enemy = Some((mirror(diagonal()), -1500.0));
+        assert_eq!(
+            KickoffPlan::classify(Point2::origin(), straight(), enemy),
+            KickoffAction::FiftyFifty,
+        );
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use crate::{
-        behavior::PreKickoff,
+        behavior::kickoff::{KickoffFake, PreKickoff},
+        eeg::Event,
         integration_tests::{TestRunner, TestScenario},
     };
     use brain_test_data::recordings;
     use common::{prelude::*, rl};
-    use nalgebra::{Point3, Rotation3};
+    use nalgebra::{Point3, Rotation3, Vector3};
     use std::f32::consts::PI;
 
     #[test]
@@ -324,6 +599,37 @@ mod integration_tests {
         assert!(!PreKickoff::is_kickoff(&packet.GameBall));
     }
 
+    #[test]
+    fn fake_against_committed_enemy() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, 0.0, 92.74),
+                car_loc: Point3::new(0.0, -4608.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                enemy_loc: Point3::new(0.0, 3000.0, 17.01),
+                enemy_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, 0.0),
+                enemy_vel: Vector3::new(0.0, -1500.0, 0.0),
+                ..Default::default()
+            })
+            .starting_boost(33.0)
+            .behavior(KickoffFake::new())
+            .run_for_millis(3000);
+
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::KickoffFake));
+        });
+
+        // The enemy knocked the ball towards our side, but we held back, so we
+        // should be between the ball and our goal.
+        let packet = test.sniff_packet();
+        let ball_loc = packet.GameBall.Physics.loc();
+        let car_loc = packet.GameCars[0].Physics.loc();
+        println!("ball_loc = {:?}", ball_loc);
+        println!("car_loc = {:?}", car_loc);
+        assert!(!PreKickoff::is_kickoff(&packet.GameBall));
+        assert!(car_loc.y < ball_loc.y);
+    }
+
     fn extrapolate_ball(
         packet: &common::halfway_house::LiveDataPacket,
         seconds: f32,
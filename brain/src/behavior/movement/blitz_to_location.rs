@@ -1,7 +1,9 @@
 use crate::{
     behavior::{
         higher_order::Chain,
-        movement::{simple_steer_towards, QuickJumpAndDodge, Yielder},
+        movement::{
+            simple_steer_towards, QuickJumpAndDodge, SpeedFlip, SpeedFlipDirection, Yielder,
+        },
     },
     eeg::Drawable,
     strategy::{Action, Behavior, Context},
@@ -14,11 +16,22 @@ use vec_box::vec_box;
 
 pub struct BlitzToLocation {
     target_loc: Point2<f32>,
+    urgent: bool,
 }
 
 impl BlitzToLocation {
     pub fn new(target_loc: Point2<f32>) -> BlitzToLocation {
-        BlitzToLocation { target_loc }
+        BlitzToLocation {
+            target_loc,
+            urgent: false,
+        }
+    }
+
+    /// Set this when racing to a shot or back to our own goal. Urgent blitzes
+    /// spend boost even when we're low and would otherwise conserve it.
+    pub fn urgent(mut self, urgent: bool) -> Self {
+        self.urgent = urgent;
+        self
     }
 }
 
@@ -40,6 +53,28 @@ impl Behavior for BlitzToLocation {
         ));
         ctx.eeg.print_value("distance", Distance(distance));
 
+        // Should we speed flip? From a slow start, it gets us up to speed much
+        // quicker than boosting alone.
+        if me.OnGround
+            && me.Physics.rot().pitch().to_degrees() < 1.0
+            && speed < SpeedFlip::MAX_START_SPEED
+            && steer.abs() < PI / 24.0
+            && me.Boost as f32 >= SpeedFlip::MIN_BOOST
+            && (self.urgent || !ctx.game.conserve_boost())
+        {
+            let end_speed = SpeedFlip::end_speed(speed, me.Boost as f32);
+            // Leave some leeway for recovery, same as with the dodge below.
+            let flip_dist = (speed + end_speed) / 2.0 * SpeedFlip::DURATION * 1.5;
+            if distance > flip_dist {
+                let direction = if steer < 0.0 {
+                    SpeedFlipDirection::Left
+                } else {
+                    SpeedFlipDirection::Right
+                };
+                return Action::tail_call(SpeedFlip::new(direction).priority(self.priority()));
+            }
+        }
+
         // Should we boost?
         if distance > 1000.0
             && me.OnGround
@@ -49,6 +84,7 @@ impl Behavior for BlitzToLocation {
             // losing wheel contact (and thus agility).
             && (speed < 1500.0 || (2000.0 <= speed && speed < rl::CAR_ALMOST_MAX_SPEED))
             && me.Boost > 0
+            && (self.urgent || !ctx.game.conserve_boost())
         {
             return Action::Yield(common::halfway_house::PlayerInput {
                 Throttle: 1.0,
@@ -85,3 +121,41 @@ impl Behavior for BlitzToLocation {
         })
     }
 }
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::movement::BlitzToLocation,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use nalgebra::Point2;
+
+    #[test]
+    fn conserve_low_boost() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                boost: 5,
+                ..Default::default()
+            })
+            .behavior(BlitzToLocation::new(Point2::new(1500.0, 3500.0)))
+            .run_for_millis(1500);
+
+        let packet = test.sniff_packet();
+        assert!(packet.GameCars[0].Boost >= 5);
+        assert!(packet.GameCars[0].Physics.Location.Y >= 500.0);
+    }
+
+    #[test]
+    fn urgent_blitz_spends_low_boost() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                boost: 5,
+                ..Default::default()
+            })
+            .behavior(BlitzToLocation::new(Point2::new(1500.0, 3500.0)).urgent(true))
+            .run_for_millis(1500);
+
+        let packet = test.sniff_packet();
+        assert!(packet.GameCars[0].Boost < 5);
+    }
+}
@@ -79,7 +79,8 @@ impl Behavior for Dodge {
     }
 }
 
-fn towards(car: &common::halfway_house::PlayerInfo, target_loc: Point2<f32>) -> (f32, f32) {
+/// The pitch and yaw inputs that dodge towards `target_loc`.
+pub fn towards(car: &common::halfway_house::PlayerInfo, target_loc: Point2<f32>) -> (f32, f32) {
     let car_loc = car.Physics.loc_2d();
     let car_forward_axis = car.Physics.forward_axis();
 
@@ -5,9 +5,10 @@ use crate::{
     },
     eeg::{color, Drawable},
     helpers::telepathy::predict_enemy_hit_direction,
+    routing::models::CarState,
     strategy::{Action, Behavior, Context, Priority},
 };
-use common::{kinematics::kinematic_time, prelude::*, rl};
+use common::{control::level_out, kinematics::kinematic_time, prelude::*, rl};
 use nalgebra::{Point2, Vector3};
 use nameof::name_of_type;
 use simulate::linear_interpolate;
@@ -16,14 +17,47 @@ use std::f32::consts::PI;
 pub struct GetToFlatGround;
 
 impl GetToFlatGround {
+    /// A front flip lands nose-first, and the car bounces around a bit before
+    /// all four wheels are settled.
+    const DODGE_SETTLE_TIME: f32 = 0.25;
+
+    /// A rough allowance for rolling back onto our wheels if we're tilted.
+    const REORIENT_TIME: f32 = 0.5;
+
+    /// Spinning faster than this (in rad/s) in the air means we're tumbling,
+    /// and need to get that under control before worrying about the landing.
+    const TUMBLE_ANG_VEL: f32 = 3.0;
+
     pub fn new() -> Self {
         Self
     }
 
+    /// Estimate how long it will take, starting from `car`, until we're back
+    /// on flat ground and ready for the next maneuver. Set `landing_from_dodge`
+    /// if `car` is the state at the end of a dodge. `gravity` is the match's,
+    /// from `Game::gravity`.
+    pub fn estimated_recovery_time(car: &CarState, landing_from_dodge: bool, gravity: f32) -> f32 {
+        let fall_dist = car.loc.z - rl::OCTANE_NEUTRAL_Z;
+        let fall_time = if fall_dist > 1.0 {
+            kinematic_time(-fall_dist, car.vel.z, gravity).unwrap_or(0.0)
+        } else {
+            0.0
+        };
+        let reorient_time = if car.on_flat_ground() {
+            0.0
+        } else {
+            Self::REORIENT_TIME
+        };
+        let settle_time = if landing_from_dodge {
+            Self::DODGE_SETTLE_TIME
+        } else {
+            0.0
+        };
+        fall_time + reorient_time + settle_time
+    }
+
     pub fn on_flat_ground(car: &common::halfway_house::PlayerInfo) -> bool {
-        car.OnGround
-            && car.Physics.rot().pitch().abs() < 15.0_f32.to_radians()
-            && car.Physics.rot().roll().abs() < 15.0_f32.to_radians()
+        car.OnGround && CarState::from(car).on_flat_ground()
     }
 }
 
@@ -40,6 +74,22 @@ impl Behavior for GetToFlatGround {
         let me = ctx.me();
 
         if !me.OnGround {
+            if me.Physics.ang_vel().norm() >= Self::TUMBLE_ANG_VEL {
+                ctx.eeg
+                    .draw(Drawable::print("stopping the tumble", color::GREEN));
+                let (pitch, yaw, roll) = level_out(
+                    me.Physics.forward_axis(),
+                    me.Physics.right_axis(),
+                    me.Physics.roof_axis(),
+                    me.Physics.ang_vel(),
+                );
+                return Action::Yield(common::halfway_house::PlayerInput {
+                    Pitch: pitch,
+                    Yaw: yaw,
+                    Roll: roll,
+                    ..Default::default()
+                });
+            }
             return Action::tail_call(Land::new());
         }
 
@@ -268,3 +318,33 @@ mod demo {
             .run_for_millis(3000);
     }
 }
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::movement::GetToFlatGround,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn recover_from_inverted_spin() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                car_loc: Point3::new(0.0, 0.0, 200.0),
+                car_rot: Rotation3::from_unreal_angles(0.0, 0.0, PI),
+                car_ang_vel: Vector3::new(3.0, 2.0, 3.0),
+                ..Default::default()
+            })
+            .behavior(GetToFlatGround::new())
+            .run_for_millis(800);
+
+        let packet = test.sniff_packet();
+        let car = &packet.GameCars[0];
+        println!("roof = {:?}", car.Physics.roof_axis());
+        assert!(car.OnGround);
+        assert!(car.Physics.roof_axis().z >= 0.9);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    behavior::{
+        higher_order::Chain,
+        movement::{LandSmoothly, QuickJumpAndDodge, Yielder},
+    },
+    strategy::Priority,
+};
+use vec_box::vec_box;
+
+/// Turn around in a hurry. Jump, dodge backwards, then cancel the flip halfway
+/// through and roll upright, so we land facing the way we came from, without
+/// losing the dodge's speed.
+pub struct HalfFlip;
+
+impl HalfFlip {
+    /// Each input needs to be held for a few frames to register.
+    const PHASE_TIME: f32 = QuickJumpAndDodge::MIN_PHASE_TIME;
+
+    /// How long to hold the stick forward after the dodge. This stops the
+    /// flip's rotation once the car is about vertical.
+    const CANCEL_TIME: f32 = 0.15;
+
+    /// How long to air roll after the cancel. By then we're close enough to
+    /// upright that `LandSmoothly` can take care of the rest.
+    const ROLL_TIME: f32 = 0.3;
+
+    /// How long until the dodge fires. Up to here, we keep the velocity we
+    /// started with.
+    pub const DODGE_TIME: f32 = Self::PHASE_TIME * 2.0;
+
+    /// How long the scripted inputs last, before `LandSmoothly` takes over.
+    pub const INPUT_TIME: f32 = Self::PHASE_TIME * 3.0 + Self::CANCEL_TIME + Self::ROLL_TIME;
+
+    /// The whole maneuver, from jump to landing.
+    pub fn maneuver() -> Chain {
+        Chain::new(Priority::Force, vec_box![
+            // Jump
+            Yielder::new(Self::PHASE_TIME, common::halfway_house::PlayerInput {
+                Jump: true,
+                ..Default::default()
+            }),
+            Yielder::new(Self::PHASE_TIME, Default::default()),
+            // Dodge backwards
+            Yielder::new(Self::PHASE_TIME, common::halfway_house::PlayerInput {
+                Pitch: 1.0,
+                Jump: true,
+                ..Default::default()
+            }),
+            // Cancel the flip
+            Yielder::new(Self::CANCEL_TIME, common::halfway_house::PlayerInput {
+                Pitch: -1.0,
+                ..Default::default()
+            }),
+            // Roll back onto our wheels
+            Yielder::new(Self::ROLL_TIME, common::halfway_house::PlayerInput {
+                Pitch: -1.0,
+                Roll: 1.0,
+                ..Default::default()
+            }),
+            LandSmoothly::new(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::movement::HalfFlip,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn turn_around() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(3000.0, 3000.0, 92.74),
+                car_loc: Point3::new(0.0, 0.0, 17.01),
+                // Facing away from where we want to go, and reversing towards it.
+                car_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, 0.0),
+                car_vel: Vector3::new(0.0, 500.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(HalfFlip::maneuver())
+            .run_for_millis(1500);
+
+        let packet = test.sniff_packet();
+        let car = &packet.GameCars[0];
+        println!("forward = {:?}", car.Physics.forward_axis());
+        println!("vel = {:?}", car.Physics.vel());
+        assert!(car.OnGround);
+        assert!(car.Physics.forward_axis().y >= 0.9);
+        assert!(car.Physics.vel().y >= 500.0);
+    }
+}
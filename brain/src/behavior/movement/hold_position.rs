@@ -0,0 +1,134 @@
+use crate::{
+    behavior::movement::{drive_towards, simple_yaw_diff},
+    eeg::Drawable,
+    strategy::{Action, Behavior, Context},
+};
+use common::prelude::*;
+use nalgebra::Point2;
+use nameof::name_of_type;
+
+/// Park at a spot and keep the nose pointed at the ball, without spending any
+/// boost. This is what to do when there's nothing better to do. It never
+/// returns on its own.
+pub struct HoldPosition {
+    loc: Point2<f32>,
+}
+
+impl HoldPosition {
+    /// Once we're this close, consider ourselves parked.
+    const ARRIVE_DIST: f32 = 250.0;
+
+    /// If we wander further than this while turning, drive back.
+    const LEASH_DIST: f32 = 500.0;
+
+    /// Close enough to facing the ball.
+    const FACING_TOLERANCE: f32 = 0.2;
+
+    /// Above this speed, stop before doing anything else.
+    const MAX_PARKED_SPEED: f32 = 300.0;
+
+    /// Below this speed, just let the car roll to a stop.
+    const CREEP_SPEED: f32 = 50.0;
+
+    /// Shuffle back and forth this gently while turning in place.
+    const SHUFFLE_THROTTLE: f32 = 0.5;
+
+    pub fn at(loc: Point2<f32>) -> Self {
+        Self { loc }
+    }
+}
+
+impl Behavior for HoldPosition {
+    fn name(&self) -> &str {
+        name_of_type!(HoldPosition)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let me = ctx.me();
+        let me_loc = me.Physics.loc_2d();
+        let dist = (self.loc - me_loc).norm();
+        ctx.eeg.draw(Drawable::Crosshair(self.loc));
+        ctx.eeg.print_distance("hold dist", dist);
+
+        if dist >= Self::LEASH_DIST {
+            return Action::Yield(drive_towards(ctx, self.loc));
+        }
+
+        let forward = me.Physics.forward_axis_2d();
+        let forward_speed = me.Physics.vel_2d().dot(&forward);
+        if forward_speed.abs() >= Self::MAX_PARKED_SPEED && dist < Self::ARRIVE_DIST {
+            return Action::Yield(common::halfway_house::PlayerInput {
+                Throttle: -forward_speed.signum(),
+                ..Default::default()
+            });
+        }
+
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let yaw_diff = simple_yaw_diff(&me.Physics, ball_loc);
+        ctx.eeg.print_angle("hold yaw_diff", yaw_diff);
+        if yaw_diff.abs() < Self::FACING_TOLERANCE {
+            if dist >= Self::ARRIVE_DIST {
+                return Action::Yield(drive_towards(ctx, self.loc));
+            }
+            // Stay put.
+            let throttle = if forward_speed.abs() >= Self::CREEP_SPEED {
+                -forward_speed.signum()
+            } else {
+                0.0
+            };
+            return Action::Yield(common::halfway_house::PlayerInput {
+                Throttle: throttle,
+                ..Default::default()
+            });
+        }
+
+        // Turn in place by shuffling towards the spot and back. Steering is
+        // reversed while reversing, so flip it to keep turning the same way.
+        let direction = if (self.loc - me_loc).dot(&forward) >= 0.0 {
+            1.0
+        } else {
+            -1.0
+        };
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: direction * Self::SHUFFLE_THROTTLE,
+            Steer: direction * yaw_diff.signum(),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::movement::HoldPosition,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point2, Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn hold_spot_facing_ball() {
+        let spot = Point2::new(0.0, -3000.0);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(-2000.0, 0.0, 92.74),
+                ball_vel: Vector3::new(800.0, 0.0, 0.0),
+                car_loc: Point3::new(0.0, -3000.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(HoldPosition::at(spot))
+            .run_for_millis(4000);
+
+        let packet = test.sniff_packet();
+        let car = &packet.GameCars[0];
+        let to_ball = packet.GameBall.Physics.loc_2d() - car.Physics.loc_2d();
+        let facing = car.Physics.forward_axis_2d().angle_to(&to_ball.to_axis());
+        println!("car_loc = {:?}", car.Physics.loc_2d());
+        println!("facing = {:?}", facing);
+        assert!((car.Physics.loc_2d() - spot).norm() < 500.0);
+        assert!(facing.abs() < PI / 6.0);
+        assert_eq!(car.Boost, 100);
+    }
+}
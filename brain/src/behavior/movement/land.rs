@@ -162,7 +162,7 @@ fn face_the_ball(ctx: &mut Context<'_>) -> Vector2<f32> {
 
 /// Simulate car freefall for increasing time intervals and try to find the
 /// first wall we will penetrate.
-fn find_landing_plane<'ctx>(ctx: &mut Context<'ctx>) -> (&'ctx Plane, f32) {
+pub(super) fn find_landing_plane<'ctx>(ctx: &mut Context<'ctx>) -> (&'ctx Plane, f32) {
     // This routine assumes the field is fully convex (or concave I guess, since
     // we're inside it?)
 
@@ -216,7 +216,7 @@ impl Land {
     }
 }
 
-fn will_be_skidding_on_landing(ctx: &mut Context<'_>, plane: &Plane) -> bool {
+pub(super) fn will_be_skidding_on_landing(ctx: &mut Context<'_>, plane: &Plane) -> bool {
     let nose = plane.project_vector(&ctx.me().Physics.forward_axis());
     let momentum = plane.project_vector(&ctx.me().Physics.vel());
     nose.normalize().dot(&momentum.normalize()) < 0.5
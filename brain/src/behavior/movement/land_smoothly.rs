@@ -0,0 +1,182 @@
+use crate::{
+    behavior::movement::{
+        dodge,
+        land::{find_landing_plane, will_be_skidding_on_landing},
+    },
+    eeg::{color, Drawable},
+    strategy::{Action, Behavior, Context},
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Vector3};
+use nameof::name_of_type;
+
+/// Land on our wheels, pointed along our momentum, so we keep as much speed as
+/// possible. Meant to follow an aerial. Unlike `Land`, this never worries
+/// about positioning; it only cares about the landing itself.
+pub struct LandSmoothly {
+    wavedashed: bool,
+    target_loc: Option<Point2<f32>>,
+}
+
+impl LandSmoothly {
+    /// Dodge this long before touching down, so the wheels hit the ground
+    /// during the first frames of the flip.
+    const WAVEDASH_LEAD_TIME: f32 = 0.05;
+
+    /// A wavedash only adds a few hundred uu/s, and past this speed there's
+    /// nothing left to gain.
+    const WAVEDASH_MAX_SPEED: f32 = rl::CAR_MAX_SPEED - 500.0;
+
+    /// The flip expires this long after leaving the ground. (Strictly, after
+    /// the first jump, but the packet doesn't tell us if we jumped.)
+    const FLIP_TIMEOUT: f32 = 1.25;
+
+    pub fn new() -> Self {
+        Self {
+            wavedashed: false,
+            target_loc: None,
+        }
+    }
+
+    /// Angle the wavedash towards `target_loc` instead of straight ahead, to
+    /// redirect while recovering. The landing itself is still lined up with
+    /// our momentum.
+    pub fn towards(mut self, target_loc: Point2<f32>) -> Self {
+        self.target_loc = Some(target_loc);
+        self
+    }
+
+    fn flip_available(ctx: &mut Context<'_>) -> bool {
+        if ctx.me().DoubleJumped {
+            return false;
+        }
+        match ctx.time_since_on_ground() {
+            Some(t) => t < Self::FLIP_TIMEOUT,
+            // We've never touched the ground, so we were put here by state
+            // setting and haven't jumped. The flip never expires in that case.
+            None => true,
+        }
+    }
+}
+
+impl Behavior for LandSmoothly {
+    fn name(&self) -> &str {
+        name_of_type!(LandSmoothly)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let me = ctx.me();
+
+        if me.OnGround {
+            return Action::Return;
+        }
+
+        // Once the dodge starts, the flip is in control. Just wait it out.
+        if self.wavedashed {
+            return Action::Yield(common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                ..Default::default()
+            });
+        }
+
+        let (plane, landing_time) = find_landing_plane(ctx);
+        ctx.eeg.print_time("landing_time", landing_time);
+
+        // Point the nose along our momentum, as projected onto the landing surface.
+        let momentum = plane.project_vector(&me.Physics.vel());
+        let forward = if momentum.norm() >= 100.0 {
+            momentum.to_axis()
+        } else {
+            plane.project_vector(&me.Physics.forward_axis()).to_axis()
+        };
+
+        let aligned = me.Physics.forward_axis().dot(&forward) >= 0.95
+            && me.Physics.roof_axis().dot(&plane.normal) >= 0.95;
+        let wavedash = Self::flip_available(ctx)
+            && aligned
+            && plane.normal.dot(&Vector3::z()) >= 0.95
+            && landing_time < Self::WAVEDASH_LEAD_TIME
+            && me.Physics.vel_2d().norm() < Self::WAVEDASH_MAX_SPEED;
+        if wavedash {
+            ctx.eeg.log(self.name(), "wavedash");
+            self.wavedashed = true;
+            let (pitch, yaw) = match self.target_loc {
+                Some(target_loc) => dodge::towards(me, target_loc),
+                None => (-1.0, 0.0),
+            };
+            return Action::Yield(common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                Pitch: pitch,
+                Yaw: yaw,
+                Jump: true,
+                ..Default::default()
+            });
+        }
+
+        ctx.eeg
+            .draw(Drawable::print("lining up landing", color::GREEN));
+
+        let (pitch, yaw, roll) = dom::get_pitch_yaw_roll(me, forward, plane.normal);
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: 1.0,
+            Pitch: pitch,
+            Yaw: yaw,
+            Roll: roll,
+            Handbrake: will_be_skidding_on_landing(ctx, plane),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::movement::LandSmoothly,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point2, Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn keep_speed_after_modest_aerial() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(-3000.0, 3000.0, 92.74),
+                car_loc: Point3::new(0.0, -1000.0, 500.0),
+                // Nose up and twisted sideways, like after going up for a ball.
+                car_rot: Rotation3::from_unreal_angles(0.7, 1.0, 0.3),
+                car_vel: Vector3::new(0.0, 1200.0, 200.0),
+                ..Default::default()
+            })
+            .behavior(LandSmoothly::new())
+            .run_for_millis(1800);
+
+        let packet = test.sniff_packet();
+        let car = &packet.GameCars[0];
+        println!("vel = {:?}", car.Physics.vel());
+        assert!(car.OnGround);
+        assert!(car.Physics.vel_2d().norm() >= 1100.0);
+    }
+
+    #[test]
+    fn wavedash_towards_target() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(-3000.0, 3000.0, 92.74),
+                // Coming down from a hop, with the target off to the side.
+                car_loc: Point3::new(0.0, 0.0, 300.0),
+                car_vel: Vector3::new(0.0, 800.0, 0.0),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(LandSmoothly::new().towards(Point2::new(2000.0, 2000.0)))
+            .run_for_millis(1500);
+
+        let packet = test.sniff_packet();
+        let car = &packet.GameCars[0];
+        println!("vel = {:?}", car.Physics.vel());
+        assert!(car.OnGround);
+        assert!(car.Physics.vel().x >= 200.0);
+    }
+}
@@ -3,11 +3,18 @@ pub use self::{
     dodge::Dodge,
     drive_towards::{drive_towards, DriveTowards},
     get_to_flat_ground::GetToFlatGround,
+    half_flip::HalfFlip,
+    hold_position::HoldPosition,
     jump_and_turn::JumpAndTurn,
     land::Land,
+    land_smoothly::LandSmoothly,
     quick_jump_and_dodge::QuickJumpAndDodge,
-    simple_steer_towards::{simple_steer_towards, simple_yaw_diff},
+    simple_steer_towards::{
+        simple_steer_towards, simple_steer_towards_avoiding_walls, simple_yaw_diff,
+    },
     skid_recover::SkidRecover,
+    speed_flip::{SpeedFlip, SpeedFlipDirection},
+    unstick::Unstick,
     yielder::Yielder,
 };
 
@@ -17,11 +24,16 @@ mod blitz_to_location;
 mod dodge;
 mod drive_towards;
 mod get_to_flat_ground;
+mod half_flip;
+mod hold_position;
 mod jump_and_turn;
 mod land;
+mod land_smoothly;
 mod quick_jump_and_dodge;
 mod simple_steer_towards;
 mod skid_recover;
+mod speed_flip;
+mod unstick;
 #[cfg(test)]
 mod wall_drive;
 mod yielder;
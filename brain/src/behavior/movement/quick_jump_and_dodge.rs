@@ -22,7 +22,7 @@ enum Phase {
 }
 
 impl QuickJumpAndDodge {
-    const MIN_PHASE_TIME: f32 = 0.05;
+    pub const MIN_PHASE_TIME: f32 = 0.05;
     const MIN_DODGE_TIME: f32 = Self::MIN_PHASE_TIME * 2.0;
     const FOLLOW_THROUGH_TIME: f32 = 0.5;
 
@@ -1,16 +1,94 @@
 use crate::utils::geometry::ExtendF32;
-use common::prelude::*;
-use nalgebra::Point2;
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Vector2};
 
 pub fn simple_steer_towards(car: &common::halfway_house::Physics, target_loc: Point2<f32>) -> f32 {
     simple_yaw_diff(car, target_loc).max(-1.0).min(1.0) * 2.0
 }
 
+/// Like `simple_steer_towards`, but when we're close to a side or back wall,
+/// bias the steering away from it, so we don't end up scraping along the wall
+/// or pinning ourselves against it.
+pub fn simple_steer_towards_avoiding_walls(
+    car: &common::halfway_house::Physics,
+    target_loc: Point2<f32>,
+) -> f32 {
+    // Start nudging when the car's center is this close to a wall.
+    const WALL_MARGIN: f32 = 300.0;
+    // How hard to nudge, right up against the wall.
+    const MAX_BIAS: f32 = 0.5;
+
+    let steer = simple_steer_towards(car, target_loc);
+
+    let loc = car.loc_2d();
+    let walls = [
+        (
+            rl::FIELD_MAX_X - loc.x.abs(),
+            Vector2::new(-loc.x.signum(), 0.0),
+        ),
+        (
+            rl::FIELD_MAX_Y - loc.y.abs(),
+            Vector2::new(0.0, -loc.y.signum()),
+        ),
+    ];
+    let bias: f32 = walls
+        .iter()
+        .filter(|(dist, _)| *dist < WALL_MARGIN)
+        .map(|(dist, inward)| {
+            let closeness = 1.0 - dist.max(0.0) / WALL_MARGIN;
+            let away = simple_yaw_diff(car, loc + inward * 1000.0).signum();
+            away * closeness * MAX_BIAS
+        })
+        .sum();
+    steer + bias
+}
+
 pub fn simple_yaw_diff(car: &common::halfway_house::Physics, target_loc: Point2<f32>) -> f32 {
     let target_yaw = car.loc_2d().negated_difference_and_angle_to(target_loc);
     (target_yaw - car.rot().yaw()).normalize_angle()
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::behavior::movement::simple_steer_towards::{
+        simple_steer_towards, simple_steer_towards_avoiding_walls,
+    };
+    use common::{halfway_house::Physics, rl};
+    use nalgebra::Point2;
+    use std::f32::consts::PI;
+
+    fn car(x: f32, y: f32, yaw: f32) -> Physics {
+        let mut car = Physics::default();
+        car.Location.X = x;
+        car.Location.Y = y;
+        car.Location.Z = rl::OCTANE_NEUTRAL_Z;
+        car.Rotation.Yaw = yaw;
+        car
+    }
+
+    #[test]
+    fn nudge_away_from_side_wall() {
+        // Driving up along the right side wall, towards a target straight ahead.
+        let car = car(rl::FIELD_MAX_X - 100.0, 0.0, PI / 2.0);
+        let target = Point2::new(rl::FIELD_MAX_X - 100.0, 3000.0);
+        let away = Point2::new(0.0, 1000.0);
+        assert!(simple_steer_towards(&car, target).abs() < 0.01);
+        let steer = simple_steer_towards_avoiding_walls(&car, target);
+        assert!(steer.abs() >= 0.1);
+        assert_eq!(steer.signum(), simple_steer_towards(&car, away).signum());
+    }
+
+    #[test]
+    fn no_nudge_away_from_walls() {
+        let car = car(0.0, 0.0, PI / 2.0);
+        let target = Point2::new(500.0, 3000.0);
+        assert_eq!(
+            simple_steer_towards_avoiding_walls(&car, target),
+            simple_steer_towards(&car, target),
+        );
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use crate::{
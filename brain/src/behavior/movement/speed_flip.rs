@@ -0,0 +1,194 @@
+use crate::{
+    behavior::{
+        higher_order::Chain,
+        movement::{LandSmoothly, QuickJumpAndDodge, Yielder},
+    },
+    strategy::{Action, Behavior, Context, Priority},
+};
+use common::rl;
+use nameof::name_of_type;
+use simulate::Car1D;
+use vec_box::vec_box;
+
+/// Get up to speed in a hurry. Boost the whole time, dodge diagonally forward,
+/// then cancel the flip and air roll the other way so we land on our wheels
+/// still facing forward. The dodge's impulse comes without the usual long
+/// somersault.
+pub struct SpeedFlip {
+    direction: SpeedFlipDirection,
+    priority: Priority,
+}
+
+/// Which way to dodge. The car lands facing about the same way regardless.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SpeedFlipDirection {
+    Left,
+    Right,
+}
+
+impl SpeedFlipDirection {
+    fn signum(self) -> f32 {
+        match self {
+            SpeedFlipDirection::Left => -1.0,
+            SpeedFlipDirection::Right => 1.0,
+        }
+    }
+}
+
+impl SpeedFlip {
+    /// Each input needs to be held for a few frames to register.
+    const PHASE_TIME: f32 = QuickJumpAndDodge::MIN_PHASE_TIME;
+
+    /// How long to hold the cancel. This stops the flip's rotation and levels
+    /// the car back out.
+    const CANCEL_TIME: f32 = 0.5;
+
+    /// How long until the dodge fires.
+    const DODGE_TIME: f32 = Self::PHASE_TIME * 2.0;
+
+    /// The time from the jump until we're back on our wheels. This is the
+    /// scripted inputs plus a rough allowance for the fall afterwards.
+    pub const DURATION: f32 = Self::PHASE_TIME * 3.0 + Self::CANCEL_TIME + 0.2;
+
+    /// Don't bother above this speed. There's not much left to gain, and we'd
+    /// rather keep our wheels on the ground.
+    pub const MAX_START_SPEED: f32 = 1000.0;
+
+    /// The flip boosts the whole way through, and it's not worth doing if the
+    /// boost runs out halfway.
+    pub const MIN_BOOST: f32 = Self::DURATION * rl::BOOST_DEPLETION;
+
+    pub fn new(direction: SpeedFlipDirection) -> Self {
+        Self {
+            direction,
+            priority: Priority::Idle,
+        }
+    }
+
+    /// Run the maneuver at the caller's priority, so anything that could
+    /// preempt the caller can still preempt the flip.
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Returns our speed once we've landed. This treats the time in the air
+    /// like boosting on the ground, plus the dodge's impulse.
+    pub fn end_speed(start_speed: f32, boost: f32) -> f32 {
+        let mut car = Car1D::new().with_speed(start_speed).with_boost(boost);
+        car.multi_step(Self::DODGE_TIME, rl::PHYSICS_DT, 1.0, true);
+        let dodge_speed = (car.speed() + rl::DODGE_FORWARD_IMPULSE).min(rl::CAR_MAX_SPEED);
+        let mut car = Car1D::new().with_speed(dodge_speed).with_boost(car.boost());
+        car.multi_step(Self::DURATION - Self::DODGE_TIME, rl::PHYSICS_DT, 1.0, true);
+        car.speed()
+    }
+
+    /// The whole maneuver, from jump to landing.
+    pub fn maneuver(direction: SpeedFlipDirection, priority: Priority) -> Chain {
+        let side = direction.signum();
+        Chain::new(priority, vec_box![
+            // Jump
+            Yielder::new(Self::PHASE_TIME, common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                Jump: true,
+                Boost: true,
+                ..Default::default()
+            }),
+            Yielder::new(Self::PHASE_TIME, common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                Boost: true,
+                ..Default::default()
+            }),
+            // Dodge diagonally forward
+            Yielder::new(Self::PHASE_TIME, common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                Pitch: -1.0,
+                Yaw: side,
+                Jump: true,
+                Boost: true,
+                ..Default::default()
+            }),
+            // Cancel the flip, and roll against it to stay level
+            Yielder::new(Self::CANCEL_TIME, common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                Pitch: 1.0,
+                Roll: -side,
+                Boost: true,
+                ..Default::default()
+            }),
+            LandSmoothly::new(),
+        ])
+    }
+}
+
+impl Behavior for SpeedFlip {
+    fn name(&self) -> &str {
+        name_of_type!(SpeedFlip)
+    }
+
+    fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        if !ctx.me().OnGround {
+            ctx.eeg.log(self.name(), "wheels must be on ground");
+            return Action::Abort;
+        }
+
+        Action::tail_call(Self::maneuver(self.direction, self.priority))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::behavior::movement::SpeedFlip;
+    use common::rl;
+    use simulate::Car1D;
+
+    #[test]
+    fn faster_than_boosting() {
+        let mut boosting = Car1D::new().with_speed(0.0).with_boost(100.0);
+        boosting.multi_step(SpeedFlip::DURATION, rl::PHYSICS_DT, 1.0, true);
+        let flipped = SpeedFlip::end_speed(0.0, 100.0);
+        assert!(flipped >= boosting.speed() + 200.0);
+    }
+
+    #[test]
+    fn supersonic_soon_after_landing() {
+        let mut car = Car1D::new()
+            .with_speed(SpeedFlip::end_speed(0.0, 100.0))
+            .with_boost(100.0 - SpeedFlip::MIN_BOOST);
+        car.multi_step(2.5 - SpeedFlip::DURATION, rl::PHYSICS_DT, 1.0, true);
+        assert!(car.speed() >= rl::CAR_ALMOST_MAX_SPEED);
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::movement::BlitzToLocation,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point2, Point3};
+
+    #[test]
+    fn speed_flip_from_a_standstill() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                // Keep the ball out of the way.
+                ball_loc: Point3::new(3000.0, 0.0, 92.74),
+                car_loc: Point3::new(0.0, -4000.0, 17.01),
+                boost: 100,
+                ..Default::default()
+            })
+            .behavior(BlitzToLocation::new(Point2::new(0.0, 4000.0)))
+            .run_for_millis(2500);
+
+        let packet = test.sniff_packet();
+        let speed = packet.GameCars[0].Physics.vel().norm();
+        println!("speed = {}", speed);
+        assert!(speed >= rl::CAR_ALMOST_MAX_SPEED);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    behavior::movement::simple_steer_towards,
+    eeg::Event,
+    strategy::{Action, Behavior, Context},
+};
+use common::prelude::*;
+use nalgebra::Point2;
+use nameof::name_of_type;
+
+/// Back away from a wall we've driven into, swinging the nose around towards
+/// the middle of the field on the way out.
+pub struct Unstick {
+    start: Option<f32>,
+}
+
+impl Unstick {
+    /// We're free once we're this far from the wall, and no longer pointed
+    /// into it.
+    const CLEARANCE: f32 = 300.0;
+    const MAX_FACING: f32 = -0.3;
+
+    /// Give up after this long, in case something else is pinning us.
+    const MAX_TIME: f32 = 2.0;
+
+    pub fn new() -> Self {
+        Self { start: None }
+    }
+}
+
+impl Behavior for Unstick {
+    fn name(&self) -> &str {
+        name_of_type!(Unstick)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::Unstick);
+
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let start = *self.start.get_or_insert(now);
+        if now - start >= Self::MAX_TIME {
+            ctx.eeg.log(self.name(), "giving up");
+            return Action::Return;
+        }
+
+        let me = ctx.me();
+        let me_loc = me.Physics.loc();
+        let wall = ctx.game.pitch().closest_wall(&me_loc);
+        let wall_dist = wall.distance_to_point(&me_loc);
+        let facing = me.Physics.forward_axis().dot(&wall.normal);
+        ctx.eeg.print_distance("wall_dist", wall_dist);
+        if wall_dist >= Self::CLEARANCE && facing >= Self::MAX_FACING {
+            ctx.eeg.log(self.name(), "free");
+            return Action::Return;
+        }
+
+        // Steering is reversed while reversing, so flip it to swing the nose
+        // towards the target.
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: -1.0,
+            Steer: -simple_steer_towards(&me.Physics, Point2::origin()),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::movement::Unstick,
+        eeg::Event,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point3, Rotation3};
+
+    #[test]
+    fn back_out_of_side_wall() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                car_loc: Point3::new(rl::FIELD_MAX_X - 100.0, 0.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, 0.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(Unstick::new())
+            .run_for_millis(2000);
+
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::Unstick));
+        });
+        let packet = test.sniff_packet();
+        let car = &packet.GameCars[0];
+        println!("car_loc = {:?}", car.Physics.loc());
+        println!("forward = {:?}", car.Physics.forward_axis());
+        assert!(car.Physics.loc().x < rl::FIELD_MAX_X - 300.0);
+        assert!(car.Physics.forward_axis().x < 0.3);
+    }
+}
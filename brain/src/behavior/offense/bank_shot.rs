@@ -0,0 +1,169 @@
+use crate::{
+    behavior::{
+        higher_order::Chain,
+        offense::Shoot,
+        strike::{GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust},
+    },
+    helpers::intercept::{naive_ground_intercept_2, NaiveIntercept},
+    routing::{behavior::FollowRoute, plan::GroundIntercept},
+    strategy::{Action, Behavior, Context, Game, Priority, Scenario},
+    utils::geometry::Plane,
+};
+use common::prelude::*;
+use nalgebra::{Point2, Point3, Unit, Vector3};
+use nameof::name_of_type;
+use std::f32::consts::PI;
+
+/// Shoot the ball off the side wall and into the enemy goal, for when the
+/// direct angle is no good.
+pub struct BankShot;
+
+impl BankShot {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns a bank off the nearest side wall, but only if a direct shot is
+    /// not viable, and the carom would actually end up in the goal.
+    pub fn viable_bank(
+        game: &Game<'_>,
+        car_loc: Point3<f32>,
+        ball_loc: Point3<f32>,
+    ) -> Option<Bank> {
+        if ball_loc.z >= GroundedHitTarget::MAX_BALL_Z {
+            return None;
+        }
+
+        if Shoot::viable_shot(game, car_loc, ball_loc).is_some() {
+            return None;
+        }
+
+        let goal = game.enemy_goal();
+        let ball_loc = ball_loc.to_2d();
+
+        // The plane that the ball's center touches as it bounces off the wall.
+        let side = ball_loc.x.signum();
+        let wall_x = side * (game.field_max_x() - game.ball_radius());
+        let wall = Plane::point_normal(
+            Point3::new(wall_x, 0.0, 0.0),
+            Unit::new_unchecked(Vector3::new(-side, 0.0, 0.0)),
+        );
+
+        // Don't try to bank if we're already against the wall.
+        if (wall_x - ball_loc.x).abs() < 500.0 {
+            return None;
+        }
+
+        // Mirror the goal across the wall, then aim at the mirror image. The point
+        // where that line crosses the wall is where the ball needs to bounce.
+        let mirrored_goal = Point2::new(2.0 * wall_x - goal.center_2d.x, goal.center_2d.y);
+        let to_mirror = mirrored_goal - ball_loc;
+        let wall_loc = ball_loc + to_mirror * ((wall_x - ball_loc.x) / to_mirror.x);
+
+        // Stay away from the corners, where the wall curves and the bounce is
+        // anyone's guess.
+        if wall_loc.y.abs() >= game.field_max_y() - 1000.0 {
+            return None;
+        }
+
+        // Follow the carom to the goal line and make sure it goes in.
+        let incoming = (wall_loc - ball_loc).to_3d(0.0);
+        let outgoing = wall.reflect_vector(&incoming).to_2d();
+        if outgoing.y * goal.normal_2d.y >= 0.0 {
+            return None;
+        }
+        let goal_line_loc = wall_loc + outgoing * ((goal.center_2d.y - wall_loc.y) / outgoing.y);
+        if goal_line_loc.x.abs() >= goal.max_x * 0.5 {
+            return None;
+        }
+        if outgoing.to_axis().angle_to(&-goal.normal_2d).abs() >= PI * (5.0 / 12.0) {
+            return None;
+        }
+
+        // The angle from the car to the wall needs to be hittable.
+        let car_to_ball = ball_loc - car_loc.to_2d();
+        if car_to_ball.angle_to(&(wall_loc - ball_loc)).abs() >= 45.0_f32.to_radians() {
+            return None;
+        }
+
+        Some(Bank { wall_loc })
+    }
+
+    fn aim(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
+        match Self::aim_calc(ctx.game, ctx.scenario, ctx.car) {
+            // The carom is only as good as the angle it leaves our bumper at, so
+            // make sure we push all the way through the ball.
+            Some(i) => Ok(GroundedHitTarget::new(
+                i.time,
+                GroundedHitTargetAdjust::RoughAim,
+                i.data.wall_loc,
+            )
+            .strike_through(ctx.game)),
+            None => Err(()),
+        }
+    }
+
+    pub fn aim_calc(
+        game: &Game<'_>,
+        scenario: &Scenario<'_>,
+        car: &common::halfway_house::PlayerInfo,
+    ) -> Option<NaiveIntercept<Bank>> {
+        naive_ground_intercept_2(&car.into(), scenario.ball_prediction(), |ball| {
+            Self::viable_bank(game, car.Physics.loc(), ball.loc)
+        })
+    }
+}
+
+pub struct Bank {
+    wall_loc: Point2<f32>,
+}
+
+impl Behavior for BankShot {
+    fn name(&self) -> &str {
+        name_of_type!(BankShot)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let intercept = match Self::aim_calc(ctx.game, &ctx.scenario, ctx.me()) {
+            Some(intercept) => intercept,
+            None => {
+                ctx.eeg.log(self.name(), "no viable bank");
+                return Action::Abort;
+            }
+        };
+
+        // As in `viable_bank`, we can't send the ball towards the wall if we
+        // come at it from any wider an angle than this.
+        let bank_dir = (intercept.data.wall_loc - intercept.ball_loc.to_2d()).to_axis();
+        let intercept = GroundIntercept::new().with_approach_cone(bank_dir, PI / 4.0);
+
+        Action::tail_call(Chain::new(Priority::Strike, vec![
+            Box::new(FollowRoute::new(intercept).same_ball_trajectory(true)),
+            Box::new(GroundedHit::hit_towards(Self::aim)),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::offense::BankShot,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use nalgebra::{Point3, Rotation3};
+
+    #[test]
+    fn bank_off_side_wall_from_tight_angle() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(3000.0, 3000.0, 92.74),
+                car_loc: Point3::new(1000.0, 2500.0, 17.01),
+                car_rot: Rotation3::identity(),
+                ..Default::default()
+            })
+            .behavior(BankShot::new())
+            .run_for_millis(5000);
+
+        assert!(test.has_scored());
+    }
+}
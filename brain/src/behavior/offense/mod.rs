@@ -1,12 +1,18 @@
 pub use self::{
-    offense::Offense, reset_behind_ball::ResetBehindBall, shoot::Shoot, tepid_hit::TepidHit,
+    bank_shot::BankShot, nudge_to_teammate::NudgeToTeammate, offense::Offense,
+    receive_pass::ReceivePass, reset_behind_ball::ResetBehindBall, shoot::Shoot,
+    tepid_hit::TepidHit, wall_shepherd::WallShepherd,
 };
 
+mod bank_shot;
 mod bounce_dribble;
+mod nudge_to_teammate;
 #[allow(clippy::module_inception)]
 mod offense;
+mod receive_pass;
 mod regroup;
 mod reset_behind_ball;
 mod shoot;
 mod side_wall_self_pass;
 mod tepid_hit;
+mod wall_shepherd;
@@ -0,0 +1,143 @@
+use crate::{
+    behavior::{
+        defense::FarPostCover,
+        higher_order::Chain,
+        strike::{GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust},
+    },
+    eeg::Event,
+    routing::{behavior::FollowRoute, plan::GroundIntercept},
+    strategy::{Action, Behavior, Context, Game, Priority, Scenario},
+};
+use common::prelude::*;
+use nalgebra::Point2;
+use nameof::name_of_type;
+use ordered_float::NotNan;
+use std::ptr;
+
+/// Push the ball softly into the path of a teammate who is further upfield,
+/// then fall back and cover the far post while they take it from there.
+pub struct NudgeToTeammate;
+
+impl NudgeToTeammate {
+    /// How far ahead of the teammate to put the ball, in seconds of their
+    /// current velocity.
+    const LEAD_TIME: f32 = 1.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns true if we have the ball well under control, and there's a
+    /// teammate upfield to give it to.
+    pub fn applicable(ctx: &mut Context<'_>) -> bool {
+        if ctx.scenario.possession() < Scenario::POSSESSION_CONTESTABLE {
+            return false;
+        }
+        if !ctx.scenario.i_am_first_man() {
+            return false;
+        }
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        Self::pass_target(ctx.game, ctx.me(), ball_loc).is_some()
+    }
+
+    /// Find the spot just ahead of the teammate who is closest to the enemy
+    /// goal, as long as they're further upfield than the ball.
+    fn pass_target(
+        game: &Game<'_>,
+        me: &common::halfway_house::PlayerInfo,
+        ball_loc: Point2<f32>,
+    ) -> Option<Point2<f32>> {
+        let goal = game.enemy_goal();
+        let teammate = game
+            .cars(game.team)
+            .filter(|&car| !ptr::eq(car, me))
+            .min_by_key(|car| {
+                NotNan::new((car.Physics.loc_2d() - goal.center_2d).norm()).unwrap()
+            })?;
+
+        let teammate_loc = teammate.Physics.loc_2d();
+        let ball_to_goal = (goal.center_2d - ball_loc).norm();
+        if (goal.center_2d - teammate_loc).norm() >= ball_to_goal - 1000.0 {
+            return None;
+        }
+
+        Some(teammate_loc + teammate.Physics.vel_2d() * Self::LEAD_TIME)
+    }
+
+    fn aim(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
+        let ball_loc = ctx.intercept_ball_loc.to_2d();
+        let target_loc = Self::pass_target(ctx.game, ctx.car, ball_loc).ok_or(())?;
+        Ok(GroundedHitTarget::new(
+            ctx.intercept_time,
+            GroundedHitTargetAdjust::RoughAim,
+            target_loc,
+        )
+        .jump(false)
+        .dodge(false))
+    }
+}
+
+impl Behavior for NudgeToTeammate {
+    fn name(&self) -> &str {
+        name_of_type!(NudgeToTeammate)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::NudgeToTeammate);
+
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        if Self::pass_target(ctx.game, ctx.me(), ball_loc).is_none() {
+            ctx.eeg.log(self.name(), "no teammate upfield");
+            return Action::Abort;
+        }
+
+        Action::tail_call(Chain::new(Priority::Strike, vec![
+            Box::new(FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true)),
+            Box::new(GroundedHit::hit_towards(Self::aim)),
+            Box::new(FarPostCover::new()),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::{higher_order::NullBehavior, offense::NudgeToTeammate},
+        eeg::Event,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point3, Rotation3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn nudge_then_rotate() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, 0.0, 92.74),
+                car_loc: Point3::new(0.0, -1500.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                enemy_loc: Point3::new(3000.0, 4000.0, 17.01),
+                ..Default::default()
+            })
+            .teammate(
+                Point3::new(-1000.0, 2500.0, 17.01),
+                Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                NullBehavior::new(),
+            )
+            .behavior(NudgeToTeammate::new())
+            .run_for_millis(4000);
+
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::NudgeToTeammate));
+            assert!(events.contains(&Event::FarPostCover));
+        });
+
+        // The ball should have gone the teammate's way, and we should have
+        // dropped back to defend.
+        let packet = test.sniff_packet();
+        assert!(packet.GameBall.Physics.loc_2d().x < 0.0);
+        assert!(packet.GameBall.Physics.loc_2d().y >= 1000.0);
+        assert!(packet.GameCars[0].Physics.loc_2d().y < 0.0);
+    }
+}
@@ -1,5 +1,10 @@
 use crate::{
-    behavior::offense::{ResetBehindBall, Shoot, TepidHit},
+    behavior::{
+        offense::{
+            BankShot, NudgeToTeammate, ReceivePass, ResetBehindBall, Shoot, TepidHit, WallShepherd,
+        },
+        strike::GroundedHitTarget,
+    },
     eeg::Event,
     helpers::{ball::BallFrame, intercept::naive_ground_intercept_2},
     routing::{behavior::FollowRoute, models::CarState, plan::GetDollar},
@@ -12,11 +17,22 @@ use nameof::name_of_type;
 use simulate::linear_interpolate;
 use std::f32::consts::PI;
 
-pub struct Offense;
+pub struct Offense {
+    max_shot_z: f32,
+}
 
 impl Offense {
     pub fn new() -> Self {
-        Self
+        Self {
+            max_shot_z: GroundedHitTarget::MAX_BALL_Z,
+        }
+    }
+
+    /// The highest ball we're willing to go up for when shooting. See
+    /// `Shoot::max_ball_z`.
+    pub fn max_shot_z(mut self, max_shot_z: f32) -> Self {
+        self.max_shot_z = max_shot_z;
+        self
     }
 }
 
@@ -28,12 +44,18 @@ impl Behavior for Offense {
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
         ctx.eeg.track(Event::Offense);
 
-        if can_we_shoot(ctx) {
+        if ReceivePass::should_receive(ctx) {
+            ctx.eeg
+                .log(self.name(), "teammate is first to the ball; waiting for a pass");
+            return Action::tail_call(ReceivePass::new());
+        }
+
+        if can_we_shoot(ctx, self.max_shot_z) {
             ctx.eeg.log(self.name(), "taking the shot!");
             ctx.quick_chat(0.05, &[
                 rlbot::flat::QuickChatSelection::Information_Incoming,
             ]);
-            return Action::tail_call(Shoot::new());
+            return Action::tail_call(Shoot::new().max_ball_z(self.max_shot_z));
         }
 
         // TODO: if angle is almost good, slightly adjust path such that good_angle
@@ -42,6 +64,18 @@ impl Behavior for Offense {
         // TODO: otherwise drive to a point where me.y < ball.y, then slam the ball
         // sideways
 
+        if NudgeToTeammate::applicable(ctx) {
+            ctx.eeg
+                .log(self.name(), "teammate is upfield; nudging it to them");
+            return Action::tail_call(NudgeToTeammate::new());
+        }
+
+        if WallShepherd::applicable(ctx) {
+            ctx.eeg
+                .log(self.name(), "ball is on the wall; shepherding it upfield");
+            return Action::tail_call(WallShepherd::new());
+        }
+
         if let Some(action) = slow_play(ctx) {
             ctx.quick_chat(0.01, &[rlbot::flat::QuickChatSelection::Information_IGotIt]);
             return action;
@@ -59,13 +93,22 @@ impl Behavior for Offense {
             return action;
         }
 
+        if BankShot::aim_calc(ctx.game, &ctx.scenario, ctx.me()).is_some() {
+            ctx.eeg.log(self.name(), "no direct shot; banking off the wall");
+            return Action::tail_call(BankShot::new());
+        }
+
         ctx.eeg
             .log(self.name(), "no good hit; going for a tepid hit");
         Action::tail_call(TepidHit::new())
     }
 }
 
-fn can_we_shoot(ctx: &mut Context<'_>) -> bool {
+fn can_we_shoot(ctx: &mut Context<'_>, max_shot_z: f32) -> bool {
+    // Below this, we'd mostly just be feeding the enemy keeper. See
+    // `Scenario::shot_quality`.
+    const MIN_SHOT_QUALITY: f32 = 0.1;
+
     let me = ctx.me();
 
     if playing_goalie(ctx.game, ctx.scenario.ball_prediction().start()) {
@@ -89,7 +132,12 @@ fn can_we_shoot(ctx: &mut Context<'_>) -> bool {
     let shoot_intercept = naive_ground_intercept_2(
         &me.into(),
         ctx.scenario.ball_prediction().iter_step_by(0.125),
-        |ball| Shoot::viable_shot(ctx.game, me.Physics.loc(), ball.loc),
+        |ball| {
+            if ball.loc.z >= max_shot_z {
+                return None;
+            }
+            Shoot::viable_shot(ctx.game, me.Physics.loc(), ball.loc)
+        },
     );
 
     let shoot_intercept = some_or_else!(shoot_intercept, {
@@ -113,6 +161,16 @@ fn can_we_shoot(ctx: &mut Context<'_>) -> bool {
         return false;
     }
 
+    let quality = ctx.scenario.shot_quality(shoot_intercept.data.aim_loc);
+    ctx.eeg.print_value("shot_quality", format!("{:.2}", quality));
+    if quality < MIN_SHOT_QUALITY {
+        ctx.eeg.log(
+            name_of_type!(Offense),
+            format!("can_we_shoot: shot quality too low ({:.2})", quality),
+        );
+        return false;
+    }
+
     true
 }
 
@@ -0,0 +1,158 @@
+use crate::{
+    behavior::{
+        movement::{drive_towards, simple_steer_towards},
+        offense::Shoot,
+    },
+    eeg::{color, Drawable},
+    strategy::{Action, Behavior, Context},
+    utils::geometry::RayCoordinateSystem,
+};
+use common::prelude::*;
+use nalgebra::{Point2, Vector2};
+use nameof::name_of_type;
+use std::f32::consts::PI;
+
+/// Wait near the enemy goal for the ball that a teammate is about to hit, then
+/// one-touch it.
+pub struct ReceivePass;
+
+impl ReceivePass {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns true if a teammate will get to the ball well before we do, and
+    /// their touch will send the ball towards the enemy goal.
+    pub fn should_receive(ctx: &mut Context<'_>) -> bool {
+        let (_teammate, teammate_intercept) = some_or_else!(ctx.scenario.teammate_intercept(), {
+            return false;
+        });
+        let me_time = ctx.scenario.me_intercept().map(|i| i.time);
+        match me_time {
+            Some(t) if t < teammate_intercept.time + 0.5 => false,
+            _ => Self::pass_target(ctx).is_some(),
+        }
+    }
+
+    /// Guess where the teammate's hit will send the ball. Assume they hit it
+    /// straight along the line from their car to the ball, and find the point
+    /// along that line nearest the enemy goal.
+    fn pass_target(ctx: &mut Context<'_>) -> Option<(Point2<f32>, Vector2<f32>)> {
+        let &(teammate, ref intercept) = ctx.scenario.teammate_intercept()?;
+        let ball_loc = intercept.ball_loc.to_2d();
+        let pass_dir = (ball_loc - teammate.Physics.loc_2d()).to_axis();
+        let goal = ctx.game.enemy_goal();
+
+        // Don't bother if the pass is headed away from the enemy goal.
+        if pass_dir.angle_to(&(goal.center_2d - ball_loc)).abs() >= PI / 3.0 {
+            return None;
+        }
+
+        let ray = RayCoordinateSystem::segment(ball_loc, ball_loc + pass_dir.into_inner());
+        let dist = ray.project(goal.center_2d).max(0.0);
+        let arrival = ball_loc + pass_dir.into_inner() * dist;
+
+        // Stay in front of the goal, not inside it or against a side wall.
+        let margin = 1000.0;
+        let target_loc = Point2::new(
+            arrival
+                .x
+                .max(-ctx.game.field_max_x() + margin)
+                .min(ctx.game.field_max_x() - margin),
+            arrival
+                .y
+                .max(-ctx.game.field_max_y() + margin * 1.5)
+                .min(ctx.game.field_max_y() - margin * 1.5),
+        );
+        Some((target_loc, pass_dir.into_inner()))
+    }
+}
+
+impl Behavior for ReceivePass {
+    fn name(&self) -> &str {
+        name_of_type!(ReceivePass)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let teammate_time = some_or_else!(ctx.scenario.teammate_intercept(), {
+            ctx.eeg.log(self.name(), "no teammate");
+            return Action::Abort;
+        })
+        .1
+        .time;
+
+        // If we can now reach the ball first, the pass has arrived.
+        if let Some(me_intercept) = ctx.scenario.me_intercept() {
+            if me_intercept.time < teammate_time {
+                ctx.eeg.log(self.name(), "the ball is ours; one-touch!");
+                return Action::tail_call(Shoot::new());
+            }
+        }
+
+        let (target_loc, pass_dir) = some_or_else!(Self::pass_target(ctx), {
+            ctx.eeg.log(self.name(), "no pass coming");
+            return Action::Abort;
+        });
+
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        ctx.eeg.draw(Drawable::Crosshair(target_loc));
+        ctx.eeg.draw(Drawable::Line(
+            ball_loc,
+            ball_loc + pass_dir * 1000.0,
+            color::YELLOW,
+        ));
+
+        let me = ctx.me();
+        if (target_loc - me.Physics.loc_2d()).norm() >= 300.0 {
+            return Action::Yield(drive_towards(ctx, target_loc));
+        }
+
+        // We're in position. Coast and face the goal so we're ready to shoot.
+        let goal_loc = ctx.game.enemy_goal().center_2d;
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: 0.0,
+            Steer: simple_steer_towards(&me.Physics, goal_loc),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::{
+            offense::ReceivePass,
+            strike::{GroundedHit, GroundedHitTarget, GroundedHitTargetAdjust},
+        },
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point2, Point3, Rotation3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn teammate_passes_and_we_finish() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(-2500.0, 2500.0, 92.74),
+                car_loc: Point3::new(500.0, 1000.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                ..Default::default()
+            })
+            .teammate(
+                Point3::new(-3000.0, 1500.0, 17.01),
+                Rotation3::from_unreal_angles(0.0, PI / 4.0, 0.0),
+                GroundedHit::hit_towards(|ctx| {
+                    Ok(GroundedHitTarget::new(
+                        ctx.intercept_time,
+                        GroundedHitTargetAdjust::RoughAim,
+                        Point2::new(0.0, 4000.0),
+                    ))
+                }),
+            )
+            .behavior(ReceivePass::new())
+            .run_for_millis(7000);
+
+        assert!(test.has_scored());
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     routing::{
         behavior::FollowRoute,
-        plan::{GetDollar, GroundDrive},
+        plan::{GetDollar, GroundDrive, KeepOut},
     },
     strategy::{Action, Behavior, BoostPickup, Context},
 };
@@ -17,6 +17,10 @@ pub struct ResetBehindBall {
 }
 
 impl ResetBehindBall {
+    /// Give the ball this much room on the way past, so we don't knock it
+    /// somewhere we didn't mean to.
+    const BALL_CLEARANCE: f32 = 200.0;
+
     pub fn behind_loc(loc: Point2<f32>, distance: f32) -> Self {
         Self {
             loc,
@@ -42,9 +46,12 @@ impl Behavior for ResetBehindBall {
             return Action::tail_call(FollowRoute::new(GetDollar::new(self.loc).pickup(pickup)));
         }
 
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let clearance = Vector2::new(Self::BALL_CLEARANCE, Self::BALL_CLEARANCE);
         let straight = GroundDrive::new(target_loc)
             .end_chop(0.5)
-            .always_prefer_dodge(true);
+            .always_prefer_dodge(true)
+            .keep_out(KeepOut::rect(ball_loc - clearance, ball_loc + clearance));
         Action::tail_call(
             FollowRoute::new(straight)
                 .same_ball_trajectory(true)
@@ -1,26 +1,48 @@
 use crate::{
     behavior::{
-        higher_order::Chain,
+        higher_order::{Chain, TryChoose},
+        offense::{ResetBehindBall, TepidHit},
         strike::{
             BounceShot, GroundedHit, GroundedHitAimContext, GroundedHitTarget,
             GroundedHitTargetAdjust,
         },
     },
-    helpers::intercept::{naive_ground_intercept_2, NaiveIntercept},
+    eeg::Event,
+    helpers::intercept::{intercept_is_behind, naive_ground_intercept_2, NaiveIntercept},
     routing::{behavior::FollowRoute, plan::GroundIntercept},
-    strategy::{Action, Behavior, Context, Game, Priority, Scenario},
+    strategy::{Action, Behavior, Context, Game, Goal, Priority, Scenario},
 };
-use common::{prelude::*, Speed};
+use common::{prelude::*, rl, Speed};
 use nalgebra::{Point2, Point3};
 use nameof::name_of_type;
 use simulate::linear_interpolate;
 use std::f32::consts::PI;
+use vec_box::vec_box;
 
-pub struct Shoot;
+pub struct Shoot {
+    max_ball_z: f32,
+}
 
 impl Shoot {
+    /// If the ball is behind us, swing around to this far behind it before
+    /// shooting.
+    const REPOSITION_DISTANCE: f32 = 1000.0;
+
+    /// How late we can be for the shot we picked before we give up on it and
+    /// take the ball whenever we can get there.
+    const ARRIVAL_TOLERANCE: f32 = 0.2;
+
     pub fn new() -> Self {
-        Self
+        Self {
+            max_ball_z: GroundedHitTarget::MAX_BALL_Z,
+        }
+    }
+
+    /// Don't commit to shots above this height. Higher balls are left to come
+    /// back down, and played off the bounce.
+    pub fn max_ball_z(mut self, max_ball_z: f32) -> Self {
+        self.max_ball_z = max_ball_z;
+        self
     }
 
     pub fn viable_shot(
@@ -46,18 +68,18 @@ impl Shoot {
             return None;
         }
 
-        let goal_angle = (ball_loc.to_2d() - aim_loc)
-            .to_axis()
-            .angle_to(&goal.normal_2d);
-        if goal_angle.abs() >= PI * (5.0 / 12.0) {
+        if !good_angle(goal, ball_loc.to_2d(), aim_loc) {
             return None;
         }
 
         Some(Shot { aim_loc })
     }
 
-    fn aim(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
-        match Self::aim_calc(ctx.game, ctx.scenario, ctx.car) {
+    fn aim(
+        ctx: &mut GroundedHitAimContext<'_, '_>,
+        max_ball_z: f32,
+    ) -> Result<GroundedHitTarget, ()> {
+        match Self::aim_calc(ctx.game, ctx.scenario, ctx.car, max_ball_z) {
             Some(i) => Ok(GroundedHitTarget::new(
                 i.time,
                 GroundedHitTargetAdjust::RoughAim,
@@ -156,15 +178,47 @@ impl Shoot {
         game: &Game<'_>,
         scenario: &Scenario<'_>,
         car: &common::halfway_house::PlayerInfo,
+        max_ball_z: f32,
     ) -> Option<NaiveIntercept<Shot>> {
         naive_ground_intercept_2(&car.into(), scenario.ball_prediction(), |ball| {
+            if ball.loc.z >= max_ball_z {
+                return None;
+            }
             Self::viable_shot(game, car.Physics.loc(), ball.loc)
         })
     }
 }
 
 pub struct Shot {
-    aim_loc: Point2<f32>,
+    pub aim_loc: Point2<f32>,
+}
+
+/// Returns true if a shot from `ball_loc` towards `aim_loc` would actually go
+/// in, i.e., it passes between the posts with room for the ball to spare.
+fn good_angle(goal: &Goal, ball_loc: Point2<f32>, aim_loc: Point2<f32>) -> bool {
+    // Nothing to shoot at from behind the goal line.
+    if goal.is_y_within_range(ball_loc.y, ..0.0) {
+        return false;
+    }
+
+    // From near the back corners, the goal is too narrow a target.
+    let goal_angle = (ball_loc - aim_loc).to_axis().angle_to(&goal.normal_2d);
+    if goal_angle.abs() >= PI * (5.0 / 12.0) {
+        return false;
+    }
+
+    // Each post needs to be far enough off the line of the shot that the ball
+    // clears it.
+    let aim = aim_loc - ball_loc;
+    let clearance = |post: Point2<f32>| {
+        let ball_to_post = post - ball_loc;
+        let margin = (rl::BALL_RADIUS / ball_to_post.norm()).min(1.0).asin();
+        (aim.angle_to(&ball_to_post), margin)
+    };
+    let [post1, post2] = goal.posts();
+    let (angle1, margin1) = clearance(post1);
+    let (angle2, margin2) = clearance(post2);
+    angle1.signum() != angle2.signum() && angle1.abs() >= margin1 && angle2.abs() >= margin2
 }
 
 impl Behavior for Shoot {
@@ -173,27 +227,158 @@ impl Behavior for Shoot {
     }
 
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
-        let intercept = Self::aim_calc(ctx.game, &ctx.scenario, ctx.me());
-        if intercept.is_none() {
-            ctx.eeg.log(self.name(), "no viable shot");
-            return Action::Abort;
+        let max_ball_z = self.max_ball_z;
+        let intercept = Self::aim_calc(ctx.game, &ctx.scenario, ctx.me(), max_ball_z);
+        let intercept = some_or_else!(intercept, {
+            // Don't just abandon a ball we were going for. A safe touch is
+            // better than nothing.
+            ctx.eeg
+                .log(self.name(), "no viable shot; falling back to a safe touch");
+            return Action::tail_call(TepidHit::new());
+        });
+
+        if intercept_is_behind(ctx, &intercept) {
+            ctx.eeg.track(Event::ShootInterceptBehind);
+            ctx.eeg
+                .log(self.name(), "the ball is behind us; repositioning first");
+            return Action::tail_call(ResetBehindBall::behind_loc(
+                intercept.ball_loc.to_2d(),
+                Self::REPOSITION_DISTANCE,
+            ));
         }
 
-        Action::tail_call(Chain::new(Priority::Strike, vec![
-            Box::new(FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true)),
-            Box::new(GroundedHit::hit_towards(Self::aim)),
+        let frame = ctx
+            .scenario
+            .ball_prediction()
+            .at_time_or_last(intercept.time)
+            .clone();
+        let shot = GroundIntercept::new().max_ball_z(max_ball_z).target_frame(
+            frame,
+            ctx.game.time(),
+            Self::ARRIVAL_TOLERANCE,
+        );
+        let earliest = GroundIntercept::new().max_ball_z(max_ball_z);
+        // We can only afford to wait for the shot we picked until the enemy goes
+        // for the ball. Then it's a race.
+        let shot = FollowRoute::new(shot).replan_on_enemy_commit(earliest.clone());
+        Action::tail_call(TryChoose::new(Priority::Strike, vec_box![
+            Self::strike(shot, max_ball_z),
+            Self::strike(FollowRoute::new(earliest), max_ball_z),
         ]))
     }
 }
 
+impl Shoot {
+    fn strike(route: FollowRoute, max_ball_z: f32) -> Chain {
+        Chain::new(Priority::Strike, vec![
+            Box::new(route.same_ball_trajectory(true)),
+            Box::new(GroundedHit::hit_towards(move |ctx| {
+                Self::aim(ctx, max_ball_z)
+            })),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{behavior::offense::shoot::good_angle, strategy::SOCCAR_GOAL_ORANGE};
+    use nalgebra::Point2;
+
+    #[test]
+    fn good_angle_table() {
+        let goal = &SOCCAR_GOAL_ORANGE;
+        let cases = [
+            // Dead center.
+            ((0.0, 3000.0), (0.0, 5120.0), true),
+            // Off to the side, aiming at the middle of the net.
+            ((-1500.0, 3500.0), (0.0, 5120.0), true),
+            // Close in, aiming just inside the post.
+            ((0.0, 4500.0), (850.0, 5120.0), false),
+            // Close in, aiming a bit further from the post.
+            ((0.0, 4500.0), (500.0, 5120.0), true),
+            // Deep in the corner.
+            ((3500.0, 4800.0), (700.0, 5120.0), false),
+            // Out along the side wall, but far enough from the goal line.
+            ((3000.0, 3000.0), (500.0, 5120.0), true),
+            // Behind the net.
+            ((0.0, 5300.0), (0.0, 5120.0), false),
+            ((1500.0, 5300.0), (0.0, 5120.0), false),
+        ];
+        for &((bx, by), (ax, ay), expected) in cases.iter() {
+            let ball_loc = Point2::new(bx, by);
+            let aim_loc = Point2::new(ax, ay);
+            assert_eq!(
+                good_angle(goal, ball_loc, aim_loc),
+                expected,
+                "ball_loc={:?}, aim_loc={:?}",
+                ball_loc,
+                aim_loc,
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use crate::{
         behavior::offense::Shoot,
+        eeg::Event,
         integration_tests::{TestRunner, TestScenario},
     };
     use common::prelude::*;
-    use nalgebra::{Point3, Rotation3, Vector3};
+    use nalgebra::{Point2, Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn safe_touch_without_shot() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(2500.0, 1000.0, 92.74),
+                car_loc: Point3::new(-1000.0, 1000.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, 0.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(Shoot::new())
+            .run_for_millis(3000);
+
+        test.examine_events(|events| {
+            assert!(
+                events.contains(&Event::TepidHitTowardEnemyGoal)
+                    || events.contains(&Event::TepidHitBlockAngleToGoal)
+                    || events.contains(&Event::TepidHitAwayFromOwnGoal)
+            );
+        });
+
+        // We should have touched the ball instead of giving up on it.
+        let packet = test.sniff_packet();
+        let ball_loc = packet.GameBall.Physics.loc();
+        assert!((ball_loc.to_2d() - Point2::new(2500.0, 1000.0)).norm() >= 500.0);
+        assert!(!test.enemy_has_scored());
+    }
+
+    #[test]
+    fn play_high_ball_off_the_bounce() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, 3000.0, 800.0),
+                car_loc: Point3::new(0.0, 500.0, 17.01),
+                ..Default::default()
+            })
+            .behavior(Shoot::new().max_ball_z(120.0))
+            .run();
+
+        // With the cap, we should wait for the ball to come down rather than
+        // jumping for it.
+        let mut max_car_z: f32 = 0.0;
+        for _ in 0..50 {
+            let packet = test.sniff_packet();
+            max_car_z = max_car_z.max(packet.GameCars[0].Physics.loc().z);
+            test.sleep_millis(100);
+        }
+        println!("max_car_z = {}", max_car_z);
+        assert!(max_car_z < 60.0);
+        assert!(test.has_scored());
+    }
 
     #[test]
     fn awkwardly_angled_breakaway() {
@@ -529,4 +714,24 @@ mod integration_tests {
 
         assert!(test.has_scored());
     }
+
+    #[test]
+    fn reposition_when_ball_is_behind() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                // The shot is lined up, but we're driving away from the ball.
+                ball_loc: Point3::new(0.0, 2000.0, 92.74),
+                car_loc: Point3::new(0.0, 500.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, 0.0),
+                car_vel: Vector3::new(0.0, -1000.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(Shoot::new())
+            .run_for_millis(4000);
+
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::ShootInterceptBehind));
+        });
+        assert!(!test.enemy_has_scored());
+    }
 }
@@ -2,7 +2,8 @@ use crate::{
     behavior::{
         defense::Defense,
         strike::{
-            GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust, WallHit,
+            GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust, PopHit,
+            WallHit,
         },
     },
     eeg::{color, Drawable, Event, EEG},
@@ -24,6 +25,10 @@ use std::f32::consts::PI;
 pub struct TepidHit;
 
 impl TepidHit {
+    /// Only take wall hits below this height. Anything higher is more of a job
+    /// for an aerial.
+    pub const MAX_WALL_BALL_Z: f32 = 1400.0;
+
     pub fn new() -> Self {
         Self
     }
@@ -40,6 +45,7 @@ impl Behavior for TepidHit {
         let mut hits = ArrayVec::<[_; 4]>::new();
         hits.push(ground(&ctx, eeg));
         hits.push(wall(&ctx, eeg));
+        hits.push(pop(&ctx, eeg));
 
         let hit = hits
             .into_iter()
@@ -54,26 +60,24 @@ impl Behavior for TepidHit {
 
         match hit {
             Some((_, HitType::Wall)) => Action::tail_call(chain!(Priority::Strike, [
-                FollowRoute::new(
-                    WallIntercept::new()
-                        .must_be_wall(true)
-                        .must_be_side_wall(true)
-                )
-                .same_ball_trajectory(true),
+                FollowRoute::new(wall_intercept()).same_ball_trajectory(true),
                 WallHit::new(),
             ])),
             Some((_, HitType::Ground)) => Action::tail_call(chain!(Priority::Strike, [
                 FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true),
                 GroundedHit::hit_towards(time_wasting_hit),
             ])),
+            Some((_, HitType::Pop)) => Action::tail_call(PopHit::new()),
             None => Action::tail_call(FollowRoute::new(GetDollar::smart(&ctx, eeg))),
         }
     }
 }
 
 fn ground(ctx: &Context2<'_, '_>, eeg: &mut EEG) -> Option<(f32, HitType)> {
-    let intercept =
-        GroundIntercept::calc_intercept(&ctx.me().into(), ctx.scenario.ball_prediction())?;
+    let intercept = GroundIntercept::calc_intercept(
+        &ctx.me().into(),
+        &ctx.scenario.ball_prediction().slice(0.0),
+    )?;
 
     if dangerous_back_wall_with_little_boost(ctx, intercept.loc) {
         eeg.log(name_of_type!(TepidHit), "too dangerous with no boost");
@@ -96,11 +100,7 @@ fn dangerous_back_wall_with_little_boost(
 }
 
 fn wall<'ball>(ctx: &Context2<'_, 'ball>, eeg: &mut EEG) -> Option<(f32, HitType)> {
-    let intercept = match WallIntercept::new()
-        .must_be_wall(true)
-        .must_be_side_wall(true)
-        .calc_intercept(ctx)
-    {
+    let intercept = match wall_intercept().calc_intercept(ctx) {
         Ok(i) => i,
         Err(reason) => {
             eeg.log(
@@ -118,10 +118,46 @@ fn wall<'ball>(ctx: &Context2<'_, 'ball>, eeg: &mut EEG) -> Option<(f32, HitType
     Some((intercept.t, HitType::Wall))
 }
 
+/// A double-jump touch for balls that are too high to hit from the ground but
+/// too low to be worth an aerial.
+fn pop(ctx: &Context2<'_, '_>, eeg: &mut EEG) -> Option<(f32, HitType)> {
+    let intercept = some_or_else!(
+        PopHit::intercept(ctx.me(), ctx.scenario.ball_prediction(), ctx.game.gravity()),
+        {
+            eeg.log(name_of_type!(TepidHit), "pop: no intercept in jump range");
+            return None;
+        }
+    );
+
+    // We'll be hitting the ball straight on, so make sure that doesn't send it
+    // towards our own net.
+    let me_loc = ctx.me().Physics.loc_2d();
+    let ball_loc = intercept.ball_loc.to_2d();
+    let aim_loc = WallRayCalculator::calculate(ball_loc, ball_loc + (ball_loc - me_loc));
+    if WallRayCalculator::wall_for_point(ctx.game, aim_loc) == Wall::OwnGoal {
+        eeg.log(name_of_type!(TepidHit), "pop: refusing to own goal");
+        return None;
+    }
+
+    eeg.log(
+        name_of_type!(TepidHit),
+        format!("pop: intercept is {}", intercept.ball_loc.pretty()),
+    );
+    Some((intercept.time, HitType::Pop))
+}
+
+fn wall_intercept() -> WallIntercept {
+    WallIntercept::new()
+        .must_be_wall(true)
+        .must_be_side_wall(true)
+        .max_ball_z(TepidHit::MAX_WALL_BALL_Z)
+}
+
 #[derive(Ord, PartialOrd, Eq, PartialEq, Debug)]
 enum HitType {
     Ground,
     Wall,
+    Pop,
 }
 
 fn time_wasting_hit(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
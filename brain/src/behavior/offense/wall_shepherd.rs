@@ -0,0 +1,163 @@
+use crate::{
+    behavior::{
+        higher_order::Chain,
+        movement::drive_towards,
+        strike::{GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust},
+    },
+    eeg::Drawable,
+    routing::{behavior::FollowRoute, plan::GroundIntercept},
+    strategy::{Action, Behavior, Context, Game, Priority, Scenario},
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Point3};
+use nameof::name_of_type;
+
+/// Keep a slow ball rolling up the side wall towards the enemy corner with a
+/// series of soft touches, staying goal-side of it the whole way.
+pub struct WallShepherd;
+
+impl WallShepherd {
+    const MAX_BALL_SPEED: f32 = 1000.0;
+    const MAX_WALL_DIST: f32 = 800.0;
+    const MAX_BALL_Z: f32 = 130.0;
+
+    /// Stop once the ball is this close to the enemy back wall, and let
+    /// someone else figure out how to center it.
+    const CORNER_DIST: f32 = 1000.0;
+
+    /// Keep the touches soft. Driving through the ball any longer than it
+    /// takes to make contact would knock it off the wall, or carry us past it.
+    const FOLLOW_THROUGH: f32 = 0.1;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns true if the ball is rolling slowly along a side wall in the
+    /// enemy half, and we have time to play with it.
+    pub fn applicable(ctx: &mut Context<'_>) -> bool {
+        if ctx.scenario.possession() < Scenario::POSSESSION_CONTESTABLE {
+            return false;
+        }
+        let ball = &ctx.packet.GameBall.Physics;
+        ctx.game
+            .enemy_goal()
+            .is_y_within_range(ball.loc().y, ..rl::FIELD_MAX_Y)
+            && Self::ball_is_shepherdable(ctx.game, ball.loc(), ball.vel_2d().norm())
+    }
+
+    fn ball_is_shepherdable(game: &Game<'_>, ball_loc: Point3<f32>, ball_speed: f32) -> bool {
+        let enemy_goal = game.enemy_goal();
+        ball_speed < Self::MAX_BALL_SPEED
+            && ball_loc.z < Self::MAX_BALL_Z
+            && game.field_max_x() - ball_loc.x.abs() < Self::MAX_WALL_DIST
+            && !enemy_goal.is_y_within_range(ball_loc.y, ..Self::CORNER_DIST)
+    }
+
+    /// A spot on the wall a bit upfield from the ball. Hitting towards it
+    /// pushes the ball forward while keeping it pressed against the wall.
+    fn wall_target(game: &Game<'_>, ball_loc: Point2<f32>) -> Point2<f32> {
+        let forward = game.enemy_goal().center_2d.y.signum();
+        Point2::new(
+            ball_loc.x.signum() * game.field_max_x(),
+            ball_loc.y + forward * 1500.0,
+        )
+    }
+
+    /// Returns true if we're between the ball and our own goal.
+    fn is_goal_side(game: &Game<'_>, car_loc: Point2<f32>, ball_loc: Point2<f32>) -> bool {
+        let forward = game.enemy_goal().center_2d.y.signum();
+        (ball_loc.y - car_loc.y) * forward >= 0.0
+    }
+
+    fn aim(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
+        let ball_loc = ctx.intercept_ball_loc;
+        if ball_loc.z >= Self::MAX_BALL_Z {
+            return Err(());
+        }
+        let aim_loc = Self::wall_target(ctx.game, ball_loc.to_2d());
+        Ok(GroundedHitTarget::new(
+            ctx.intercept_time,
+            GroundedHitTargetAdjust::RoughAim,
+            aim_loc,
+        )
+        .jump(false)
+        .dodge(false)
+        .follow_through(Self::FOLLOW_THROUGH))
+    }
+}
+
+impl Behavior for WallShepherd {
+    fn name(&self) -> &str {
+        name_of_type!(WallShepherd)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let ball = &ctx.packet.GameBall.Physics;
+        if !Self::ball_is_shepherdable(ctx.game, ball.loc(), ball.vel_2d().norm()) {
+            ctx.eeg.log(self.name(), "ball is no longer on the wall");
+            return Action::Return;
+        }
+
+        let ball_loc = ball.loc_2d();
+        let me_loc = ctx.me().Physics.loc_2d();
+
+        // Get back behind the ball before touching it, or we'll just knock it
+        // back towards our own goal.
+        if !Self::is_goal_side(ctx.game, me_loc, ball_loc) {
+            let forward = ctx.game.enemy_goal().center_2d.y.signum();
+            let behind = Point2::new(
+                ball_loc.x - ball_loc.x.signum() * 500.0,
+                ball_loc.y - forward * 1000.0,
+            );
+            ctx.eeg.log(self.name(), "getting goal-side");
+            ctx.eeg.draw(Drawable::Crosshair(behind));
+            return Action::Yield(drive_towards(ctx, behind));
+        }
+
+        ctx.eeg
+            .draw(Drawable::Crosshair(Self::wall_target(ctx.game, ball_loc)));
+
+        // Touch it, then come back here and see if it needs another.
+        Action::tail_call(Chain::new(Priority::Strike, vec![
+            Box::new(FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true)),
+            Box::new(GroundedHit::hit_towards(Self::aim)),
+            Box::new(Self::new()),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::offense::WallShepherd,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn shepherd_ball_up_the_wall() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(3800.0, 500.0, 92.74),
+                ball_vel: Vector3::new(0.0, 300.0, 0.0),
+                car_loc: Point3::new(3000.0, -1500.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                car_vel: Vector3::new(0.0, 500.0, 0.0),
+                enemy_loc: Point3::new(-3000.0, 4500.0, 17.01),
+                ..Default::default()
+            })
+            .behavior(WallShepherd::new())
+            .run_for_millis(6000);
+
+        let packet = test.sniff_packet();
+        let ball_loc = packet.GameBall.Physics.loc();
+        println!("ball_loc = {:?}", ball_loc);
+        // The ball should have made good progress, and still be on the wall.
+        assert!(ball_loc.y >= 2500.0);
+        assert!(ball_loc.x >= 3000.0);
+        assert!(ball_loc.z < 300.0);
+    }
+}
@@ -26,7 +26,13 @@ impl Behavior for FiftyFifty {
         Action::tail_call(While::new(
             WeDontWinTheRace,
             Chain::new(Priority::Idle, vec![
-                Box::new(FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true)),
+                // The enemy is right there, so the ball will be jostled around a
+                // lot. Keep after it instead of giving up every time it moves.
+                Box::new(
+                    FollowRoute::new(GroundIntercept::new())
+                        .same_ball_trajectory(true)
+                        .replan_interval(0.2),
+                ),
                 Box::new(GroundedHit::hit_towards(defensive_hit)),
             ]),
         ))
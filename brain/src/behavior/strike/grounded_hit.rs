@@ -10,13 +10,13 @@ use crate::{
     strategy::{Action, Behavior, Context, Game, Priority, Scenario},
     utils::intercept_memory::{InterceptMemory, InterceptMemoryResult},
 };
-use common::{physics, prelude::*, rl, Coordinate, Distance};
+use common::{physics, prelude::*, rl, Speed};
 use derive_new::new;
 use nalgebra::{Point2, Point3, UnitQuaternion, Vector3};
 use nameof::name_of_type;
 use simulate::{
     car_single_jump::{time_to_z, JUMP_MAX_Z},
-    linear_interpolate, Car1D,
+    linear_interpolate, solve_arrival_then_cruise,
 };
 use std::f32::consts::PI;
 
@@ -44,6 +44,11 @@ impl GroundedHit<fn(&mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTar
     const CONTACT_Z_OFFSET: f32 = -70.0; // This is misguided and should probably go away.
     pub const MAX_BALL_Z: f32 = 220.0 - Self::CONTACT_Z_OFFSET; // TODO: how high can I jump
 
+    /// If the car and ball are closing on each other faster than this, skip the
+    /// dodge. The timing window is too small to get right, and a mistimed dodge
+    /// whiffs completely, whereas a plain bump still sends the ball back.
+    pub const MAX_DODGE_CLOSING_SPEED: f32 = 3000.0;
+
     /// A preset for `Aim` that hits the ball straight ahead.
     #[allow(dead_code)]
     pub fn opposite_of_self(
@@ -212,20 +217,35 @@ where
 
         // TODO: iteratively find contact point which hits the ball towards aim_loc
 
-        ctx.eeg.print_time("intercept_time", intercept.time);
-        ctx.eeg
-            .print_value("intercept_loc_z", Coordinate(intercept.ball_loc.z));
+        let intercept_angle = me
+            .Physics
+            .forward_axis_2d()
+            .into_inner()
+            .angle_to(&(intercept.ball_loc.to_2d() - me.Physics.loc_2d()));
+        ctx.eeg.draw(Drawable::intercept_marker(
+            intercept.ball_loc,
+            intercept.time,
+        ));
+        ctx.eeg.print_angle("intercept_angle", intercept_angle);
         ctx.eeg.draw(Drawable::Crosshair(target.aim_loc));
-        ctx.eeg.draw(Drawable::ghost_ball(intercept.ball_loc));
         ctx.eeg
             .draw(Drawable::GhostCar(target_loc, me.Physics.rot()));
 
+        let closing_speed = closing_speed(me, intercept);
+        ctx.eeg.print_value("closing_speed", Speed(closing_speed));
+        let too_fast_to_dodge = closing_speed >= GroundedHit::MAX_DODGE_CLOSING_SPEED;
+        if target.dodge && too_fast_to_dodge {
+            ctx.eeg
+                .draw(Drawable::print("too fast to dodge", color::GREEN));
+        }
+
         Ok(Plan {
             intercept_time: target.intercept_time,
             target_loc,
             target_rot,
             jump: target.jump,
-            dodge: target.dodge,
+            dodge: target.dodge && !too_fast_to_dodge,
+            follow_through: target.follow_through,
         })
     }
 
@@ -261,13 +281,16 @@ where
             }
             GroundedHitTargetAdjust::StraightOn => naive_target_loc,
         };
+        if target.strike_offset != 0.0 {
+            let push_dir = (target.aim_loc - intercept.ball_loc.to_2d()).normalize();
+            target_loc += push_dir.to_3d(0.0) * target.strike_offset;
+        }
         // Don't get too far underneath the ball, since we might end up hitting it way
         // up in the air (which we never want to do).
         target_loc.z = target_loc.z.max(intercept.ball_loc.z * 0.6);
         (target_loc, target_rot)
     }
 
-    #[allow(clippy::if_same_then_else)]
     fn estimate_approach(&mut self, ctx: &mut Context<'_>, plan: &Plan) -> Do {
         let total_time = plan.intercept_time;
         let jump_duration = Self::jump_duration(plan.target_loc.z);
@@ -277,50 +300,26 @@ where
             return Do::Jump;
         }
 
-        let would_reach = |throttle, boost| {
-            // Phase 1: driving forward
-            let mut drive = Car1D::new()
-                .with_speed(ctx.me().Physics.vel().norm())
-                .with_boost(ctx.me().Boost as f32);
-            drive.advance(drive_time, throttle, boost);
-            let drive_start_loc = ctx.me().Physics.loc_2d();
-            let drive_forward = (plan.target_loc.to_2d() - drive_start_loc).to_axis();
-            let drive_end_loc = drive_start_loc + drive_forward.as_ref() * drive.distance();
-            let drive_end_vel = drive_forward.as_ref() * drive.speed();
-
-            // Phase 2: a jump in which the xy-velocity stays constant
-            let jump_end_loc = drive_end_loc + drive_end_vel * jump_duration;
-
-            // Calculate how far ahead/behind the target location
-            (jump_end_loc - plan.target_loc.to_2d()).dot(&drive_forward)
-        };
+        let me = ctx.me();
+        let start_speed = me.Physics.vel().norm().min(rl::CAR_MAX_SPEED);
+        let distance = (plan.target_loc.to_2d() - me.Physics.loc_2d()).norm();
 
         // Aim for a few uu behind the ball so we don't make contact before we dodge.
         let target_offset = -10.0;
 
-        let coast_offset = would_reach(0.0, false);
-        let throttle_offset = would_reach(1.0, false);
-        let blitz_offset = would_reach(1.0, true);
-
-        let (throttle, boost) = if coast_offset > target_offset + 100.0 {
-            (-1.0, false) // We're _really_ overshooting…
-        } else if coast_offset > target_offset {
-            (0.0, false) // We're overshooting…
-        } else if throttle_offset > target_offset {
-            (0.0, false)
-        } else if blitz_offset > target_offset {
-            (1.0, false)
-        } else {
-            (1.0, true)
-        };
+        let arrival = solve_arrival_then_cruise(
+            start_speed,
+            me.Boost as f32,
+            distance + target_offset,
+            drive_time,
+            jump_duration,
+        );
+        let (throttle, boost) = arrival.controls();
 
         ctx.eeg.print_value("target", plan.target_loc);
         ctx.eeg.print_time("drive_time", drive_time);
         ctx.eeg.print_time("total_time", total_time);
-        ctx.eeg.print_value("coast_offset", Distance(coast_offset));
-        ctx.eeg
-            .print_value("throttle_offset", Distance(throttle_offset));
-        ctx.eeg.print_value("blitz_offset", Distance(blitz_offset));
+        ctx.eeg.print_value("arrival", format!("{:?}", arrival));
 
         Do::Drive(throttle, boost)
     }
@@ -343,11 +342,14 @@ where
         if !plan.jump {
             // Don't jump, but at least follow-through on the hit so we don't prematurely
             // turn around.
-            return Action::tail_call(Yielder::new(0.25, common::halfway_house::PlayerInput {
-                Throttle: 1.0,
-                Boost: true,
-                ..Default::default()
-            }));
+            return Action::tail_call(Yielder::new(
+                plan.follow_through,
+                common::halfway_house::PlayerInput {
+                    Throttle: 1.0,
+                    Boost: true,
+                    ..Default::default()
+                },
+            ));
         }
 
         let mut steps = Vec::<Box<dyn Behavior>>::new();
@@ -386,6 +388,14 @@ where
     }
 }
 
+/// How fast the car and ball will be approaching each other at the moment of
+/// contact.
+fn closing_speed(car: &common::halfway_house::PlayerInfo, intercept: &NaiveIntercept) -> f32 {
+    let car_to_ball = (intercept.ball_loc - car.Physics.loc()).to_2d().normalize();
+    let car_vel = car_to_ball * intercept.car_speed.max(car.Physics.vel_2d().norm());
+    (car_vel - intercept.ball_vel.to_2d()).dot(&car_to_ball)
+}
+
 pub fn car_ball_contact_with_pitch(
     game: &Game<'_>,
     ball_loc: Point3<f32>,
@@ -450,11 +460,34 @@ pub struct GroundedHitTarget {
     jump: bool,
     #[new(value = "true")]
     dodge: bool,
+    #[new(value = "0.0")]
+    strike_offset: f32,
+    #[new(value = "GroundedHitTarget::DEFAULT_FOLLOW_THROUGH")]
+    follow_through: f32,
 }
 
 impl GroundedHitTarget {
     pub const MAX_BALL_Z: f32 = GroundedHit::MAX_BALL_Z;
 
+    /// Long enough to be sure we've touched the ball, but short enough that
+    /// we don't go sailing past the play.
+    pub const DEFAULT_FOLLOW_THROUGH: f32 = 0.25;
+
+    /// Aim the car this far past the contact point, along the direction we want
+    /// to push the ball. This way we're still driving through the ball when we
+    /// touch it, instead of just arriving next to it.
+    pub fn strike_offset(mut self, strike_offset: f32) -> Self {
+        self.strike_offset = strike_offset;
+        self
+    }
+
+    /// Strike all the way through the ball, as if the car's nose was aimed at
+    /// the far side of the ball.
+    pub fn strike_through(self, game: &Game<'_>) -> Self {
+        let offset = game.ball_radius() + game.me_vehicle().pivot_to_front_dist();
+        self.strike_offset(offset)
+    }
+
     pub fn jump(mut self, jump: bool) -> Self {
         self.jump = jump;
         self
@@ -464,6 +497,14 @@ impl GroundedHitTarget {
         self.dodge = dodge;
         self
     }
+
+    /// If we hit the ball without jumping, keep driving through the contact
+    /// point for this many seconds before handing off, so we don't bail before
+    /// the touch actually happens. (A dodge has its own follow-through.)
+    pub fn follow_through(mut self, follow_through: f32) -> Self {
+        self.follow_through = follow_through;
+        self
+    }
 }
 
 pub enum GroundedHitTargetAdjust {
@@ -477,6 +518,7 @@ struct Plan {
     target_rot: UnitQuaternion<f32>,
     jump: bool,
     dodge: bool,
+    follow_through: f32,
 }
 
 enum Do {
@@ -493,6 +535,7 @@ mod integration_tests {
     };
     use common::{prelude::*, rl};
     use nalgebra::{Point2, Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
 
     #[test]
     #[ignore = "The great bankruptcy of 2018"]
@@ -540,4 +583,115 @@ mod integration_tests {
         // We don't score it yet. This test just makes sure we actually hit the ball lol
         // assert!(test.has_scored());
     }
+
+    #[test]
+    fn fast_incoming_ball_without_dodge() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, 1500.0, 92.74),
+                ball_vel: Vector3::new(0.0, -2000.0, 0.0),
+                car_loc: Point3::new(0.0, -1500.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                car_vel: Vector3::new(0.0, 1200.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(GroundedHit::hit_towards(|ctx| {
+                Ok(GroundedHitTarget::new(
+                    ctx.intercept_time,
+                    GroundedHitTargetAdjust::StraightOn,
+                    Point2::new(0.0, rl::FIELD_MAX_Y),
+                ))
+            }))
+            .run_for_millis(1500);
+
+        // We should have sent the ball back where it came from, and not be
+        // tumbling around after a whiffed flip.
+        let packet = test.sniff_packet();
+        assert!(packet.GameBall.Physics.vel().y > 0.0);
+        assert!(!packet.GameCars[0].DoubleJumped);
+    }
+
+    /// Hit a rolling ball towards a target off to the side, and return how far
+    /// (in radians) the ball's direction ends up from the target.
+    fn push_alignment_error(strike_through: bool) -> f32 {
+        let aim_loc = Point2::new(-2000.0, 4000.0);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, 0.0, 92.74),
+                ball_vel: Vector3::new(300.0, 0.0, 0.0),
+                car_loc: Point3::new(0.0, -2500.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                car_vel: Vector3::new(0.0, 500.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(GroundedHit::hit_towards(move |ctx| {
+                let target = GroundedHitTarget::new(
+                    ctx.intercept_time,
+                    GroundedHitTargetAdjust::RoughAim,
+                    aim_loc,
+                )
+                .jump(false)
+                .dodge(false);
+                if strike_through {
+                    Ok(target.strike_through(ctx.game))
+                } else {
+                    Ok(target)
+                }
+            }))
+            .run_for_millis(3000);
+
+        let packet = test.sniff_packet();
+        let ball_loc = packet.GameBall.Physics.loc_2d();
+        let ball_vel = packet.GameBall.Physics.vel_2d();
+        assert!(ball_vel.norm() >= 500.0);
+        ball_vel.angle_to(&(aim_loc - ball_loc)).abs()
+    }
+
+    #[test]
+    fn strike_through_improves_alignment() {
+        let baseline = push_alignment_error(false);
+        let strike_through = push_alignment_error(true);
+        println!(
+            "baseline = {}, strike_through = {}",
+            baseline, strike_through
+        );
+        assert!(strike_through < baseline);
+        assert!(strike_through < PI / 12.0);
+    }
+
+    /// Bump a stationary ball without jumping, and return how fast the ball is
+    /// going afterwards.
+    fn bump_ball_speed(follow_through: f32) -> f32 {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, 0.0, 92.74),
+                car_loc: Point3::new(0.0, -2000.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                car_vel: Vector3::new(0.0, 500.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(GroundedHit::hit_towards(move |ctx| {
+                Ok(GroundedHitTarget::new(
+                    ctx.intercept_time,
+                    GroundedHitTargetAdjust::StraightOn,
+                    Point2::new(0.0, rl::FIELD_MAX_Y),
+                )
+                .jump(false)
+                .dodge(false)
+                .follow_through(follow_through))
+            }))
+            .run_for_millis(2500);
+
+        let packet = test.sniff_packet();
+        packet.GameBall.Physics.vel_2d().norm()
+    }
+
+    #[test]
+    fn follow_through_makes_solid_contact() {
+        let bail = bump_ball_speed(0.0);
+        let follow_through = bump_ball_speed(GroundedHitTarget::DEFAULT_FOLLOW_THROUGH);
+        println!("bail = {}, follow_through = {}", bail, follow_through);
+        assert!(follow_through >= 1000.0);
+        assert!(follow_through >= bail);
+    }
 }
@@ -4,6 +4,7 @@ pub use self::{
     grounded_hit::{
         GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust,
     },
+    pop_hit::PopHit,
     wall_hit::WallHit,
 };
 
@@ -13,4 +14,5 @@ mod fifty_fifty;
 mod ground_shot;
 mod grounded_hit;
 mod jump_shot;
+mod pop_hit;
 mod wall_hit;
@@ -0,0 +1,189 @@
+use crate::{
+    behavior::{
+        higher_order::Chain,
+        movement::{simple_steer_towards, Yielder},
+        strike::GroundedHit,
+    },
+    eeg::Drawable,
+    helpers::{
+        ball::BallTrajectory,
+        intercept::{naive_ground_intercept, NaiveIntercept},
+    },
+    routing::recover::{IsSkidding, NotOnFlatGround},
+    strategy::{Action, Behavior, Context, Priority},
+};
+use common::{prelude::*, rl};
+use nameof::name_of_type;
+use simulate::{car_double_jump, solve_arrival_then_cruise};
+use vec_box::vec_box;
+
+/// Double jump into a ball that's too high for `GroundedHit`, popping it
+/// straight ahead. It's not a real aerial, but it beats standing around
+/// underneath the ball waiting for it to come down.
+pub struct PopHit;
+
+impl PopHit {
+    /// Make contact a bit below the ball's center so it goes up and over
+    /// whoever is waiting for it.
+    const CONTACT_Z_OFFSET: f32 = -70.0;
+
+    /// Anything lower than this is `GroundedHit`'s job.
+    pub const MIN_BALL_Z: f32 = GroundedHit::MAX_BALL_Z;
+
+    /// If it's time to jump but we'd miss the contact point by more than this,
+    /// don't bother.
+    const MAX_CONTACT_MISS: f32 = 100.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// The highest ball we can reach with a double jump.
+    pub fn max_ball_z(gravity: f32) -> f32 {
+        car_double_jump::max_z(gravity) - Self::CONTACT_Z_OFFSET
+    }
+
+    /// Finds the first ball we can drive underneath while it's within double
+    /// jump range.
+    pub fn intercept(
+        car: &common::halfway_house::PlayerInfo,
+        ball_prediction: &BallTrajectory,
+        gravity: f32,
+    ) -> Option<NaiveIntercept> {
+        let max_ball_z = Self::max_ball_z(gravity);
+        naive_ground_intercept(
+            ball_prediction.iter(),
+            car.Physics.loc(),
+            car.Physics.vel(),
+            car.Boost as f32,
+            |ball| ball.loc.z >= Self::MIN_BALL_Z && ball.loc.z < max_ball_z,
+        )
+    }
+}
+
+impl Behavior for PopHit {
+    fn name(&self) -> &str {
+        name_of_type!(PopHit)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        let me = ctx.me();
+
+        if IsSkidding.evaluate(&me.into()) {
+            ctx.eeg.log(self.name(), name_of_type!(IsSkidding));
+            return Action::Abort;
+        }
+        if NotOnFlatGround.evaluate(&me.into()) {
+            ctx.eeg.log(self.name(), name_of_type!(NotOnFlatGround));
+            return Action::Abort;
+        }
+
+        let gravity = ctx.game.gravity();
+        let intercept = some_or_else!(
+            Self::intercept(me, ctx.scenario.ball_prediction(), gravity),
+            {
+                ctx.eeg.log(self.name(), "can't find intercept");
+                return Action::Abort;
+            }
+        );
+
+        // Drive to where the nose will be touching the ball.
+        let me_loc = me.Physics.loc_2d();
+        let ball_loc = intercept.ball_loc.to_2d();
+        let contact_dist = ctx.game.ball_radius() + ctx.game.me_vehicle().pivot_to_front_dist();
+        let target_loc = ball_loc - (ball_loc - me_loc).normalize() * contact_dist;
+
+        let jump_time = jump_duration(intercept.ball_loc.z + Self::CONTACT_Z_OFFSET, gravity);
+        let drive_time = intercept.time - jump_time;
+        if drive_time <= 0.0 {
+            // We keep our momentum through the jump, so check where we'd be
+            // when we get up there.
+            let contact_loc = me_loc + me.Physics.vel().to_2d() * jump_time;
+            if (contact_loc - target_loc).norm() > Self::MAX_CONTACT_MISS {
+                ctx.eeg.log(self.name(), "too late to get under the ball");
+                return Action::Abort;
+            }
+            return Action::tail_call(double_jump(jump_time));
+        }
+
+        let start_speed = me.Physics.vel().norm().min(rl::CAR_MAX_SPEED);
+        let arrival = solve_arrival_then_cruise(
+            start_speed,
+            me.Boost as f32,
+            (target_loc - me_loc).norm(),
+            drive_time,
+            jump_time,
+        );
+        let (throttle, boost) = arrival.controls();
+
+        ctx.eeg.draw(Drawable::intercept_marker(
+            intercept.ball_loc,
+            intercept.time,
+        ));
+        ctx.eeg.print_time("drive_time", drive_time);
+        ctx.eeg.print_value("arrival", format!("{:?}", arrival));
+
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: throttle,
+            Steer: simple_steer_towards(&me.Physics, target_loc),
+            Boost: boost && me.Physics.vel().norm() < rl::CAR_ALMOST_MAX_SPEED,
+            ..Default::default()
+        })
+    }
+}
+
+fn jump_duration(z: f32, gravity: f32) -> f32 {
+    // The intercept predicate keeps us in range, but stay clear of the very top
+    // of the arc where `time_to_z()` has no margin for rounding error.
+    let clamped = z.min(car_double_jump::max_z(gravity) - 1.0);
+    car_double_jump::time_to_z(clamped, gravity).unwrap()
+}
+
+fn double_jump(jump_time: f32) -> Chain {
+    let second_jump_hold = 6.0 / 120.0;
+    let float_time = jump_time - car_double_jump::SECOND_JUMP_TIME - second_jump_hold;
+    Chain::new(Priority::Strike, vec_box![
+        Yielder::new(
+            rl::CAR_JUMP_FORCE_TIME,
+            common::halfway_house::PlayerInput {
+                Jump: true,
+                ..Default::default()
+            }
+        ),
+        Yielder::new(car_double_jump::RELEASE_TIME, Default::default()),
+        Yielder::new(second_jump_hold, common::halfway_house::PlayerInput {
+            Jump: true,
+            ..Default::default()
+        }),
+        // Float through the contact before handing control back.
+        Yielder::new(float_time.max(0.0) + 0.1, Default::default()),
+    ])
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::strike::PopHit,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn pop_a_ball_above_jump_height() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, 1000.0, 450.0),
+                ball_vel: Vector3::new(0.0, 0.0, 300.0),
+                car_loc: Point3::new(0.0, 0.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(PopHit::new())
+            .run_for_millis(2500);
+
+        let packet = test.sniff_packet();
+        assert!(packet.GameBall.Physics.vel().y >= 500.0);
+    }
+}
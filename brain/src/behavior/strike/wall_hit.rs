@@ -3,12 +3,12 @@ use crate::{
         movement::{Dodge, JumpAndTurn, Yielder},
         strike::grounded_hit::car_ball_contact_with_pitch,
     },
-    eeg::{Event, EEG},
+    eeg::{Drawable, Event, EEG},
     helpers::ball::BallFrame,
     routing::models::CarState,
     strategy::{Action, Behavior, Context, Context2, Priority},
     utils::{
-        geometry::flattener::Flattener,
+        geometry::{flattener::Flattener, UnfoldError},
         intercept_memory::{InterceptMemory, InterceptMemoryResult},
     },
 };
@@ -18,6 +18,7 @@ use common::{
     prelude::*,
     rl,
 };
+use derive_new::new;
 use nalgebra::{Isometry3, Point2, Point3, UnitComplex, UnitQuaternion, Vector3};
 use nameof::name_of_type;
 use simulate::{car_jump, Car1D};
@@ -77,9 +78,14 @@ impl Behavior for WallHit {
             }
         };
 
+        eeg.draw(Drawable::intercept_marker(
+            intercept_ball_loc,
+            intercept_time,
+        ));
+
         match calculate_approach(ctx, eeg, intercept_time, &path) {
             Step::Drive(throttle, boost) => drive(ctx.me(), &path, throttle, boost),
-            Step::Jump => jump(eeg, &path),
+            Step::Jump => jump(ctx, eeg, &path),
         }
     }
 }
@@ -107,8 +113,17 @@ fn check_intercept(ctx: &Context2<'_, '_>, ball: &BallFrame) -> Result<(), ()> {
         return Err(());
     }
 
-    let me_to_ground = me_surface.unfold(&ground)?;
-    let target_to_me = target_surface.unfold(&me_surface)?;
+    // A surface parallel to another one (e.g. the ceiling and the floor, or
+    // opposite walls) has no seam to drive across, so there's no way to reach
+    // the ball from here.
+    let me_to_ground = match me_surface.unfold(&ground) {
+        Ok(m) => m,
+        Err(UnfoldError::Parallel) => return Err(()),
+    };
+    let target_to_me = match target_surface.unfold(&me_surface) {
+        Ok(m) => m,
+        Err(UnfoldError::Parallel) => return Err(()),
+    };
     let target_to_ground = me_to_ground * target_to_me;
 
     let me_to_flat = Flattener::new(me_to_ground);
@@ -152,8 +167,26 @@ fn flat_target(
     }
 
     // Build the origami structure
-    let me_to_ground = me_surface.unfold(&ground)?;
-    let intercept_to_me = intercept_surface.unfold(&me_surface)?;
+    let me_to_ground = match me_surface.unfold(&ground) {
+        Ok(m) => m,
+        Err(UnfoldError::Parallel) => {
+            eeg.log(
+                name_of_type!(WallHit),
+                "our surface is parallel to the ground",
+            );
+            return Err(());
+        }
+    };
+    let intercept_to_me = match intercept_surface.unfold(&me_surface) {
+        Ok(m) => m,
+        Err(UnfoldError::Parallel) => {
+            eeg.log(
+                name_of_type!(WallHit),
+                "the intercept's surface is parallel to ours",
+            );
+            return Err(());
+        }
+    };
     let intercept_to_ground = me_to_ground * intercept_to_me;
     let ground_to_intercept = intercept_to_ground.inverse();
 
@@ -168,6 +201,7 @@ fn flat_target(
     let me_forward = me_to_flat * me.Physics.forward_axis();
     let me_to_ball = intercept_to_flat * *intercept_ball_loc - me_to_flat * me.Physics.loc();
     let steer = me_forward.angle_to(&me_to_ball.to_axis());
+    eeg.print_angle("intercept_angle", steer);
     if steer.abs() >= PI / 3.0 {
         eeg.track(Event::WallHitNotFacingTarget);
         eeg.log(name_of_type!(WallHit), "not facing the target");
@@ -231,7 +265,7 @@ fn calculate_approach(
     target_time: f32,
     path: &Path,
 ) -> Step {
-    let (jump_distance, jump_time) = calculate_jump(path);
+    let (jump_distance, jump_time) = calculate_jump(ctx.game.gravity(), path);
     let drive_time = target_time - jump_time;
 
     if drive_time < 0.0 {
@@ -246,7 +280,7 @@ fn calculate_approach(
         target_loc: path.ground_target_loc,
     };
 
-    let jump = SimJump;
+    let jump = SimJump::new(ctx.game.gravity());
 
     let trial = |throttle, boost| {
         let state = ctx.me().into();
@@ -291,9 +325,10 @@ fn calculate_approach(
     Step::Drive(throttle, boost)
 }
 
-fn calculate_jump(path: &Path) -> (f32, f32) {
+fn calculate_jump(gravity: f32, path: &Path) -> (f32, f32) {
     let jump_distance = path.ground_target_loc.z - rl::OCTANE_NEUTRAL_Z;
-    let jump_time = car_jump::jump_duration(&path.target_rot, jump_distance.max(0.001)).unwrap();
+    let jump_time =
+        car_jump::jump_duration(&path.target_rot, jump_distance.max(0.001), gravity).unwrap();
     assert!(jump_time < 1.0, "{}", jump_time);
     (jump_distance, jump_time)
 }
@@ -320,8 +355,8 @@ fn drive(
     })
 }
 
-fn jump(eeg: &mut EEG, path: &Path) -> Action {
-    let (_jump_distance, jump_time) = calculate_jump(path);
+fn jump(ctx: &Context2<'_, '_>, eeg: &mut EEG, path: &Path) -> Action {
+    let (_jump_distance, jump_time) = calculate_jump(ctx.game.gravity(), path);
 
     // If the ball is very close to the wall, don't jump; instead, just chip it off
     // the wall. This way we retain more control of our car.
@@ -384,18 +419,21 @@ impl SimDrive {
     }
 }
 
-struct SimJump;
+#[derive(new)]
+struct SimJump {
+    gravity: f32,
+}
 
 impl SimJump {
     fn simulate(&self, start: &CarState, time: f32, target_rot: &UnitQuaternion<f32>) -> CarState {
         let force_time = time.min(rl::CAR_JUMP_FORCE_TIME);
         let v_0 = start.vel + start.roof_axis().into_inner() * rl::CAR_JUMP_IMPULSE_SPEED;
-        let a = start.roof_axis().into_inner() + Vector3::z() * rl::GRAVITY;
+        let a = start.roof_axis().into_inner() + Vector3::z() * self.gravity;
         let (d, vel) = kinematic(v_0, a, force_time);
         let loc = start.loc + d;
 
         let coast_time = (force_time - rl::CAR_JUMP_FORCE_TIME).max(0.0);
-        let a = Vector3::z() * rl::GRAVITY;
+        let a = Vector3::z() * self.gravity;
         let (d, vel) = kinematic(vel, a, coast_time);
         let loc = loc + d;
 
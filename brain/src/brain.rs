@@ -1,6 +1,7 @@
 #[cfg(test)]
 use crate::strategy::Behavior;
 use crate::{
+    behavior::PreKickoff,
     eeg::{color, Drawable, EEG},
     helpers::ball::{BallPredictor, ChipBallPrediction, FrameworkBallPrediction},
     strategy::{infer_game_mode, Context, Dropshot, Game, Runner, Scenario, Soccar},
@@ -19,6 +20,16 @@ pub struct Brain {
     /// This is not automated or enforced in any way, it's just a convenient
     /// memory slot for optional use in behaviors.
     last_quick_chat: f32,
+    last_planned_path: Option<Vec<Point3<f32>>>,
+    /// The game time of the last tick where the ball was set up for a kickoff.
+    last_kickoff: Option<f32>,
+    /// The game time of the last tick where our wheels were on the ground.
+    last_on_ground: Option<f32>,
+    /// The controls we returned on the previous tick.
+    last_input: common::halfway_house::PlayerInput,
+    boost_conservation_threshold: f32,
+    height_aware_possession: bool,
+    draw_ball_prediction: bool,
 }
 
 impl Brain {
@@ -29,9 +40,20 @@ impl Brain {
             player_index: None,
             fps_counter: FPSCounter::new(),
             last_quick_chat: 0.0,
+            last_planned_path: None,
+            last_kickoff: None,
+            last_on_ground: None,
+            last_input: Default::default(),
+            boost_conservation_threshold: Game::DEFAULT_BOOST_CONSERVATION_THRESHOLD,
+            height_aware_possession: false,
+            draw_ball_prediction: false,
         }
     }
 
+    /// The spacing between points when drawing the ball prediction. Drawing
+    /// every frame would be hundreds of line segments.
+    const BALL_PREDICTION_DRAW_DT: f32 = 0.1;
+
     // This is just here so it's exported from the crate since I'm lazy
     pub fn infer_game_mode(field_info: rlbot::flat::FieldInfo<'_>) -> rlbot::GameMode {
         infer_game_mode(field_info)
@@ -41,6 +63,15 @@ impl Brain {
         Self::new(Runner::new(Soccar::new()), ChipBallPrediction::new())
     }
 
+    /// Like `soccar`, but play any ball higher than `max_shot_z` once it comes
+    /// back down instead of going up for it. See `Soccar::max_shot_z`.
+    pub fn soccar_with_max_shot_z(max_shot_z: f32) -> Self {
+        Self::new(
+            Runner::new(Soccar::new().max_shot_z(max_shot_z)),
+            ChipBallPrediction::new(),
+        )
+    }
+
     pub fn dropshot(rlbot: &'static rlbot::RLBot) -> Self {
         Self::new(
             Runner::new(Dropshot::new()),
@@ -60,16 +91,66 @@ impl Brain {
         Self::new(Runner::with_current(behavior), ChipBallPrediction::new())
     }
 
+    /// Swap out where the ball prediction comes from, e.g. to replay a
+    /// recording for a deterministic test.
+    #[cfg(test)]
+    pub fn set_ball_predictor(&mut self, ball_predictor: impl BallPredictor + 'static) {
+        self.ball_predictor = Box::new(ball_predictor);
+    }
+
     #[cfg(test)]
     pub fn set_behavior(&mut self, behavior: impl Behavior + 'static, eeg: &mut EEG) {
         eeg.log(name_of_type!(Brain), format!("! {}", behavior.name()));
         self.runner = Runner::with_current(behavior);
     }
 
+    /// Offer `behavior` to the runner as an interruption. Returns whether its
+    /// priority was high enough to take over.
+    #[cfg(test)]
+    pub fn inject_behavior(&mut self, behavior: Box<dyn Behavior>, eeg: &mut EEG) -> bool {
+        let name = behavior.name().to_string();
+        let accepted = self.runner.interrupt(behavior);
+        if accepted {
+            eeg.log(name_of_type!(Brain), format!("! {}", name));
+        }
+        accepted
+    }
+
     pub fn set_player_index(&mut self, player_index: i32) {
         self.player_index = Some(player_index);
     }
 
+    /// Below this much boost, the bot stops spending boost on marginal speed
+    /// gains. Pass `0.0` to disable conservation.
+    pub fn set_boost_conservation_threshold(&mut self, threshold: f32) {
+        self.boost_conservation_threshold = threshold;
+    }
+
+    /// Judge possession by who can actually touch the ball, not just who can
+    /// get underneath it first. See `Scenario::height_aware_possession`.
+    pub fn set_height_aware_possession(&mut self, height_aware_possession: bool) {
+        self.height_aware_possession = height_aware_possession;
+    }
+
+    /// Draw the predicted ball path in the EEG every tick. Useful for
+    /// eyeballing whether intercepts line up with the prediction.
+    pub fn set_draw_ball_prediction(&mut self, draw: bool) {
+        self.draw_ball_prediction = draw;
+    }
+
+    /// Returns true once `set_player_index` has been called. Until then,
+    /// `tick` can only return neutral inputs.
+    pub fn has_player_index(&self) -> bool {
+        self.player_index.is_some()
+    }
+
+    /// Returns points sampled along the route the car followed during the last
+    /// tick, for drawing with an external renderer. Returns `None` if no route
+    /// was being followed.
+    pub fn last_planned_path(&self) -> Option<Vec<Point3<f32>>> {
+        self.last_planned_path.clone()
+    }
+
     pub fn tick(
         &mut self,
         field_info: rlbot::flat::FieldInfo<'_>,
@@ -88,11 +169,11 @@ impl Brain {
 
         let mut result = self.determine_controls(field_info, packet, eeg);
 
-        result.Throttle = clamp(result.Throttle, -1.0, 1.0);
-        result.Steer = clamp(result.Steer, -1.0, 1.0);
-        result.Pitch = clamp(result.Pitch, -1.0, 1.0);
-        result.Yaw = clamp(result.Yaw, -1.0, 1.0);
-        result.Roll = clamp(result.Roll, -1.0, 1.0);
+        result.Throttle = clamp_input(eeg, "throttle", result.Throttle);
+        result.Steer = clamp_input(eeg, "steer", result.Steer);
+        result.Pitch = clamp_input(eeg, "pitch", result.Pitch);
+        result.Yaw = clamp_input(eeg, "yaw", result.Yaw);
+        result.Roll = clamp_input(eeg, "roll", result.Roll);
 
         eeg.draw(Drawable::print("-----------------------", color::GREEN));
         eeg.print_value("throttle", ControllerInput(result.Throttle));
@@ -104,6 +185,7 @@ impl Brain {
         eeg.print_value("boost", result.Boost);
         eeg.print_value("handbrake", result.Handbrake);
 
+        self.last_input = result;
         result
     }
 
@@ -113,13 +195,53 @@ impl Brain {
         packet: &common::halfway_house::LiveDataPacket,
         eeg: &mut EEG,
     ) -> common::halfway_house::PlayerInput {
+        self.last_planned_path = None;
+
+        let player_index = some_or_else!(self.player_index, {
+            log::warn!("player_index is not set yet; returning neutral input");
+            eeg.log(
+                name_of_type!(Brain),
+                "no player_index; returning neutral input",
+            );
+            return Default::default();
+        });
+
         let start = Instant::now();
 
-        let game = Game::new(field_info, packet, self.player_index.unwrap() as usize);
-        let scenario = Scenario::new(&game, &*self.ball_predictor, packet);
-        let mut ctx = Context::new(&game, packet, &scenario, eeg, &mut self.last_quick_chat);
+        if PreKickoff::is_kickoff(&packet.GameBall) {
+            self.last_kickoff = Some(packet.GameInfo.TimeSeconds);
+        }
+
+        let game = Game::new(field_info, packet, player_index as usize)
+            .boost_conservation_threshold(self.boost_conservation_threshold);
+        if game.me().OnGround {
+            self.last_on_ground = Some(packet.GameInfo.TimeSeconds);
+        }
+        let scenario = Scenario::new(&game, &*self.ball_predictor, packet)
+            .height_aware_possession(self.height_aware_possession);
+        let mut ctx = Context::new(
+            &game,
+            packet,
+            &scenario,
+            eeg,
+            &mut self.last_quick_chat,
+            &mut self.last_planned_path,
+            self.last_kickoff,
+            self.last_on_ground,
+            self.last_input,
+        );
 
         ctx.eeg.print_time("possession", ctx.scenario.possession());
+        ctx.eeg
+            .print_value("phase", format!("{:?}", ctx.scenario.phase()));
+        if self.draw_ball_prediction {
+            let frames: Vec<_> = ctx
+                .scenario
+                .ball_prediction()
+                .iter_step_by(Self::BALL_PREDICTION_DRAW_DT)
+                .collect();
+            ctx.eeg.draw(Drawable::ball_prediction(&frames));
+        }
 
         let result = self.runner.execute_old(&mut ctx);
 
@@ -138,7 +260,95 @@ impl Brain {
     }
 }
 
+/// Clamp a controller axis to `[-1, 1]`, and make some noise if it was out of
+/// range.
+fn clamp_input(eeg: &mut EEG, name: &str, value: f32) -> f32 {
+    if !(-1.0..=1.0).contains(&value) {
+        eeg.log(
+            name_of_type!(Brain),
+            format!("clamped {} from {:.2}", name, value),
+        );
+        eeg.clamped_inputs += 1;
+    }
+    clamp(value, -1.0, 1.0)
+}
+
 fn format_fps(fps: Option<usize>) -> String {
     fps.map(|x| format!("{:.0}", x))
         .unwrap_or_else(|| "...".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{brain::Brain, eeg::EEG};
+    use common::halfway_house::{LiveDataPacket, PlayerInfo};
+
+    #[test]
+    fn tick_before_player_index() {
+        let mut builder = flatbuffers::FlatBufferBuilder::new();
+        let field_info = rlbot::flat::FieldInfo::create(&mut builder, &Default::default());
+        builder.finish(field_info, None);
+        let field_info =
+            flatbuffers::get_root::<rlbot::flat::FieldInfo<'_>>(builder.finished_data());
+
+        let packet = LiveDataPacket {
+            GameCars: vec![PlayerInfo::default()].into(),
+            NumCars: 1,
+            GameBall: Default::default(),
+            GameBoosts: Default::default(),
+            GameInfo: Default::default(),
+            Teams: Default::default(),
+            NumTeams: 0,
+        };
+
+        let mut brain = Brain::soccar();
+        let mut eeg = EEG::new();
+        assert!(!brain.has_player_index());
+
+        let input = brain.tick(field_info, &packet, &mut eeg);
+        assert_eq!(input.Throttle, 0.0);
+        assert_eq!(input.Steer, 0.0);
+        assert!(!input.Jump);
+        assert!(!input.Boost);
+
+        brain.set_player_index(0);
+        assert!(brain.has_player_index());
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        integration_tests::{TestRunner, TestScenario},
+        strategy::{Action, Behavior, Context},
+    };
+    use nameof::name_of_type;
+
+    struct OversteerForever;
+
+    impl Behavior for OversteerForever {
+        fn name(&self) -> &str {
+            name_of_type!(OversteerForever)
+        }
+
+        fn execute_old(&mut self, _ctx: &mut Context<'_>) -> Action {
+            Action::Yield(common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                Steer: 3.0,
+                ..Default::default()
+            })
+        }
+    }
+
+    #[test]
+    fn report_clamped_inputs() {
+        let test = TestRunner::new()
+            .scenario(TestScenario::default())
+            .behavior(OversteerForever)
+            .run_for_millis(500);
+
+        test.examine_clamped_inputs(|count| {
+            assert!(count > 0);
+        });
+    }
+}
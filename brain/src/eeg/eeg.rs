@@ -1,4 +1,7 @@
-use crate::eeg::{color, window::Window};
+use crate::{
+    eeg::{color, window::Window},
+    helpers::ball::BallFrame,
+};
 use common::{prelude::*, rl, Angle, Distance, PrettyPrint, Time};
 use graphics::types::Color;
 use nalgebra::{Point2, Point3, Rotation3};
@@ -10,6 +13,12 @@ pub struct EEG {
     current_packet_time: f32,
     draw_list: DrawList,
     pub events: Option<HashSet<Event>>,
+    /// The number of controller inputs that were outside their valid range
+    /// and had to be clamped. A nonzero value means some behavior is doing
+    /// something fishy.
+    pub clamped_inputs: usize,
+    /// The number of times `FollowRoute` has planned a route from scratch.
+    pub route_plans: usize,
     // I added quick-chat here only for convenience before a tournament, but it should really be
     // somewhere else…
     pub quick_chat: Option<rlbot::flat::QuickChatSelection>,
@@ -19,6 +28,7 @@ pub struct EEG {
 pub enum Event {
     Defense,
     Retreat,
+    RetreatFacingPlay,
     HitToOwnCorner,
     PushFromLeftToRight,
     PushFromRightToLeft,
@@ -31,6 +41,15 @@ pub enum Event {
     PanicDefense,
     WallHitFinishedWithoutJump,
     WallHitNotFacingTarget,
+    NudgeToTeammate,
+    FarPostCover,
+    KickoffFake,
+    AbsorbBall,
+    Unstick,
+    ResetToNet,
+    FollowRouteEnemyCommit,
+    ShootInterceptBehind,
+    HoldPosition,
 }
 
 impl EEG {
@@ -42,6 +61,8 @@ impl EEG {
             current_packet_time: 0.0,
             draw_list: DrawList::new(),
             events: None,
+            clamped_inputs: 0,
+            route_plans: 0,
             quick_chat: None,
         }
     }
@@ -153,6 +174,8 @@ pub enum Drawable {
     Line(Point2<f32>, Point2<f32>, Color),
     Arc(Point2<f32>, f32, f32, f32, Color),
     Print(String, Color),
+    InterceptMarker(Point3<f32>, f32),
+    Polyline(Vec<Point2<f32>>, Color),
 }
 
 impl Drawable {
@@ -167,4 +190,19 @@ impl Drawable {
     pub fn print(text: impl Into<String>, color: Color) -> Self {
         Drawable::Print(text.into(), color)
     }
+
+    /// Marks where we expect to hit the ball, along with a readout of how long
+    /// until we get there and how high the ball will be.
+    /// Like everything else, it only lasts for the frame it was drawn in, so
+    /// once there's no intercept to draw, it goes away.
+    pub fn intercept_marker(loc: Point3<f32>, time: f32) -> Self {
+        Drawable::InterceptMarker(loc, time)
+    }
+
+    /// Traces the ball's predicted path along the ground. Pass in frames from
+    /// `iter_step_by` rather than the whole trajectory, since every frame
+    /// becomes a line segment.
+    pub fn ball_prediction(frames: &[BallFrame]) -> Self {
+        Drawable::Polyline(frames.iter().map(|f| f.loc.to_2d()).collect(), color::WHITE)
+    }
 }
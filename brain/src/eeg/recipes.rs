@@ -1,6 +1,6 @@
 //! This is just a dumping ground of marginally-useful visualization stuff.
 
-use common::prelude::*;
+use common::{prelude::*, rl};
 
 pub fn draw_ball_prediction(rlbot: &rlbot::RLBot, packet: &common::halfway_house::LiveDataPacket) {
     use chip::Ball;
@@ -13,7 +13,7 @@ pub fn draw_ball_prediction(rlbot: &rlbot::RLBot, packet: &common::halfway_house
     let green = rg.color_rgb(0, 255, 0);
     for _ in 0..(5 * 120 / 4) {
         for _ in 0..4 {
-            ball.step(1.0 / 120.0);
+            ball.step(rl::PHYSICS_DT);
         }
         let cur = ball.pos();
         rg.draw_line_3d((prev.x, prev.y, prev.z), (cur.x, cur.y, cur.z), green);
@@ -4,7 +4,7 @@ use crate::{
     eeg::{color, eeg::Drawable},
     strategy::Team,
 };
-use common::{prelude::*, rl};
+use common::{prelude::*, rl, Coordinate, Time};
 use graphics::{
     circle_arc, clear, ellipse, line, rectangle, text, Ellipse, Rectangle, Transformed,
 };
@@ -237,6 +237,28 @@ fn thread(rx: crossbeam_channel::Receiver<ThreadMessage>) {
                             Drawable::Print(txt, color) => {
                                 prints.push((txt, color));
                             }
+                            Drawable::InterceptMarker(loc, time) => {
+                                Ellipse::new_border(color::YELLOW, OUTLINE_RADIUS).draw(
+                                    ball_rect,
+                                    &Default::default(),
+                                    transform.trans(f64::from(loc.x), f64::from(loc.y)),
+                                    g,
+                                );
+                                let txt =
+                                    format!("intercept: {} z={}", Time(time), Coordinate(loc.z));
+                                prints.push((txt, color::YELLOW));
+                            }
+                            Drawable::Polyline(points, color) => {
+                                for pair in points.windows(2) {
+                                    let pts = [
+                                        f64::from(pair[0].x),
+                                        f64::from(pair[0].y),
+                                        f64::from(pair[1].x),
+                                        f64::from(pair[1].y),
+                                    ];
+                                    line(color, OUTLINE_RADIUS, pts, transform, g);
+                                }
+                            }
                         }
                     }
 
@@ -1,12 +1,30 @@
 use chip::Ball;
+#[cfg(test)]
+use collect::{RecordingRigidBodyState, RecordingTick};
 use common::{math::fractionality, prelude::*, rl, vector_iter};
 use derive_new::new;
-use nalgebra::{Point3, Vector3};
+use nalgebra::{Point2, Point3, Vector3};
 use ordered_float::OrderedFloat;
-use std::{iter::Cloned, slice::Iter};
+#[cfg(test)]
+use std::{cell::Cell, io::Read};
+use std::{
+    iter::{self, Cloned},
+    slice::Iter,
+};
 
 const PREDICT_DURATION: f32 = 7.0;
 
+/// Prediction confidence halves every this many seconds.
+const CONFIDENCE_HALF_LIFE: f32 = 3.0;
+
+/// Each bounce or wall contact multiplies the confidence by this much, since
+/// small errors in the ball's spin or the contact point get amplified.
+const CONFIDENCE_BOUNCE_FACTOR: f32 = 0.6;
+
+/// A change in velocity between two frames bigger than gravity can explain by
+/// at least this much means the ball hit something.
+const BOUNCE_DELTA_V: f32 = 50.0;
+
 pub struct BallTrajectory {
     frames: Vec<BallFrame>,
 }
@@ -17,16 +35,48 @@ pub struct BallFrame {
     dt: f32,
     pub loc: Point3<f32>,
     pub vel: Vector3<f32>,
+    ang_vel: Vector3<f32>,
 }
 
 impl BallFrame {
+    #[cfg(test)]
+    pub fn new(t: f32, dt: f32, loc: Point3<f32>, vel: Vector3<f32>) -> Self {
+        Self {
+            t,
+            dt,
+            loc,
+            vel,
+            ang_vel: Vector3::zeros(),
+        }
+    }
+
     pub fn dt(&self) -> f32 {
         self.dt
     }
+
+    /// The ball's spin, in radians per second.
+    pub fn ang_vel(&self) -> Vector3<f32> {
+        self.ang_vel
+    }
+
+    /// Returns false if the ball has escaped the arena. This can't happen in a
+    /// real game, but the prediction sometimes clips through a wall or the
+    /// floor, and every frame after that is garbage.
+    ///
+    /// Only the y bound is loosened enough to cover the inside of the goals;
+    /// the side walls and ceiling just get a little slop for rounding.
+    pub fn is_in_bounds(&self) -> bool {
+        const SLOP: f32 = 50.0;
+        const GOAL_DEPTH: f32 = 880.0;
+        self.loc.x.abs() < rl::FIELD_MAX_X + SLOP
+            && self.loc.y.abs() < rl::FIELD_MAX_Y + GOAL_DEPTH + SLOP
+            && self.loc.z > 0.0
+            && self.loc.z < rl::FIELD_MAX_Z + SLOP
+    }
 }
 
 impl BallTrajectory {
-    fn new(frames: Vec<BallFrame>) -> Self {
+    pub fn new(frames: Vec<BallFrame>) -> Self {
         assert!(!frames.is_empty());
         Self { frames }
     }
@@ -45,36 +95,103 @@ impl BallTrajectory {
         self.frames.iter()
     }
 
+    /// Iterate over the frames, `dt` seconds apart. If `dt` isn't a multiple
+    /// of the prediction's frame interval, it's rounded to the nearest one (but
+    /// never less than one frame), and the frames report the rounded step in
+    /// their `dt`.
     pub fn iter_step_by<'a>(&'a self, dt: f32) -> impl Iterator<Item = BallFrame> + 'a {
-        let factor = dt / self.frames[0].dt;
-        // Enforce integral divisions.
-        assert!(fractionality(factor) <= 1e-5);
-        let factor = factor.round();
+        let factor = step_factor(self.frames[0].dt, dt);
+        let step_dt = factor as f32 * self.frames[0].dt;
+        self.frames
+            .iter()
+            .step_by(factor)
+            .map(move |f| BallFrame { dt: step_dt, ..*f })
+    }
 
+    /// Iterate over the frames roughly `dt` seconds apart, picking whichever
+    /// frame is nearest to each multiple of `dt`. Unlike `iter_step_by`, `dt`
+    /// can be anything, at the cost of the steps being uneven by up to a frame.
+    /// The frames report `dt` as their step.
+    pub fn iter_step_by_approx<'a>(&'a self, dt: f32) -> impl Iterator<Item = BallFrame> + 'a {
+        let frame_dt = self.frames[0].dt;
+        let dt = dt.max(frame_dt);
+        let mut next_t = self.start().t;
         self.frames
             .iter()
-            .step_by(factor as usize)
+            .filter(move |f| {
+                if f.t < next_t - frame_dt / 2.0 {
+                    return false;
+                }
+                next_t += dt;
+                true
+            })
             .map(move |f| BallFrame { dt, ..*f })
     }
 
     /// Iterate over the frames, but skip the given number of seconds at the
     /// start.
+    #[deprecated(note = "use `slice` or `slice_from` instead")]
     pub fn hacky_expensive_slice(&self, delay: f32) -> Self {
-        let frames = match self.frames.iter().position(|f| f.t >= delay) {
-            Some(i) => {
-                let start_time = self.frames[i].t;
-                self.frames[i..]
-                    .iter()
-                    .map(|f| BallFrame {
-                        t: f.t - start_time,
-                        ..*f
-                    })
-                    .collect()
-            }
-            None => vec![self.frames.last().unwrap().clone()],
+        self.slice_from(delay)
+    }
+
+    /// Returns the trajectory starting `delay` seconds from now, re-timed so
+    /// the new first frame is at `t = 0`. The first frame is interpolated to
+    /// land exactly on `delay`, so no sub-frame precision is lost.
+    ///
+    /// This copies the frames. If a borrowed view will do, use `slice`.
+    pub fn slice_from(&self, delay: f32) -> Self {
+        self.slice(delay).to_trajectory()
+    }
+
+    /// Like `slice_from`, but borrows the frames instead of copying them.
+    pub fn slice(&self, delay: f32) -> BallTrajectorySlice<'_> {
+        let first = some_or_else!(self.at_time_interpolated(delay), {
+            return BallTrajectorySlice {
+                first: BallFrame {
+                    t: 0.0,
+                    ..self.last().clone()
+                },
+                rest: &[],
+                delay,
+            };
+        });
+
+        // Index of the first frame strictly after the interpolated one.
+        let i = match self
+            .frames
+            .binary_search_by_key(&OrderedFloat(delay), |f| OrderedFloat(f.t))
+        {
+            Ok(i) => i + 1,
+            Err(i) => i,
+        };
+
+        BallTrajectorySlice {
+            first: BallFrame { t: 0.0, ..first },
+            rest: &self.frames[i..],
+            delay,
+        }
+    }
+
+    /// Returns the ball's state at exactly time `t`, linearly interpolating
+    /// between the two nearest frames. Returns `None` if `t` is past the end of
+    /// the prediction.
+    pub fn at_time_interpolated(&self, t: f32) -> Option<BallFrame> {
+        let i = match self
+            .frames
+            .binary_search_by_key(&OrderedFloat(t), |f| OrderedFloat(f.t))
+        {
+            Ok(i) => return Some(self.frames[i].clone()),
+            Err(i) => i,
         };
+        if i == 0 {
+            return Some(self.start().clone());
+        }
+        if i >= self.frames.len() {
+            return None;
+        }
 
-        Self::new(frames)
+        Some(interpolate(&self.frames[i - 1], &self.frames[i], t))
     }
 
     pub fn at_time(&self, t: f32) -> Option<&BallFrame> {
@@ -94,6 +211,70 @@ impl BallTrajectory {
     pub fn at_time_or_last(&self, t: f32) -> &BallFrame {
         self.at_time(t).unwrap_or_else(|| self.last())
     }
+
+    /// Returns the first frame where the ball (as seen from above) is inside
+    /// the given circle. If the ball starts out inside the circle, that's the
+    /// first frame.
+    pub fn first_entry_into_circle(&self, center: Point2<f32>, radius: f32) -> Option<&BallFrame> {
+        self.frames
+            .iter()
+            .find(|f| (f.loc.to_2d() - center).norm() < radius)
+    }
+
+    /// How much to trust the prediction `t` seconds from now, from 1.0 (the
+    /// present) down towards 0.0. Confidence decays steadily over time, and
+    /// drops sharply with each bounce along the way. Past the end of the
+    /// prediction, there's nothing to trust at all. `gravity` is the match's,
+    /// from `Game::gravity`.
+    pub fn confidence_at(&self, t: f32, gravity: f32) -> f32 {
+        if t > self.last().t {
+            return 0.0;
+        }
+        let bounces = self
+            .frames
+            .windows(2)
+            .take_while(|w| w[1].t <= t)
+            .filter(|w| is_bounce(&w[0], &w[1], gravity))
+            .count();
+        0.5_f32.powf(t.max(0.0) / CONFIDENCE_HALF_LIFE)
+            * CONFIDENCE_BOUNCE_FACTOR.powi(bounces as i32)
+    }
+
+    /// Returns the first frame where the ball is on (or just about to touch)
+    /// the ground, i.e., the next chance to play it without jumping. If the
+    /// ball is already rolling, that's the first frame.
+    pub fn next_ground_contact(&self) -> Option<&BallFrame> {
+        // The prediction is discrete, so a falling ball might never be sampled
+        // exactly at the moment of impact.
+        const MARGIN: f32 = 20.0;
+
+        self.frames
+            .iter()
+            .find(|f| f.loc.z < rl::BALL_RADIUS + MARGIN && f.vel.z <= 0.0)
+    }
+
+    /// Returns every time the ball bounces off the floor, a wall, or the
+    /// ceiling. Each frame returned is the one where the ball is closest to
+    /// the surface it bounced off. `gravity` is the match's, from
+    /// `Game::gravity`.
+    pub fn bounces(&self, gravity: f32) -> Vec<BallFrame> {
+        let mut bounces = Vec::new();
+        // A single contact can span several frames, so track the closest
+        // approach until it's over.
+        let mut contact: Option<&BallFrame> = None;
+        for w in self.frames.windows(2) {
+            if !is_bounce(&w[0], &w[1], gravity) {
+                bounces.extend(contact.take().cloned());
+                continue;
+            }
+            contact = contact
+                .into_iter()
+                .chain(w)
+                .min_by_key(|f| OrderedFloat(clearance(f)));
+        }
+        bounces.extend(contact.cloned());
+        bounces
+    }
 }
 
 impl<'a> IntoIterator for &'a BallTrajectory {
@@ -105,6 +286,153 @@ impl<'a> IntoIterator for &'a BallTrajectory {
     }
 }
 
+/// A borrowed view of the tail end of a `BallTrajectory`, re-timed so it
+/// starts at `t = 0`. Frames are re-timed as they're read, so creating one is
+/// cheap no matter how long the trajectory is. See `BallTrajectory::slice`.
+#[derive(Clone)]
+pub struct BallTrajectorySlice<'a> {
+    /// The interpolated first frame, already re-timed.
+    first: BallFrame,
+    /// The parent's frames after `first`, not yet re-timed.
+    rest: &'a [BallFrame],
+    delay: f32,
+}
+
+impl<'a> BallTrajectorySlice<'a> {
+    pub fn start(&self) -> &BallFrame {
+        &self.first
+    }
+
+    pub fn last(&self) -> BallFrame {
+        match self.rest.last() {
+            Some(frame) => self.retime(frame),
+            None => self.first.clone(),
+        }
+    }
+
+    pub fn iter<'s>(&'s self) -> impl Iterator<Item = BallFrame> + 's {
+        iter::once(self.first.clone()).chain(self.rest.iter().map(move |f| self.retime(f)))
+    }
+
+    /// See `BallTrajectory::iter_step_by`.
+    pub fn iter_step_by<'s>(&'s self, dt: f32) -> impl Iterator<Item = BallFrame> + 's {
+        let factor = step_factor(self.first.dt, dt);
+        let step_dt = factor as f32 * self.first.dt;
+        self.iter()
+            .step_by(factor)
+            .map(move |f| BallFrame { dt: step_dt, ..f })
+    }
+
+    /// See `BallTrajectory::at_time`.
+    pub fn at_time(&self, t: f32) -> Option<BallFrame> {
+        if t <= 0.0 {
+            return Some(self.first.clone());
+        }
+        let i = match self
+            .rest
+            .binary_search_by_key(&OrderedFloat(t + self.delay), |f| OrderedFloat(f.t))
+        {
+            Ok(i) => i,
+            Err(i) => i,
+        };
+        self.rest.get(i).map(|f| self.retime(f))
+    }
+
+    pub fn at_time_or_last(&self, t: f32) -> BallFrame {
+        self.at_time(t).unwrap_or_else(|| self.last())
+    }
+
+    /// Returns the part of this slice starting `delay` seconds after its start,
+    /// re-timed so it starts at `t = 0`. Like `BallTrajectory::slice`, this
+    /// borrows the same frames instead of copying them.
+    pub fn slice(&self, delay: f32) -> BallTrajectorySlice<'a> {
+        if delay <= 0.0 {
+            return self.clone();
+        }
+
+        let (first, i) = match self
+            .rest
+            .binary_search_by_key(&OrderedFloat(delay + self.delay), |f| OrderedFloat(f.t))
+        {
+            Ok(i) => (self.retime(&self.rest[i]), i + 1),
+            Err(i) if i == self.rest.len() => (self.last(), i),
+            Err(0) => (
+                interpolate(&self.first, &self.retime(&self.rest[0]), delay),
+                0,
+            ),
+            Err(i) => {
+                let prev = self.retime(&self.rest[i - 1]);
+                (interpolate(&prev, &self.retime(&self.rest[i]), delay), i)
+            }
+        };
+
+        BallTrajectorySlice {
+            first: BallFrame { t: 0.0, ..first },
+            rest: &self.rest[i..],
+            delay: self.delay + delay,
+        }
+    }
+
+    /// Copy the frames into a standalone trajectory.
+    pub fn to_trajectory(&self) -> BallTrajectory {
+        BallTrajectory::new(self.iter().collect())
+    }
+
+    fn retime(&self, frame: &BallFrame) -> BallFrame {
+        BallFrame {
+            t: frame.t - self.delay,
+            ..*frame
+        }
+    }
+}
+
+/// The ball's state at time `t`, linearly interpolated between two frames.
+fn interpolate(prev: &BallFrame, next: &BallFrame, t: f32) -> BallFrame {
+    let a = (t - prev.t) / (next.t - prev.t);
+    BallFrame {
+        t,
+        dt: prev.dt,
+        loc: prev.loc + (next.loc - prev.loc) * a,
+        vel: prev.vel + (next.vel - prev.vel) * a,
+        ang_vel: prev.ang_vel + (next.ang_vel - prev.ang_vel) * a,
+    }
+}
+
+/// How many frames to advance per step to move `dt` seconds at a time. If `dt`
+/// isn't a multiple of `frame_dt`, it's rounded to the nearest one (but never
+/// less than one frame).
+fn step_factor(frame_dt: f32, dt: f32) -> usize {
+    let factor = (dt / frame_dt).round().max(1.0);
+    if fractionality(dt / frame_dt) > 1e-3 {
+        log::warn!(
+            "iter_step_by: dt {} is not a multiple of {}; using {}",
+            dt,
+            frame_dt,
+            factor * frame_dt,
+        );
+    }
+    factor as usize
+}
+
+/// Returns true if the ball's velocity changed between two frames by more than
+/// gravity can account for. A rolling ball has the floor cancelling out
+/// gravity, so that much is allowed for too.
+fn is_bounce(prev: &BallFrame, next: &BallFrame, gravity: f32) -> bool {
+    let dt = next.t - prev.t;
+    let expected_dv = Vector3::z() * gravity * dt;
+    (next.vel - prev.vel - expected_dv).norm() >= BOUNCE_DELTA_V - gravity * dt
+}
+
+/// The distance from the ball's center to the nearest surface of the arena.
+/// This treats the arena as a box, ignoring the goals and the curved corners.
+fn clearance(frame: &BallFrame) -> f32 {
+    let loc = frame.loc;
+    loc.z
+        .min(rl::FIELD_MAX_Z - loc.z)
+        .min(rl::FIELD_MAX_X - loc.x.abs())
+        .min(rl::FIELD_MAX_Y - loc.y.abs())
+}
+
 pub trait BallPredictor {
     fn predict(&self, packet: &common::halfway_house::LiveDataPacket) -> BallTrajectory;
 }
@@ -116,6 +444,7 @@ impl BallPredictor for ChipBallPrediction {
     fn predict(&self, packet: &common::halfway_house::LiveDataPacket) -> BallTrajectory {
         const DT: f32 = rl::PHYSICS_DT;
 
+        let gravity = packet.GameInfo.gravity();
         let mut ball = Ball::new();
         ball.set_pos(packet.GameBall.Physics.loc());
         ball.set_vel(packet.GameBall.Physics.vel());
@@ -132,16 +461,23 @@ impl BallPredictor for ChipBallPrediction {
             dt: DT,
             loc: ball.pos(),
             vel: ball.vel(),
+            ang_vel: ball.omega(),
         });
 
         while frames.len() < num_frames {
             t += DT;
             ball.step(DT);
+            if gravity != rl::GRAVITY {
+                let (loc, vel) = correct_gravity(ball.pos(), ball.vel(), gravity, DT);
+                ball.set_pos(loc);
+                ball.set_vel(vel);
+            }
             frames.push(BallFrame {
                 t,
                 dt: DT,
                 loc: ball.pos(),
                 vel: ball.vel(),
+                ang_vel: ball.omega(),
             });
         }
 
@@ -149,29 +485,169 @@ impl BallPredictor for ChipBallPrediction {
     }
 }
 
+/// Chip always simulates standard gravity. After each step, make up the
+/// difference between that and the match's actual gravity. This only happens
+/// in flight; a rolling ball has the floor holding it up regardless.
+fn correct_gravity(
+    loc: Point3<f32>,
+    vel: Vector3<f32>,
+    gravity: f32,
+    dt: f32,
+) -> (Point3<f32>, Vector3<f32>) {
+    if loc.z < rl::BALL_RADIUS + 1.0 {
+        return (loc, vel);
+    }
+    let dv = Vector3::z() * (gravity - rl::GRAVITY) * dt;
+    (loc + dv * dt, vel + dv)
+}
+
 #[derive(new)]
 pub struct FrameworkBallPrediction {
     rlbot: &'static rlbot::RLBot,
 }
 
+impl FrameworkBallPrediction {
+    /// The framework predicts 6 seconds at 60 Hz. Anything much shorter than
+    /// that means something went wrong (e.g., the game is paused, or the
+    /// prediction hasn't started yet).
+    const MIN_FRAMES: usize = 60;
+}
+
 impl BallPredictor for FrameworkBallPrediction {
-    fn predict(&self, _packet: &common::halfway_house::LiveDataPacket) -> BallTrajectory {
-        const DT: f32 = 1.0 / 60.0;
-
-        let packet = self.rlbot.interface().get_ball_prediction().unwrap();
-        let start_time = packet.slices().unwrap().get(0).gameSeconds();
-        let frames = vector_iter(packet.slices().unwrap())
-            .map(|slice| BallFrame {
-                t: slice.gameSeconds() - start_time,
-                dt: DT,
-                loc: point3(slice.physics().unwrap().location().unwrap()),
-                vel: vector3(slice.physics().unwrap().velocity().unwrap()),
+    fn predict(&self, packet: &common::halfway_house::LiveDataPacket) -> BallTrajectory {
+        const DT: f32 = rl::FRAMEWORK_PREDICTION_DT;
+
+        let prediction = self.rlbot.interface().get_ball_prediction();
+        let slices = prediction.as_ref().and_then(|p| p.slices());
+        let frames = match slices {
+            Some(slices) if slices.len() > 0 => {
+                let start_time = slices.get(0).gameSeconds();
+                vector_iter(slices)
+                    .map(|slice| BallFrame {
+                        t: slice.gameSeconds() - start_time,
+                        dt: DT,
+                        loc: point3(slice.physics().unwrap().location().unwrap()),
+                        vel: vector3(slice.physics().unwrap().velocity().unwrap()),
+                        ang_vel: vector3(slice.physics().unwrap().angularVelocity().unwrap()),
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        frames_or_fallback(frames, Self::MIN_FRAMES, packet)
+    }
+}
+
+/// Replays a recorded ball path instead of simulating one, so tests see the
+/// same prediction no matter what the ball in the packet is doing. The
+/// recording starts playing from the first packet it's asked about, and after
+/// that only the packet's game time is used.
+#[cfg(test)]
+pub struct RecordedBallPrediction {
+    recording: BallTrajectory,
+    start_time: Cell<Option<f32>>,
+}
+
+#[cfg(test)]
+impl RecordedBallPrediction {
+    /// `times` are in seconds, and the first one lines up with the first
+    /// packet.
+    pub fn new(times: &[f32], states: &[RecordingRigidBodyState]) -> Self {
+        assert_eq!(times.len(), states.len());
+        let frames = times
+            .iter()
+            .zip(states)
+            .map(|(&t, state)| BallFrame {
+                t: t - times[0],
+                dt: rl::PHYSICS_DT,
+                loc: state.loc,
+                vel: state.vel,
+                ang_vel: state.ang_vel,
             })
             .collect();
-        BallTrajectory::new(frames)
+        Self {
+            recording: BallTrajectory::new(frames),
+            start_time: Cell::new(None),
+        }
+    }
+
+    /// Reads the ball's path from a CSV recording, starting at `start_time`.
+    pub fn from_csv(r: impl Read, start_time: f32) -> Self {
+        let ticks: Vec<_> = RecordingTick::parse(r)
+            .skip_while(|r| r.time < start_time)
+            .collect();
+        let times: Vec<_> = ticks.iter().map(|t| t.time).collect();
+        let states: Vec<_> = ticks.into_iter().map(|t| t.ball).collect();
+        Self::new(&times, &states)
     }
 }
 
+#[cfg(test)]
+impl BallPredictor for RecordedBallPrediction {
+    fn predict(&self, packet: &common::halfway_house::LiveDataPacket) -> BallTrajectory {
+        const DT: f32 = rl::PHYSICS_DT;
+
+        let now = packet.GameInfo.TimeSeconds;
+        let start_time = self.start_time.get().unwrap_or(now);
+        self.start_time.set(Some(start_time));
+        let elapsed = now - start_time;
+
+        let num_frames = (PREDICT_DURATION / DT).ceil() as usize;
+        let mut frames = Vec::with_capacity(num_frames);
+        while frames.len() < num_frames {
+            let t = frames.len() as f32 * DT;
+            // Stop when the recording runs out.
+            let frame = some_or_else!(self.recording.at_time_interpolated(elapsed + t), {
+                break;
+            });
+            frames.push(BallFrame { t, dt: DT, ..frame });
+        }
+        frames_or_fallback(frames, 1, packet)
+    }
+}
+
+/// Use the given frames if there are enough of them. Otherwise, fall back to a
+/// crude prediction so we at least have something to work with.
+fn frames_or_fallback(
+    frames: Vec<BallFrame>,
+    min_frames: usize,
+    packet: &common::halfway_house::LiveDataPacket,
+) -> BallTrajectory {
+    if frames.len() >= min_frames {
+        return BallTrajectory::new(frames);
+    }
+    log::warn!(
+        "ball prediction only had {} frames; extrapolating instead",
+        frames.len(),
+    );
+    extrapolate_constant_velocity(packet)
+}
+
+/// Pretend the ball keeps going in a straight line for a short while. This
+/// ignores gravity and bounces, so it's only fit for emergencies.
+fn extrapolate_constant_velocity(packet: &common::halfway_house::LiveDataPacket) -> BallTrajectory {
+    const DURATION: f32 = 1.0;
+    const DT: f32 = rl::FRAMEWORK_PREDICTION_DT;
+
+    let loc = packet.GameBall.Physics.loc();
+    let vel = packet.GameBall.Physics.vel();
+    let ang_vel = packet.GameBall.Physics.ang_vel();
+    let num_frames = (DURATION / DT).ceil() as usize + 1;
+    let frames = (0..num_frames)
+        .map(|i| {
+            let t = i as f32 * DT;
+            BallFrame {
+                t,
+                dt: DT,
+                loc: loc + vel * t,
+                vel,
+                ang_vel,
+            }
+        })
+        .collect();
+    BallTrajectory::new(frames)
+}
+
 fn point3(v: &rlbot::flat::Vector3) -> Point3<f32> {
     Point3::new(v.x(), v.y(), v.z())
 }
@@ -179,3 +655,407 @@ fn point3(v: &rlbot::flat::Vector3) -> Point3<f32> {
 fn vector3(v: &rlbot::flat::Vector3) -> Vector3<f32> {
     Vector3::new(v.x(), v.y(), v.z())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::ball::{
+        correct_gravity, frames_or_fallback, BallFrame, BallPredictor, BallTrajectory,
+        RecordedBallPrediction,
+    };
+    use collect::RecordingRigidBodyState;
+    use common::{halfway_house::LiveDataPacket, prelude::*, rl};
+    use nalgebra::{Point2, Point3, UnitQuaternion, Vector3};
+
+    fn trajectory() -> BallTrajectory {
+        let frames = (0..10)
+            .map(|i| BallFrame {
+                t: i as f32 * 0.1,
+                dt: 0.1,
+                loc: Point3::new(i as f32 * 100.0, 0.0, 92.75),
+                vel: Vector3::new(1000.0, 0.0, 0.0),
+                ang_vel: Vector3::zeros(),
+            })
+            .collect();
+        BallTrajectory::new(frames)
+    }
+
+    #[test]
+    fn is_in_bounds() {
+        let frame = |x, y, z| BallFrame {
+            t: 0.0,
+            dt: 0.1,
+            loc: Point3::new(x, y, z),
+            vel: Vector3::zeros(),
+            ang_vel: Vector3::zeros(),
+        };
+        assert!(frame(0.0, 0.0, 92.75).is_in_bounds());
+        // In the back of the net.
+        assert!(frame(0.0, -5800.0, 92.75).is_in_bounds());
+        // Through the side wall or the ceiling.
+        assert!(!frame(rl::FIELD_MAX_X + 500.0, 0.0, 92.75).is_in_bounds());
+        assert!(!frame(0.0, 0.0, rl::FIELD_MAX_Z + 500.0).is_in_bounds());
+    }
+
+    #[test]
+    fn at_time_interpolated() {
+        let traj = trajectory();
+        let frame = traj.at_time_interpolated(0.25).unwrap();
+        assert!((frame.t - 0.25).abs() < 1e-5);
+        assert!((frame.loc.x - 250.0).abs() < 1e-3);
+        assert!(traj.at_time_interpolated(1.5).is_none());
+    }
+
+    #[test]
+    fn at_time_interpolated_spin() {
+        let frames = (0..2)
+            .map(|i| BallFrame {
+                t: i as f32 * 0.1,
+                dt: 0.1,
+                loc: Point3::new(0.0, 0.0, 92.75),
+                vel: Vector3::zeros(),
+                ang_vel: Vector3::new(0.0, i as f32 * 4.0, 0.0),
+            })
+            .collect();
+        let traj = BallTrajectory::new(frames);
+        let frame = traj.at_time_interpolated(0.025).unwrap();
+        assert!((frame.ang_vel().y - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn slice_from() {
+        let traj = trajectory();
+        let slice = traj.slice_from(0.25);
+        assert!((slice.start().t).abs() < 1e-5);
+        assert!((slice.start().loc.x - 250.0).abs() < 1e-3);
+        let second = slice.iter().nth(1).unwrap();
+        assert!((second.t - 0.05).abs() < 1e-5);
+        assert!((second.loc.x - 300.0).abs() < 1e-3);
+        assert!((slice.last().t - 0.65).abs() < 1e-5);
+    }
+
+    #[test]
+    fn slice_from_exact_frame() {
+        let traj = trajectory();
+        let slice = traj.slice_from(0.3);
+        assert_eq!(slice.iter().count(), 7);
+        assert!((slice.iter().nth(1).unwrap().t - 0.1).abs() < 1e-5);
+    }
+
+    #[test]
+    fn slice_matches_slice_from() {
+        let traj = trajectory();
+        for &delay in &[0.0, 0.25, 0.3, 0.85, 5.0] {
+            let view = traj.slice(delay);
+            let copy = traj.slice_from(delay);
+            assert_eq!(view.iter().count(), copy.iter().count());
+            for (a, b) in view.iter().zip(copy.iter()) {
+                assert!((a.t - b.t).abs() < 1e-5);
+                assert_eq!(a.loc, b.loc);
+            }
+            assert!((view.last().t - copy.last().t).abs() < 1e-5);
+            for &t in &[0.0, 0.05, 0.12, 0.63, 2.0] {
+                assert_eq!(
+                    view.at_time(t).map(|f| f.loc),
+                    copy.at_time(t).map(|f| f.loc),
+                );
+            }
+            let stepped: Vec<_> = view.iter_step_by(0.2).collect();
+            let expected: Vec<_> = copy.iter_step_by(0.2).collect();
+            assert_eq!(stepped.len(), expected.len());
+            for (a, b) in stepped.iter().zip(&expected) {
+                assert!((a.t - b.t).abs() < 1e-5);
+                assert_eq!(a.dt(), b.dt());
+            }
+        }
+    }
+
+    #[test]
+    fn slice_of_slice() {
+        let traj = trajectory();
+        for &(outer, inner) in &[(0.0, 0.25), (0.15, 0.1), (0.1, 0.12), (0.25, 5.0)] {
+            let nested = traj.slice(outer).slice(inner);
+            let direct = traj.slice(outer + inner);
+            assert_eq!(nested.iter().count(), direct.iter().count());
+            for (a, b) in nested.iter().zip(direct.iter()) {
+                assert!((a.t - b.t).abs() < 1e-5);
+                assert!((a.loc - b.loc).norm() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn first_entry_into_circle() {
+        // The ball rolls along the x axis, straight through the circle.
+        let traj = trajectory();
+        let frame = traj
+            .first_entry_into_circle(Point2::new(500.0, 50.0), 150.0)
+            .unwrap();
+        assert!((frame.loc.x - 400.0).abs() < 1e-3);
+
+        // This circle is well off to the side.
+        assert!(traj
+            .first_entry_into_circle(Point2::new(500.0, 500.0), 150.0)
+            .is_none());
+
+        // The ball starts out inside this one.
+        let frame = traj
+            .first_entry_into_circle(Point2::new(0.0, 0.0), 150.0)
+            .unwrap();
+        assert_eq!(frame.t, 0.0);
+    }
+
+    #[test]
+    fn slice_past_the_end() {
+        let traj = trajectory();
+        let slice = traj.slice_from(5.0);
+        assert_eq!(slice.iter().count(), 1);
+        assert!((slice.start().loc.x - 900.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn iter_step_by_integral() {
+        let traj = trajectory();
+        let frames: Vec<_> = traj.iter_step_by(0.2).collect();
+        assert_eq!(frames.len(), 5);
+        assert!((frames[1].t - 0.2).abs() < 1e-6);
+        assert!((frames[1].dt() - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn iter_step_by_non_integral() {
+        let traj = trajectory();
+        // 0.22 isn't a multiple of 0.1, so it should round to 0.2 instead of
+        // panicking.
+        let frames: Vec<_> = traj.iter_step_by(0.22).collect();
+        assert_eq!(frames.len(), 5);
+        assert!((frames[1].t - 0.2).abs() < 1e-6);
+        assert!((frames[1].dt() - 0.2).abs() < 1e-6);
+
+        // Steps shorter than a frame still make progress.
+        let frames: Vec<_> = traj.iter_step_by(0.01).collect();
+        assert_eq!(frames.len(), 10);
+    }
+
+    #[test]
+    fn iter_step_by_approx() {
+        let frames = (0..840)
+            .map(|i| BallFrame {
+                t: i as f32 / 120.0,
+                dt: 1.0 / 120.0,
+                loc: Point3::new(0.0, 0.0, 92.75),
+                vel: Vector3::zeros(),
+                ang_vel: Vector3::zeros(),
+            })
+            .collect();
+        let traj = BallTrajectory::new(frames);
+
+        for &dt in &[0.3, 0.33, 0.125, 1.0 / 120.0] {
+            let frames: Vec<_> = traj.iter_step_by_approx(dt).collect();
+            assert_eq!(frames.len(), (7.0 / dt).ceil() as usize, "{}", dt);
+            for (i, frame) in frames.iter().enumerate() {
+                assert!((frame.t - i as f32 * dt).abs() <= 1.0 / 240.0 + 1e-4);
+                assert_eq!(frame.dt(), dt);
+            }
+        }
+
+        // Steps shorter than a frame still make progress.
+        assert_eq!(traj.iter_step_by_approx(0.001).count(), 840);
+    }
+
+    #[test]
+    fn next_ground_contact() {
+        // A ball lobbed up and away, which comes back down about 1.85 seconds
+        // later.
+        let frames = (0..120)
+            .map(|i| {
+                let t = i as f32 / 60.0;
+                let z = 92.75 + 600.0 * t - 325.0 * t * t;
+                BallFrame {
+                    t,
+                    dt: 1.0 / 60.0,
+                    loc: Point3::new(0.0, 500.0 * t, z.max(92.75)),
+                    vel: Vector3::new(0.0, 500.0, 600.0 - 650.0 * t),
+                    ang_vel: Vector3::zeros(),
+                }
+            })
+            .collect();
+        let traj = BallTrajectory::new(frames);
+
+        let frame = traj.next_ground_contact().unwrap();
+        assert!((frame.t - 1.85).abs() < 0.05);
+        assert!((frame.loc.y - 925.0).abs() < 25.0);
+
+        // A rolling ball is already on the ground.
+        let rolling = trajectory();
+        assert_eq!(rolling.next_ground_contact().unwrap().t, 0.0);
+    }
+
+    #[test]
+    fn confidence_decays_over_time() {
+        let traj = trajectory();
+        assert!((traj.confidence_at(0.0, rl::GRAVITY) - 1.0).abs() < 1e-6);
+        assert!(traj.confidence_at(0.5, rl::GRAVITY) < traj.confidence_at(0.1, rl::GRAVITY));
+        assert!(traj.confidence_at(0.5, rl::GRAVITY) > 0.8);
+        assert_eq!(traj.confidence_at(5.0, rl::GRAVITY), 0.0);
+    }
+
+    #[test]
+    fn confidence_drops_after_bounce() {
+        // A ball falling straight down, which bounces off the floor at 0.5s.
+        let frames = (0..60)
+            .map(|i| {
+                let t = i as f32 / 60.0;
+                let vel_z = if t < 0.5 {
+                    -650.0 * t
+                } else {
+                    200.0 - 650.0 * (t - 0.5)
+                };
+                BallFrame {
+                    t,
+                    dt: 1.0 / 60.0,
+                    loc: Point3::new(0.0, 0.0, 200.0),
+                    vel: Vector3::new(0.0, 0.0, vel_z),
+                    ang_vel: Vector3::zeros(),
+                }
+            })
+            .collect();
+        let traj = BallTrajectory::new(frames);
+
+        let before = traj.confidence_at(0.45, rl::GRAVITY);
+        let after = traj.confidence_at(0.55, rl::GRAVITY);
+        assert!(before > 0.85);
+        assert!(after < before * 0.7);
+    }
+
+    /// A ball lobbed up and away, which comes back down at 1s, loses half its
+    /// vertical speed in the bounce, and lands again at 1.5s.
+    fn bouncing_ball() -> BallTrajectory {
+        let frames = (0..120)
+            .map(|i| {
+                let t = i as f32 / 60.0;
+                let (z, vel_z) = if t < 1.0 {
+                    (92.75 + 325.0 * t * (1.0 - t), 325.0 - 650.0 * t)
+                } else if t < 1.5 {
+                    let t = t - 1.0;
+                    (92.75 + 325.0 * t * (1.0 - 2.0 * t), 162.5 - 650.0 * t)
+                } else {
+                    (92.75, 0.0)
+                };
+                BallFrame {
+                    t,
+                    dt: 1.0 / 60.0,
+                    loc: Point3::new(0.0, 500.0 * t, z),
+                    vel: Vector3::new(0.0, 500.0, vel_z),
+                    ang_vel: Vector3::zeros(),
+                }
+            })
+            .collect();
+        BallTrajectory::new(frames)
+    }
+
+    #[test]
+    fn bounces() {
+        let bounces = bouncing_ball().bounces(rl::GRAVITY);
+        assert_eq!(bounces.len(), 2);
+        assert!((bounces[0].t - 1.0).abs() < 0.02, "{}", bounces[0].t);
+        assert!((bounces[1].t - 1.5).abs() < 0.02, "{}", bounces[1].t);
+        for bounce in &bounces {
+            assert!(bounce.loc.z < 100.0, "{}", bounce.loc.z);
+        }
+    }
+
+    #[test]
+    fn rolling_ball_never_bounces() {
+        assert!(trajectory().bounces(rl::GRAVITY).is_empty());
+    }
+
+    fn packet() -> LiveDataPacket {
+        LiveDataPacket {
+            GameCars: Default::default(),
+            NumCars: 0,
+            GameBall: Default::default(),
+            GameBoosts: Default::default(),
+            GameInfo: Default::default(),
+            Teams: Default::default(),
+            NumTeams: 0,
+        }
+    }
+
+    #[test]
+    fn empty_prediction_falls_back_to_extrapolation() {
+        let mut packet = packet();
+        packet.GameBall.Physics.Location.X = 100.0;
+        packet.GameBall.Physics.Location.Z = 500.0;
+        packet.GameBall.Physics.Velocity.X = 1000.0;
+        packet.GameBall.Physics.AngularVelocity.Y = 3.0;
+
+        let traj = frames_or_fallback(Vec::new(), 60, &packet);
+        assert_eq!(traj.start().loc, Point3::new(100.0, 0.0, 500.0));
+        assert!(traj.last().t >= 0.5);
+        let later = traj.at_time(0.5).unwrap();
+        assert!((later.loc.x - 600.0).abs() < 1.0);
+        assert_eq!(later.vel, packet.GameBall.Physics.vel());
+        assert_eq!(later.ang_vel(), packet.GameBall.Physics.ang_vel());
+    }
+
+    /// Lob the ball straight up, stepping with standard gravity the way chip
+    /// does, then correcting it to `gravity`. Returns the height after one
+    /// second.
+    fn lob_height(gravity: f32) -> f32 {
+        let mut loc = Point3::new(0.0, 0.0, 200.0);
+        let mut vel = Vector3::new(0.0, 0.0, 1000.0);
+        for _ in 0..120 {
+            vel.z += rl::GRAVITY * rl::PHYSICS_DT;
+            loc += vel * rl::PHYSICS_DT;
+            let (l, v) = correct_gravity(loc, vel, gravity, rl::PHYSICS_DT);
+            loc = l;
+            vel = v;
+        }
+        loc.z
+    }
+
+    #[test]
+    fn low_gravity_floats_higher() {
+        let standard = lob_height(rl::GRAVITY);
+        let low = lob_height(rl::GRAVITY / 2.0);
+        // z = 200 + 1000t - 325t², more or less.
+        assert!((standard - 875.0).abs() < 10.0);
+        assert!((low - standard - 162.5).abs() < 10.0);
+    }
+
+    #[test]
+    fn enough_frames_are_used_as_is() {
+        let packet = packet();
+        let frames = trajectory().iter().cloned().collect();
+        let traj = frames_or_fallback(frames, 10, &packet);
+        assert_eq!(traj.last().loc.x, 900.0);
+    }
+    #[test]
+    fn recorded_prediction_follows_game_time() {
+        let times = [10.0, 10.5, 11.0];
+        let states: Vec<_> = times
+            .iter()
+            .map(|&t| RecordingRigidBodyState {
+                loc: Point3::new((t - 10.0) * 1000.0, 0.0, 92.75),
+                rot: UnitQuaternion::identity(),
+                vel: Vector3::new(1000.0, 0.0, 0.0),
+                ang_vel: Vector3::zeros(),
+            })
+            .collect();
+        let predictor = RecordedBallPrediction::new(&times, &states);
+
+        // The ball in the packet is ignored.
+        let mut packet = packet();
+        packet.GameBall.Physics.Location.X = -3000.0;
+        packet.GameInfo.TimeSeconds = 100.0;
+        let traj = predictor.predict(&packet);
+        assert_eq!(traj.start().loc.x, 0.0);
+        assert!((traj.at_time_interpolated(0.25).unwrap().loc.x - 250.0).abs() < 1.0);
+        assert!((traj.last().t - 1.0).abs() < 0.01);
+
+        packet.GameInfo.TimeSeconds = 100.75;
+        let traj = predictor.predict(&packet);
+        assert!((traj.start().loc.x - 750.0).abs() < 1.0);
+        assert!((traj.last().t - 0.25).abs() < 0.01);
+    }
+}
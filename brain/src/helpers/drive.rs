@@ -1,5 +1,5 @@
 use crate::{behavior::movement::simple_yaw_diff, utils::geometry::ExtendF32};
-use common::prelude::*;
+use common::{prelude::*, rl};
 use nalgebra::Point2;
 use simulate::Car1D;
 
@@ -19,6 +19,25 @@ pub fn rough_time_drive_to_loc(
     base_time + sim_car.time()
 }
 
+/// Returns the number of seconds until the car reaches max speed if it boosts
+/// in a straight line, or `None` if it will run out of boost first.
+pub fn time_to_supersonic(car: &common::halfway_house::PlayerInfo) -> Option<f32> {
+    const DT: f32 = rl::PHYSICS_DT;
+
+    let mut sim_car = Car1D::new()
+        .with_speed(car.Physics.vel().norm().min(rl::CAR_MAX_SPEED))
+        .with_boost(car.Boost as f32);
+    while sim_car.speed() < rl::CAR_ALMOST_MAX_SPEED {
+        let boost = sim_car.boost();
+        sim_car.advance(DT, 1.0, true);
+        if sim_car.boost() >= boost {
+            // We're out of boost, and throttle alone won't get us there.
+            return None;
+        }
+    }
+    Some(sim_car.time())
+}
+
 // Very very rough
 fn steer_penalty(car: &common::halfway_house::PlayerInfo, desired_aim: f32) -> f32 {
     let turn = (car.Physics.rot().yaw() - desired_aim)
@@ -27,3 +46,33 @@ fn steer_penalty(car: &common::halfway_house::PlayerInfo, desired_aim: f32) -> f
     // Literally just guessing here
     turn * 0.5
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::drive::time_to_supersonic;
+    use common::{halfway_house::PlayerInfo, rl};
+
+    fn car(speed: f32, boost: i32) -> PlayerInfo {
+        let mut car = PlayerInfo::default();
+        car.Physics.Velocity.X = speed;
+        car.Boost = boost;
+        car
+    }
+
+    #[test]
+    fn not_enough_boost() {
+        assert!(time_to_supersonic(&car(0.0, 10)).is_none());
+    }
+
+    #[test]
+    fn full_boost() {
+        let time = time_to_supersonic(&car(0.0, 100)).unwrap();
+        assert!(time > 1.0 && time < 3.0, "time = {}", time);
+    }
+
+    #[test]
+    fn already_supersonic() {
+        let time = time_to_supersonic(&car(rl::CAR_MAX_SPEED, 0)).unwrap();
+        assert_eq!(time, 0.0);
+    }
+}
@@ -1,8 +1,8 @@
-use crate::{helpers::ball::BallFrame, routing::models::CarState};
-use common::prelude::*;
+use crate::{helpers::ball::BallFrame, routing::models::CarState, strategy::Context};
+use common::{prelude::*, rl};
 use nalgebra::{Point3, UnitQuaternion, Vector3};
 use simulate::Car1D;
-use std::borrow::Borrow;
+use std::{borrow::Borrow, f32::consts::PI};
 
 pub fn naive_ground_intercept<'a>(
     ball: impl Iterator<Item = &'a BallFrame>,
@@ -41,20 +41,25 @@ where
         .with_speed(start.vel.norm())
         .with_boost(start.boost);
 
-    let (sim_ball, data) = ball.into_iter().find_map(|ball| {
-        let ball = ball.borrow();
+    // If the prediction says the ball leaves the field, stop looking. Anything
+    // we find past that point would be chasing a ghost.
+    let (sim_ball, data) = ball
+        .into_iter()
+        .take_while(|ball| ball.borrow().is_in_bounds())
+        .find_map(|ball| {
+            let ball = ball.borrow();
 
-        sim_car.advance(ball.dt(), 1.0, true);
+            sim_car.advance(ball.dt(), 1.0, true);
 
-        let target_dist = (ball.loc - start.loc).to_2d().norm() - RADII;
-        if sim_car.distance() >= target_dist {
-            if let Some(data) = predicate(&ball).into_intercept_data() {
-                return Some((ball.clone(), data));
+            let target_dist = (ball.loc - start.loc).to_2d().norm() - RADII;
+            if sim_car.distance() >= target_dist {
+                if let Some(data) = predicate(&ball).into_intercept_data() {
+                    return Some((ball.clone(), data));
+                }
             }
-        }
 
-        None
-    })?;
+            None
+        })?;
 
     let intercept_loc = sim_ball.loc - (sim_ball.loc - start.loc).normalize() * RADII;
     let intercept = NaiveIntercept {
@@ -111,5 +116,169 @@ pub fn naive_intercept_penalty(car: &CarState, ball: &BallFrame) -> f32 {
     let car_to_ball = ball_loc - car.loc_2d();
 
     let fudge = 0.75;
-    car_forward.angle_to(&car_to_ball.to_axis()).abs() * fudge
+    let angle_penalty = car_forward.angle_to(&car_to_ball.to_axis()).abs() * fudge;
+
+    // A ball crossing our line of approach slides across the nose on the way
+    // in, and takes a correction to hit squarely.
+    let centering_fudge = 0.25;
+    let speed = car.vel.norm().max(rl::CAR_NORMAL_SPEED);
+    let strike = strike_pose(car, ball, speed);
+    let centering_penalty = (1.0 - contact_centering(&strike, ball.loc)) * centering_fudge;
+
+    angle_penalty + centering_penalty
+}
+
+/// Where the car will be, and which way it'll face, a moment before it
+/// strikes `ball`. This assumes it has turned to face the ball and is driving
+/// straight at it at `speed`. The ball comes in along its velocity relative to
+/// the car, not along the car's heading.
+fn strike_pose(car: &CarState, ball: &BallFrame, speed: f32) -> CarState {
+    // About where the car's center is when the two first touch.
+    const CONTACT_DIST: f32 = 150.0;
+
+    let approach = (ball.loc.to_2d() - car.loc_2d()).to_axis();
+    let car_vel = approach.into_inner() * speed;
+    let rel_vel = car_vel - ball.vel.to_2d();
+    let incoming = if rel_vel.norm() >= 1.0 {
+        rel_vel.to_axis()
+    } else {
+        approach
+    };
+    CarState {
+        loc: (ball.loc.to_2d() - incoming.into_inner() * CONTACT_DIST).to_3d(car.loc.z),
+        rot: UnitQuaternion::from_axis_angle(&Vector3::z_axis(), approach.y.atan2(approach.x)),
+        vel: car_vel.to_3d(0.0),
+        boost: car.boost,
+    }
+}
+
+/// How squarely the ball meets the car's nose, given where the car is at the
+/// moment of the strike, from 1.0 (dead center) down to 0.0 (a glancing blow,
+/// a miss, or the ball is behind us).
+pub fn contact_centering(car: &CarState, ball_loc: Point3<f32>) -> f32 {
+    // Half the width of an Octane's hitbox.
+    const CAR_HALF_WIDTH: f32 = 42.1;
+    const REACH: f32 = rl::BALL_RADIUS + CAR_HALF_WIDTH;
+
+    let car_to_ball = ball_loc.to_2d() - car.loc_2d();
+    if car_to_ball.dot(&car.forward_axis_2d()) < 0.0 {
+        return 0.0;
+    }
+    let lateral = car_to_ball.dot(&car.right_axis_2d()).abs();
+    (1.0 - lateral / REACH).max(0.0)
+}
+
+/// Whether we'd have to turn around to reach the intercept. Striking from
+/// here tends to end in a big awkward loop, so it's usually better to
+/// reposition first.
+pub fn intercept_is_behind<D>(ctx: &Context<'_>, intercept: &NaiveIntercept<D>) -> bool {
+    is_behind(&ctx.me().into(), intercept.ball_loc)
+}
+
+fn is_behind(car: &CarState, ball_loc: Point3<f32>) -> bool {
+    // Anything within this angle of our nose can be reached with a turn.
+    const MAX_FORWARD_ANGLE: f32 = PI * 2.0 / 3.0;
+
+    let car_to_ball = ball_loc.to_2d() - car.loc_2d();
+    car.forward_axis_2d().angle_to(&car_to_ball).abs() >= MAX_FORWARD_ANGLE
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::{
+            ball::BallFrame,
+            intercept::{contact_centering, is_behind, naive_ground_intercept_2, strike_pose},
+        },
+        routing::models::CarState,
+    };
+    use nalgebra::{Point3, UnitQuaternion, Vector3};
+
+    fn car(x: f32, speed: f32, boost: f32) -> CarState {
+        CarState {
+            loc: Point3::new(x, 0.0, 17.01),
+            rot: UnitQuaternion::identity(),
+            vel: Vector3::new(speed, 0.0, 0.0),
+            boost,
+        }
+    }
+
+    /// The ball rolls away from the car at a steady pace, then (according to
+    /// the bogus prediction) clips through the side wall and keeps going.
+    fn clipping_ball() -> Vec<BallFrame> {
+        (0..600)
+            .map(|i| {
+                let t = i as f32 / 60.0;
+                BallFrame::new(
+                    t,
+                    1.0 / 60.0,
+                    Point3::new(3000.0 + t * 1000.0, 0.0, 92.74),
+                    Vector3::new(1000.0, 0.0, 0.0),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stops_when_ball_leaves_field() {
+        // Without the guard, the car would eventually catch the ball somewhere
+        // beyond the side wall.
+        let intercept = naive_ground_intercept_2(&car(0.0, 0.0, 100.0), clipping_ball(), |_| true);
+        assert!(intercept.is_none());
+    }
+
+    #[test]
+    fn keeps_intercepts_before_ball_leaves_field() {
+        let intercept =
+            naive_ground_intercept_2(&car(2000.0, 2000.0, 100.0), clipping_ball(), |ball| {
+                ball.t >= 0.5
+            });
+        let intercept = intercept.unwrap();
+        assert!(intercept.ball_loc.x < 4096.0);
+    }
+
+    #[test]
+    fn head_on_contact_is_centered() {
+        let car = car(0.0, 1000.0, 0.0);
+        let head_on = contact_centering(&car, Point3::new(1000.0, 0.0, 92.74));
+        let glancing = contact_centering(&car, Point3::new(1000.0, 100.0, 92.74));
+        let miss = contact_centering(&car, Point3::new(1000.0, 300.0, 92.74));
+        let behind = contact_centering(&car, Point3::new(-1000.0, 0.0, 92.74));
+        assert!((head_on - 1.0).abs() < 1e-3, "{}", head_on);
+        assert!(glancing > 0.0 && glancing < 0.5, "{}", glancing);
+        assert_eq!(miss, 0.0);
+        assert_eq!(behind, 0.0);
+    }
+
+    #[test]
+    fn crossing_ball_meets_nose_off_center() {
+        // We're facing away from the ball, but we'll have turned around by the
+        // time we get there, so that doesn't matter.
+        let car = car(0.0, -1000.0, 0.0);
+        let still = BallFrame::new(
+            1.0,
+            1.0 / 60.0,
+            Point3::new(1000.0, 0.0, 92.74),
+            Vector3::zeros(),
+        );
+        let crossing = BallFrame::new(
+            1.0,
+            1.0 / 60.0,
+            Point3::new(1000.0, 0.0, 92.74),
+            Vector3::new(0.0, 1000.0, 0.0),
+        );
+        let still = contact_centering(&strike_pose(&car, &still, 1400.0), still.loc);
+        let crossing = contact_centering(&strike_pose(&car, &crossing, 1400.0), crossing.loc);
+        assert!((still - 1.0).abs() < 1e-3, "{}", still);
+        assert!(crossing < 0.5, "{}", crossing);
+    }
+
+    #[test]
+    fn ball_behind_the_car() {
+        let car = car(0.0, 1000.0, 0.0);
+        assert!(!is_behind(&car, Point3::new(1000.0, 0.0, 92.74)));
+        assert!(!is_behind(&car, Point3::new(0.0, 1000.0, 92.74)));
+        assert!(is_behind(&car, Point3::new(-1000.0, 0.0, 92.74)));
+        assert!(is_behind(&car, Point3::new(-1000.0, -1000.0, 92.74)));
+    }
 }
@@ -1,14 +1,16 @@
 use crate::{
     behavior::higher_order::NullBehavior,
+    helpers::ball::RecordedBallPrediction,
     integration_tests::{
         playback::{BallRecording, CarRecording},
-        running_test::RunningTest,
+        running_test::{RunningTest, Teammate},
         scenario::TestScenario,
     },
     strategy::{Behavior, Runner, Soccar},
 };
 use brain_test_data::OneVOneScenario;
 use collect::{RecordingPlayerInput, RecordingPlayerTick, RecordingRigidBodyState, RecordingTick};
+use nalgebra::{Point3, Rotation3, UnitQuaternion, Vector3};
 use std::{
     fs::File,
     panic,
@@ -23,6 +25,8 @@ pub struct TestRunner {
     car_inital_state: Option<(RecordingRigidBodyState, f32)>,
     enemy_recording: Option<(Vec<f32>, Vec<RecordingPlayerTick>)>,
     enemy_initial_boost: f32,
+    teammate: Option<Teammate>,
+    ball_predictor: Option<RecordedBallPrediction>,
 }
 
 impl TestRunner {
@@ -35,6 +39,8 @@ impl TestRunner {
             car_inital_state: None,
             enemy_recording: None,
             enemy_initial_boost: Self::DEFAULT_STARTING_BOOST,
+            teammate: None,
+            ball_predictor: None,
         }
     }
 
@@ -116,6 +122,37 @@ impl TestRunner {
         self
     }
 
+    /// Add a second car to our team, controlled by its own brain running the
+    /// given behavior. An idle car is added to the enemy team to keep the
+    /// teams even.
+    pub fn teammate(
+        mut self,
+        loc: Point3<f32>,
+        rot: Rotation3<f32>,
+        behavior: impl Behavior + Send + 'static,
+    ) -> Self {
+        self.teammate = Some(Teammate {
+            state: RecordingRigidBodyState {
+                loc,
+                rot: UnitQuaternion::from_rotation_matrix(&rot),
+                vel: Vector3::zeros(),
+                ang_vel: Vector3::zeros(),
+            },
+            boost: Self::DEFAULT_STARTING_BOOST,
+            behavior: Box::new(behavior),
+        });
+        self
+    }
+
+    /// Instead of simulating the ball's path, predict it by replaying a
+    /// recording, starting at `start_time`. The live ball is ignored, so this
+    /// keeps the prediction the same from run to run.
+    pub fn recorded_ball_prediction(mut self, path: impl AsRef<Path>, start_time: f32) -> Self {
+        let file = File::open(path).unwrap();
+        self.ball_predictor = Some(RecordedBallPrediction::from_csv(file, start_time));
+        self
+    }
+
     pub fn soccar(mut self) -> Self {
         self.behavior = Some(Box::new(|_| Box::new(Runner::new(Soccar::new()))));
         self
@@ -184,6 +221,8 @@ impl TestRunner {
             ball,
             car,
             enemy,
+            self.teammate,
+            self.ball_predictor,
             move |p| behavior(p),
             ready_wait_send,
             messages_rx,
@@ -201,4 +240,12 @@ impl TestRunner {
         test.sleep_millis(millis);
         test
     }
+
+    /// Like `run_for_millis`, but counts physics ticks (120 per second of game
+    /// time) instead of wall-clock time. See `RunningTest::step_ticks`.
+    pub fn run_for_ticks(self, ticks: u64) -> RunningTest {
+        let test = self.run();
+        test.step_ticks(ticks);
+        test
+    }
 }
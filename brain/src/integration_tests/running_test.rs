@@ -1,6 +1,7 @@
 use crate::{
     behavior::higher_order::{Fuse, NullBehavior},
     eeg::Event,
+    helpers::ball::RecordedBallPrediction,
     integration_tests::{
         playback::{BallPlayback, BallRecording, CarPlayback, CarRecording},
         utils::rotator,
@@ -9,8 +10,9 @@ use crate::{
     Brain, EEG,
 };
 use collect::{get_packet_and_inject_rigid_body_tick, RecordingRigidBodyState};
-use common::{halfway_house::translate_player_input, ExtendRLBot};
+use common::{halfway_house::translate_player_input, rl, ExtendRLBot};
 use lazy_static::lazy_static;
+use nalgebra::{Point3, Vector3};
 use std::{
     collections::HashSet,
     panic,
@@ -40,12 +42,34 @@ impl RunningTest {
         thread::sleep(Duration::from_millis(millis))
     }
 
+    /// Block until the game clock has advanced by `ticks` physics ticks (120
+    /// per second). Unlike `sleep_millis`, this measures game time rather than
+    /// wall-clock time. This is not deterministic. The game still runs in real
+    /// time, so if the host hiccups, the brain skips ticks and sees fewer of
+    /// them than it would otherwise.
+    pub fn step_ticks(&self, ticks: u64) {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        self.messages.send(Message::StepTicks(ticks, tx)).unwrap();
+        rx.recv().unwrap()
+    }
+
     pub fn set_behavior(&self, behavior: impl Behavior + Send + 'static) {
         self.messages
             .send(Message::SetBehavior(Box::new(behavior)))
             .unwrap();
     }
 
+    /// Offer `behavior` as an interruption to whatever is running, the same
+    /// way the strategy would. Returns whether its priority was high enough to
+    /// take over.
+    pub fn inject_behavior(&self, behavior: impl Behavior + 'static) -> bool {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        self.messages
+            .send(Message::InjectBehavior(Box::new(behavior), tx))
+            .unwrap();
+        rx.recv().unwrap()
+    }
+
     pub fn sniff_packet(&self) -> common::halfway_house::LiveDataPacket {
         let (tx, rx) = crossbeam_channel::bounded(1);
         self.messages.send(Message::SniffPacket(tx)).unwrap();
@@ -82,10 +106,24 @@ impl RunningTest {
         self.examine_eeg(move |eeg| f(eeg.events.as_ref().unwrap()));
     }
 
+    /// Examine the number of controller inputs which were out of range and got
+    /// clamped.
+    pub fn examine_clamped_inputs(&self, f: impl Fn(usize) + Send + 'static) {
+        self.examine_eeg(move |eeg| f(eeg.clamped_inputs));
+    }
+
+    /// Examine the number of routes that were planned from scratch.
+    pub fn examine_route_plans(&self, f: impl Fn(usize) + Send + 'static) {
+        self.examine_eeg(move |eeg| f(eeg.route_plans));
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn spawn_thread(
         ball_scenario: BallRecording,
         car_scenario: CarRecording,
         enemy_scenario: CarRecording,
+        teammate: Option<Teammate>,
+        ball_predictor: Option<RecordedBallPrediction>,
         behavior: impl FnOnce(&common::halfway_house::LiveDataPacket) -> Box<dyn Behavior>
             + Send
             + 'static,
@@ -97,6 +135,8 @@ impl RunningTest {
                 ball_scenario,
                 car_scenario,
                 enemy_scenario,
+                teammate,
+                ball_predictor,
                 behavior,
                 ready_wait,
                 messages,
@@ -105,9 +145,22 @@ impl RunningTest {
     }
 }
 
+pub struct Teammate {
+    pub state: RecordingRigidBodyState,
+    pub boost: f32,
+    pub behavior: Box<dyn Behavior + Send>,
+}
+
+/// In 2v2 tests, the teammate and the idle enemy are added after the two
+/// players of the usual 1v1.
+const TEAMMATE_INDEX: usize = 2;
+const IDLE_ENEMY_INDEX: usize = 3;
+
 pub enum Message {
     SniffPacket(crossbeam_channel::Sender<common::halfway_house::LiveDataPacket>),
     SetBehavior(Box<dyn Behavior + Send>),
+    InjectBehavior(Box<dyn Behavior + Send>, crossbeam_channel::Sender<bool>),
+    StepTicks(u64, crossbeam_channel::Sender<()>),
     HasScored(crossbeam_channel::Sender<bool>),
     EnemyHasScored(crossbeam_channel::Sender<bool>),
     ExamineEEG(Box<dyn Fn(&EEG) + Send>),
@@ -128,10 +181,13 @@ fn unlock_rlbot_singleton() -> MutexGuard<'static, Option<rlbot::RLBot>> {
     rlbot_guard
 }
 
+#[allow(clippy::too_many_arguments)]
 fn test_thread(
     ball_scenario: BallRecording,
     car_scenario: CarRecording,
     enemy_scenario: CarRecording,
+    teammate: Option<Teammate>,
+    ball_predictor: Option<RecordedBallPrediction>,
     behavior: impl FnOnce(&common::halfway_house::LiveDataPacket) -> Box<dyn Behavior>,
     ready_wait: Arc<Barrier>,
     messages: crossbeam_channel::Receiver<Message>,
@@ -139,13 +195,27 @@ fn test_thread(
     let rlbot_guard = unlock_rlbot_singleton();
     let rlbot = rlbot_guard.as_ref().unwrap();
 
-    let match_settings = rlbot::MatchSettings::rlbot_vs_rlbot("Subject", "Mushroom")
+    let mut match_settings = rlbot::MatchSettings::rlbot_vs_rlbot("Subject", "Mushroom")
         .skip_replays(true)
         .mutator_settings(
             rlbot::MutatorSettings::new()
                 .match_length(rlbot::MatchLength::Unlimited)
                 .respawn_time_option(rlbot::RespawnTimeOption::Disable_Goal_Reset),
         );
+    if teammate.is_some() {
+        let players = &mut match_settings.player_configurations;
+        players.push(rlbot::PlayerConfiguration::new(
+            rlbot::PlayerClass::RLBotPlayer,
+            "Sidekick",
+            Team::Blue.to_ffi().into(),
+        ));
+        players.push(rlbot::PlayerConfiguration::new(
+            rlbot::PlayerClass::RLBotPlayer,
+            "Toadstool",
+            Team::Orange.to_ffi().into(),
+        ));
+        assert_eq!(players.len(), IDLE_ENEMY_INDEX + 1);
+    }
     rlbot.start_match(&match_settings).unwrap();
 
     let mut eeg = EEG::new();
@@ -155,6 +225,9 @@ fn test_thread(
 
     let mut brain = Brain::with_behavior(NullBehavior::new());
     brain.set_player_index(0);
+    if let Some(ball_predictor) = ball_predictor {
+        brain.set_ball_predictor(ball_predictor);
+    }
 
     let mut packets = rlbot.packeteer();
     let mut physicist = rlbot.physicist();
@@ -178,17 +251,33 @@ fn test_thread(
         car_scenario.starting_boost,
         enemy_scenario.initial_state(),
         enemy_scenario.starting_boost,
+        teammate.as_ref().map(|t| (&t.state, t.boost)),
     );
 
     let rigid_body_tick = physicist.next_flat().unwrap();
     let first_packet = get_packet_and_inject_rigid_body_tick(rlbot, rigid_body_tick).unwrap();
 
     brain.set_behavior(Fuse::new(behavior(&first_packet)), &mut eeg);
+
+    // The teammate gets its own brain, with a quiet EEG so its logs don't get
+    // mixed up with ours.
+    let mut teammate = teammate.map(|teammate| {
+        let mut teammate_eeg = EEG::new();
+        let mut teammate_brain = Brain::with_behavior(NullBehavior::new());
+        teammate_brain.set_player_index(TEAMMATE_INDEX as i32);
+        teammate_brain.set_behavior(Fuse::new(teammate.behavior), &mut teammate_eeg);
+        (teammate_brain, teammate_eeg)
+    });
+
     ready_wait.wait();
 
     let mut ball = BallPlayback::new(ball_scenario, first_packet.GameInfo.TimeSeconds);
     let mut enemy = CarPlayback::new(enemy_scenario, 1, first_packet.GameInfo.TimeSeconds);
 
+    // Callers blocked in `step_ticks`, along with the game time they're waiting
+    // for.
+    let mut tick_waiters: Vec<(f32, crossbeam_channel::Sender<()>)> = Vec::new();
+
     'tick_loop: loop {
         let rigid_body_tick = physicist.next_flat().unwrap();
         let packet = get_packet_and_inject_rigid_body_tick(rlbot, rigid_body_tick).unwrap();
@@ -204,6 +293,13 @@ fn test_thread(
                 Message::SetBehavior(behavior) => {
                     brain.set_behavior(Fuse::new(behavior), &mut eeg);
                 }
+                Message::InjectBehavior(behavior, tx) => {
+                    tx.send(brain.inject_behavior(behavior, &mut eeg)).unwrap();
+                }
+                Message::StepTicks(ticks, tx) => {
+                    let duration = ticks as f32 * rl::PHYSICS_DT;
+                    tick_waiters.push((packet.GameInfo.TimeSeconds + duration, tx));
+                }
                 Message::HasScored(tx) => {
                     let first_score = first_packet.Teams[Team::Blue.to_ffi() as usize].Score;
                     let current_score = packet.Teams[Team::Blue.to_ffi() as usize].Score;
@@ -234,6 +330,25 @@ fn test_thread(
                 log::warn!("could not quick chat {:?}", chat);
             }
         }
+
+        if let Some((ref mut teammate_brain, ref mut teammate_eeg)) = teammate {
+            teammate_eeg.begin(&packet);
+            let input = teammate_brain.tick(field_info, &packet, teammate_eeg);
+            rlbot
+                .update_player_input(TEAMMATE_INDEX as i32, &translate_player_input(&input))
+                .unwrap();
+            teammate_eeg.show(&packet);
+        }
+
+        let now = packet.GameInfo.TimeSeconds;
+        tick_waiters.retain(|(target, tx)| {
+            // Allow for rounding error in the game clock.
+            if now < *target - rl::PHYSICS_DT / 2.0 {
+                return true;
+            }
+            tx.send(()).unwrap();
+            false
+        });
     }
 
     // For tidiness, make the cars stop moving when the test is finished.
@@ -251,14 +366,21 @@ fn setup_scenario(
     car_boost: f32,
     enemy: &RecordingRigidBodyState,
     enemy_boost: f32,
+    teammate: Option<(&RecordingRigidBodyState, f32)>,
 ) {
     let field_info = rlbot.interface().update_field_info_flatbuffer().unwrap();
     let num_boosts = field_info.boostPads().unwrap().len() as i32;
 
     set_state(rlbot, ball, car, car_boost, enemy, enemy_boost, num_boosts);
+    if let Some((teammate, teammate_boost)) = teammate {
+        set_teammate_state(rlbot, teammate, teammate_boost);
+    }
     // Wait for car suspension to settle to neutral, then set it again.
     thread::sleep(Duration::from_millis(1000));
     set_state(rlbot, ball, car, car_boost, enemy, enemy_boost, num_boosts);
+    if let Some((teammate, teammate_boost)) = teammate {
+        set_teammate_state(rlbot, teammate, teammate_boost);
+    }
 
     // Wait a few frames for the state to take effect.
     let mut packeteer = rlbot.packeteer();
@@ -316,3 +438,28 @@ fn set_state(
 
     rlbot.set_game_state(&game_state).unwrap();
 }
+
+fn set_teammate_state(rlbot: &rlbot::RLBot, teammate: &RecordingRigidBodyState, boost: f32) {
+    let teammate_state = rlbot::DesiredCarState::new()
+        .physics(
+            rlbot::DesiredPhysics::new()
+                .location(teammate.loc)
+                .rotation(rotator(teammate.rot))
+                .velocity(teammate.vel)
+                .angular_velocity(teammate.ang_vel),
+        )
+        .jumped(false)
+        .double_jumped(false)
+        .boost_amount(boost);
+    // Park the idle enemy out of the way.
+    let idle_enemy_state = rlbot::DesiredCarState::new().physics(
+        rlbot::DesiredPhysics::new()
+            .location(Point3::new(-6000.0, 6000.0, 0.0))
+            .velocity(Vector3::zeros())
+            .angular_velocity(Vector3::zeros()),
+    );
+    let game_state = rlbot::DesiredGameState::new()
+        .car_state(TEAMMATE_INDEX, teammate_state)
+        .car_state(IDLE_ENEMY_INDEX, idle_enemy_state);
+    rlbot.set_game_state(&game_state).unwrap();
+}
@@ -1,26 +1,41 @@
 use crate::{
-    eeg::{color, Drawable},
+    eeg::{color, Drawable, Event},
     routing::models::{
-        PlanningContext, ProvisionalPlanExpansion, ProvisionalPlanExpansionTail, RoutePlan,
-        RoutePlanError, RoutePlanner, SegmentRunAction, SegmentRunner,
+        ApproachRecord, PlanningContext, ProvisionalPlanExpansion, ProvisionalPlanExpansionTail,
+        RoutePlan, RoutePlanError, RoutePlanner, SegmentPlan, SegmentRunAction, SegmentRunner,
     },
     rules::SameBallTrajectory,
     strategy::{Action, Behavior, Context},
 };
+use nalgebra::Point3;
 use nameof::name_of_type;
 
 pub struct FollowRoute {
-    /// Option dance: This only holds a planner before the first tick.
+    /// Option dance: This is only `None` while the planner is busy planning.
     planner: Option<Box<dyn RoutePlanner>>,
     current: Option<Current>,
     never_recover: bool,
     same_ball_trajectory: Option<SameBallTrajectory>,
+    replan_interval: Option<f32>,
+    last_plan_time: Option<f32>,
+    /// Set when something has changed enough to warrant a new plan, but we
+    /// haven't been able to act on it yet.
+    replan_pending: bool,
+    approach: Option<ApproachRecord>,
+    /// The planner to switch to once the enemy commits. Option dance, same as
+    /// `planner`.
+    contest_planner: Option<Box<dyn RoutePlanner>>,
+    /// Whether the enemy had committed as of the last tick, and so whether
+    /// `contest_planner` is the active planner.
+    contesting: bool,
 }
 
 struct Current {
     plan: RoutePlan,
     runner: Box<dyn SegmentRunner>,
     provisional_expansion_tail: ProvisionalPlanExpansionTail,
+    /// The game time when the current segment started running.
+    start_time: f32,
 }
 
 impl FollowRoute {
@@ -34,6 +49,12 @@ impl FollowRoute {
             current: None,
             never_recover: false,
             same_ball_trajectory: None,
+            replan_interval: None,
+            last_plan_time: None,
+            replan_pending: false,
+            approach: None,
+            contest_planner: None,
+            contesting: false,
         }
     }
 
@@ -52,6 +73,29 @@ impl FollowRoute {
         };
         self
     }
+
+    /// When `same_ball_trajectory` notices a perturbance, plan again from
+    /// scratch instead of aborting, but no more often than once every
+    /// `interval` seconds. A short interval tracks a jittery intercept closely
+    /// but never commits to anything; something around 0.2 seconds is a
+    /// reasonable compromise. The new plan waits until the current segment
+    /// ends, unless the segment says it can be interrupted.
+    pub fn replan_interval(mut self, interval: f32) -> Self {
+        assert!(interval > 0.0);
+        self.replan_interval = Some(interval);
+        self
+    }
+
+    /// Once the enemy's intercept time drops below ours, switch to `planner`
+    /// and plan again from scratch (and switch back if they back off). A route
+    /// planned while we had the ball to ourselves can afford to be slow and
+    /// careful, but once the enemy goes for the ball it turns into a race, so
+    /// `planner` should be something fast. Like any other replan, the switch
+    /// waits until the current segment can be interrupted.
+    pub fn replan_on_enemy_commit(mut self, planner: impl RoutePlanner + 'static) -> Self {
+        self.contest_planner = Some(Box::new(planner));
+        self
+    }
 }
 
 impl Behavior for FollowRoute {
@@ -60,13 +104,45 @@ impl Behavior for FollowRoute {
     }
 
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
-        if let Some(ref mut same_ball_trajectory) = self.same_ball_trajectory {
-            return_some!(same_ball_trajectory.execute_old(ctx));
+        let perturbed = match self.same_ball_trajectory {
+            Some(ref mut same_ball_trajectory) => same_ball_trajectory.execute_old(ctx),
+            None => None,
+        };
+        if let Some(action) = perturbed {
+            if self.replan_interval.is_none() || self.current.is_none() {
+                return action;
+            }
+            ctx.eeg
+                .log(self.name(), "ball trajectory changed; re-plan pending");
+            self.same_ball_trajectory = Some(SameBallTrajectory::new());
+            self.replan_pending = true;
+        }
+
+        if self.contest_planner.is_some() {
+            let committed = self.enemy_committed(ctx);
+            if committed != self.contesting {
+                if committed {
+                    ctx.eeg.track(Event::FollowRouteEnemyCommit);
+                    ctx.eeg.log(self.name(), "enemy committed; re-plan pending");
+                } else {
+                    ctx.eeg
+                        .log(self.name(), "enemy backed off; re-plan pending");
+                }
+                self.contesting = committed;
+                self.replan_pending = true;
+            }
+        }
+
+        if self.replan_pending {
+            let segment = self.current.as_ref().map(|c| &*c.plan.segment);
+            if self.replan_allowed(ctx, segment) {
+                ctx.eeg.log(self.name(), "re-planning");
+                self.current = None;
+            }
         }
 
         if self.current.is_none() {
-            let planner = &*self.planner.take().unwrap();
-            if let Err(action) = self.advance(planner, ctx) {
+            if let Err(action) = self.plan_from_scratch(ctx) {
                 return action;
             }
         }
@@ -77,6 +153,48 @@ impl Behavior for FollowRoute {
 }
 
 impl FollowRoute {
+    /// Whether a pending replan can happen now. `segment` is the segment
+    /// that's running, or `None` if we're between segments.
+    fn replan_allowed(&self, ctx: &mut Context<'_>, segment: Option<&dyn SegmentPlan>) -> bool {
+        let since_last_plan = self
+            .last_plan_time
+            .map(|t| ctx.packet.GameInfo.TimeSeconds - t);
+        replan_allowed(since_last_plan, self.replan_interval, segment)
+    }
+
+    fn plan_from_scratch(&mut self, ctx: &mut Context<'_>) -> Result<(), Action> {
+        let contesting = self.contesting;
+        let planner = if contesting {
+            self.contest_planner.take()
+        } else {
+            self.planner.take()
+        };
+        let planner = planner.unwrap();
+        let result = self.advance(&*planner, ctx);
+        if contesting {
+            self.contest_planner = Some(planner);
+        } else {
+            self.planner = Some(planner);
+        }
+        result?;
+        self.replan_pending = false;
+        self.last_plan_time = Some(ctx.packet.GameInfo.TimeSeconds);
+        ctx.eeg.route_plans += 1;
+        Ok(())
+    }
+
+    /// Returns `true` if the enemy would beat us to the ball.
+    fn enemy_committed(&self, ctx: &mut Context<'_>) -> bool {
+        let enemy_time = match ctx.scenario.enemy_intercept() {
+            Some((_enemy, intercept)) => intercept.time,
+            None => return false,
+        };
+        match ctx.scenario.me_intercept() {
+            Some(intercept) => enemy_time < intercept.time,
+            None => true,
+        }
+    }
+
     fn draw(&mut self, ctx: &mut Context<'_>) {
         // This provisional expansion serves two purposes:
         // 1. Make sure each segment thinks it can complete successfully.
@@ -87,6 +205,26 @@ impl FollowRoute {
         for segment in provisional_expansion.iter() {
             segment.draw(ctx);
         }
+        *ctx.planned_path = Some(sample_path(&provisional_expansion));
+
+        let elapsed = ctx.packet.GameInfo.TimeSeconds - current.start_time;
+        let time_remaining = (provisional_expansion.duration() - elapsed).max(0.0);
+        ctx.eeg.print_time("route time_remaining", time_remaining);
+
+        if let Some(ref approach) = self.approach {
+            let ball_loc = approach.ball_loc;
+            ctx.eeg.draw(Drawable::Line(
+                ball_loc - approach.approach_dir.into_inner() * 500.0,
+                ball_loc,
+                color::ORANGE,
+            ));
+            ctx.eeg.draw(Drawable::Line(
+                ball_loc,
+                ball_loc + approach.hit_dir.into_inner() * 500.0,
+                color::GREEN,
+            ));
+            ctx.eeg.print_angle("approach angle", approach.angle());
+        }
     }
 
     fn advance(&mut self, planner: &dyn RoutePlanner, ctx: &mut Context<'_>) -> Result<(), Action> {
@@ -98,6 +236,9 @@ impl FollowRoute {
             Ok((plan, log)) => (plan, log),
             Err(err) => return Err(self.handle_error(ctx, planner.name(), err.error, err.log)),
         };
+        if log.approach.is_some() {
+            self.approach = log.approach;
+        }
         ctx.eeg.log(
             self.name(),
             format!("next segment is {}", plan.segment.name()),
@@ -107,7 +248,7 @@ impl FollowRoute {
                 ctx,
                 error.planner_name,
                 error.error,
-                log.into_iter().chain(error.log),
+                log.lines.into_iter().chain(error.log),
             )
         })?;
 
@@ -116,6 +257,7 @@ impl FollowRoute {
             plan,
             runner,
             provisional_expansion_tail: tail,
+            start_time: ctx.packet.GameInfo.TimeSeconds,
         });
         Ok(())
     }
@@ -172,6 +314,13 @@ impl FollowRoute {
         }
 
         let current = self.current.take().unwrap();
+        if self.replan_pending && self.replan_allowed(ctx, None) {
+            ctx.eeg.log(self.name(), "re-planning between segments");
+            if let Err(action) = self.plan_from_scratch(ctx) {
+                return action;
+            }
+            return self.go(ctx);
+        }
         let next = some_or_else!(current.plan.next, {
             return Action::Return;
         });
@@ -181,3 +330,149 @@ impl FollowRoute {
         self.go(ctx)
     }
 }
+
+fn replan_allowed(
+    since_last_plan: Option<f32>,
+    interval: Option<f32>,
+    segment: Option<&dyn SegmentPlan>,
+) -> bool {
+    match (since_last_plan, interval) {
+        (Some(t), Some(interval)) if t < interval => false,
+        _ => segment.map(SegmentPlan::interruptible).unwrap_or(true),
+    }
+}
+
+/// Sample points along the whole plan, at regular intervals in time.
+fn sample_path(expansion: &ProvisionalPlanExpansion<'_>) -> Vec<Point3<f32>> {
+    const SAMPLE_DT: f32 = 0.1;
+
+    let mut points = Vec::new();
+    for segment in expansion.iter() {
+        let duration = segment.duration();
+        let mut t = 0.0;
+        while t < duration {
+            points.push(segment.position_at(t));
+            t += SAMPLE_DT;
+        }
+    }
+    if let Some(last) = expansion.iter().last() {
+        points.push(last.end().loc);
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        behavior::replan_allowed,
+        models::{CarState, CarState2D, SegmentPlan},
+        segments::{ForwardDodge, NullSegment},
+    };
+    use common::rl;
+    use nalgebra::{Point2, UnitComplex, Vector2};
+    use simulate::CarForwardDodge;
+
+    fn start() -> CarState {
+        CarState2D {
+            loc: Point2::origin(),
+            rot: UnitComplex::identity(),
+            vel: Vector2::new(1000.0, 0.0),
+            boost: 0.0,
+        }
+        .to_3d()
+    }
+
+    #[test]
+    fn dodge_in_progress_survives_replan() {
+        let dodge = ForwardDodge::new(start(), CarForwardDodge::calc_1d(1000.0), rl::GRAVITY);
+        let dodge: &dyn SegmentPlan = &dodge;
+        assert!(!replan_allowed(Some(1.0), Some(0.2), Some(dodge)));
+    }
+
+    #[test]
+    fn replan_interruptible_segment() {
+        let segment = NullSegment::new(start());
+        assert!(replan_allowed(Some(1.0), Some(0.2), Some(&segment)));
+        assert!(!replan_allowed(Some(0.1), Some(0.2), Some(&segment)));
+    }
+
+    #[test]
+    fn replan_between_segments() {
+        assert!(replan_allowed(Some(1.0), Some(0.2), None));
+        assert!(replan_allowed(None, Some(0.2), None));
+        assert!(!replan_allowed(Some(0.1), Some(0.2), None));
+        assert!(replan_allowed(Some(0.1), None, None));
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        eeg::Event,
+        integration_tests::{TestRunner, TestScenario},
+        routing::{
+            behavior::FollowRoute,
+            plan::{GroundIntercept, GroundStraightPlanner},
+            segments::StraightMode,
+        },
+    };
+    use common::prelude::*;
+    use nalgebra::{Point2, Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn replan_interval_limits_replanning() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, 3000.0, 92.74),
+                ball_vel: Vector3::new(0.0, -300.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(FollowRoute::new(GroundIntercept::new()).replan_interval(0.2))
+            .run_for_millis(2000);
+
+        // The ball rolls along as predicted, so nothing should trigger a
+        // replan, and certainly not more than once every 0.2 seconds.
+        test.examine_route_plans(|count| {
+            assert!(count >= 1, "count = {}", count);
+            assert!(count <= 11, "count = {}", count);
+        });
+    }
+
+    #[test]
+    fn replan_when_enemy_commits() {
+        // We're easing up to a stationary ball, planning to arrive at a crawl,
+        // but the enemy is charging in from the other side.
+        let route = FollowRoute::new(
+            GroundStraightPlanner::new(Point2::new(0.0, 1500.0), StraightMode::Asap)
+                .target_end_speed(300.0),
+        )
+        .replan_on_enemy_commit(GroundStraightPlanner::new(
+            Point2::new(0.0, 1500.0),
+            StraightMode::Asap,
+        ));
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, 1700.0, 92.74),
+                car_loc: Point3::new(0.0, -1000.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                enemy_loc: Point3::new(0.0, 4000.0, 17.01),
+                enemy_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, 0.0),
+                enemy_vel: Vector3::new(0.0, -1500.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(route)
+            .run_for_millis(1500);
+
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::FollowRouteEnemyCommit));
+        });
+        test.examine_route_plans(|count| {
+            assert!(count >= 2, "count = {}", count);
+        });
+        let packet = test.sniff_packet();
+        let speed = packet.GameCars[0].Physics.vel_2d().norm();
+        println!("speed = {}", speed);
+        assert!(speed >= 1200.0);
+    }
+}
@@ -1,12 +1,12 @@
 use crate::{
-    helpers::ball::BallTrajectory,
+    helpers::ball::BallTrajectorySlice,
     strategy::{Context, Context2, Game, Scenario},
     utils::geometry::flattener::Flattener,
 };
-use common::{physics, prelude::*, rl, PrettyPrint};
+use common::{physics, prelude::*, rl, Angle, PrettyPrint};
 use derive_new::new;
 use nalgebra::{Point2, Point3, Unit, UnitComplex, UnitQuaternion, Vector2, Vector3};
-use std::{fmt, iter};
+use std::{fmt, iter, ptr};
 
 #[derive(Clone)]
 pub struct CarState {
@@ -49,6 +49,14 @@ impl CarState {
         physics::car_roof_axis(self.rot)
     }
 
+    /// Returns true if the car is upright on the ground, as opposed to on a
+    /// wall or a ramp. `CarState` doesn't track wheel contact, so this assumes
+    /// the car has it.
+    pub fn on_flat_ground(&self) -> bool {
+        let rot = self.rot.to_rotation_matrix();
+        rot.pitch().abs() < 15.0_f32.to_radians() && rot.roll().abs() < 15.0_f32.to_radians()
+    }
+
     pub fn flatten(&self, flattener: &Flattener) -> CarState2D {
         CarState2D {
             loc: *flattener * self.loc,
@@ -140,7 +148,11 @@ impl Clone for Box<dyn RoutePlanner> {
 pub struct PlanningContext<'a: 's, 's> {
     pub game: &'s Game<'a>,
     pub start: CarState,
-    pub ball_prediction: &'s BallTrajectory,
+    /// How many seconds from now `start` is, i.e. how far into the route
+    /// we're planning from.
+    pub start_time: f32,
+    /// The ball prediction, re-timed so `t = 0` is `start_time`.
+    pub ball_prediction: BallTrajectorySlice<'s>,
 }
 
 impl<'a: 's, 's> PlanningContext<'a, 's> {
@@ -148,14 +160,29 @@ impl<'a: 's, 's> PlanningContext<'a, 's> {
         PlanningContext {
             game: &ctx.game,
             start: ctx.me().into(),
-            ball_prediction: ctx.scenario.ball_prediction(),
+            start_time: 0.0,
+            ball_prediction: ctx.scenario.ball_prediction().slice(0.0),
         }
     }
 
+    /// Everyone else on our team, as of `start_time`. They're assumed to keep
+    /// going in a straight line at their current velocity.
+    pub fn teammates(&self) -> impl Iterator<Item = CarState> + '_ {
+        let me = self.game.me();
+        self.game
+            .cars(self.game.team)
+            .filter(move |car| !ptr::eq(*car, me))
+            .map(move |car| {
+                let mut state = CarState::from(car);
+                state.loc += state.vel * self.start_time;
+                state
+            })
+    }
+
     pub fn plan(
         planner: &dyn RoutePlanner,
         ctx: &mut Context<'_>,
-    ) -> Result<(RoutePlan, Vec<String>), ProvisionalExpandError<'a>> {
+    ) -> Result<(RoutePlan, PlanningLog), ProvisionalExpandError<'a>> {
         let (ctx, _eeg) = ctx.split();
         Self::plan2(planner, &ctx)
     }
@@ -163,7 +190,7 @@ impl<'a: 's, 's> PlanningContext<'a, 's> {
     pub fn plan2(
         planner: &dyn RoutePlanner,
         ctx: &Context2<'_, '_>,
-    ) -> Result<(RoutePlan, Vec<String>), ProvisionalExpandError<'a>> {
+    ) -> Result<(RoutePlan, PlanningLog), ProvisionalExpandError<'a>> {
         let context = PlanningContext::from_context(ctx);
         Self::plan_2(planner, &context)
     }
@@ -171,11 +198,16 @@ impl<'a: 's, 's> PlanningContext<'a, 's> {
     pub fn plan_2(
         planner: &dyn RoutePlanner,
         context: &PlanningContext<'_, '_>,
-    ) -> Result<(RoutePlan, Vec<String>), ProvisionalExpandError<'a>> {
+    ) -> Result<(RoutePlan, PlanningLog), ProvisionalExpandError<'a>> {
         let mut log = Vec::new();
-        let mut dump = PlanningDump { log: &mut log };
-        match planner.plan(context, &mut dump) {
-            Ok(plan) => Ok((plan, log)),
+        let mut dump = PlanningDump::new(&mut log);
+        let result = planner.plan(context, &mut dump);
+        let approach = dump.approach;
+        match result {
+            Ok(plan) => Ok((plan, PlanningLog {
+                lines: log,
+                approach,
+            })),
             Err(error) => Err(ProvisionalExpandError {
                 planner_name: planner.name(),
                 error,
@@ -185,11 +217,43 @@ impl<'a: 's, 's> PlanningContext<'a, 's> {
     }
 }
 
+/// Everything a planner recorded while it was planning.
+pub struct PlanningLog {
+    pub lines: Vec<String>,
+    pub approach: Option<ApproachRecord>,
+}
+
 pub struct PlanningDump<'a> {
     pub log: &'a mut Vec<String>,
+    approach: Option<ApproachRecord>,
+}
+
+/// The direction a plan will approach the ball from, alongside the direction
+/// we would like to hit the ball. If these are far apart, the plan is going to
+/// arrive at a bad angle.
+#[derive(Copy, Clone)]
+pub struct ApproachRecord {
+    pub ball_loc: Point2<f32>,
+    pub approach_dir: Unit<Vector2<f32>>,
+    pub hit_dir: Unit<Vector2<f32>>,
+}
+
+impl ApproachRecord {
+    /// The angle between the approach and the desired hit direction, from 0
+    /// (dead on) to PI (exactly backwards).
+    pub fn angle(&self) -> f32 {
+        self.approach_dir.angle_to(&self.hit_dir).abs()
+    }
 }
 
 impl<'a> PlanningDump<'a> {
+    pub fn new(log: &'a mut Vec<String>) -> Self {
+        Self {
+            log,
+            approach: None,
+        }
+    }
+
     fn log_line(&mut self, message: impl Into<String>) {
         self.log.push(message.into());
     }
@@ -213,6 +277,13 @@ impl<'a> PlanningDump<'a> {
         self.log_pretty(planner, "start vel", state.vel);
     }
 
+    pub fn log_approach(&mut self, planner: &dyn RoutePlanner, approach: ApproachRecord) {
+        self.log_pretty(planner, "approach dir", approach.approach_dir);
+        self.log_pretty(planner, "hit dir", approach.hit_dir);
+        self.log_pretty(planner, "approach angle", Angle(approach.angle()));
+        self.approach = Some(approach);
+    }
+
     pub fn log_plan(&mut self, planner: &dyn RoutePlanner, plan: &RoutePlan) {
         let name = plan.segment.name();
         let end = plan.segment.end();
@@ -266,6 +337,7 @@ pub enum RoutePlanError {
     CannotOperateWall,
     NoWallIntercept,
     TurnAngleTooLarge,
+    CannotArriveOnTime,
     OtherError(&'static str),
 }
 
@@ -281,6 +353,7 @@ impl fmt::Debug for RoutePlanError {
             RoutePlanError::CannotOperateWall => f.write_str(stringify!(CannotOperateWall)),
             RoutePlanError::NoWallIntercept => f.write_str(stringify!(NoWallIntercept)),
             RoutePlanError::TurnAngleTooLarge => f.write_str(stringify!(TurnAngleTooLarge)),
+            RoutePlanError::CannotArriveOnTime => f.write_str(stringify!(CannotArriveOnTime)),
             RoutePlanError::OtherError(msg) => write!(f, "{}({:?})", stringify!(OtherError), msg),
         }
     }
@@ -297,23 +370,26 @@ impl RoutePlan {
         &self,
         scenario: &Scenario<'_>,
     ) -> Result<ProvisionalPlanExpansionTail, ProvisionalExpandError<'_>> {
-        self.provisional_expand_2(scenario.game, scenario.ball_prediction())
+        self.provisional_expand_2(scenario.game, scenario.ball_prediction().slice(0.0), 0.0)
     }
 
+    /// `start_time` is how many seconds from now this plan's segment starts.
     pub fn provisional_expand_2(
         &self,
         game: &Game<'_>,
-        ball_prediction: &BallTrajectory,
+        ball_prediction: BallTrajectorySlice<'_>,
+        start_time: f32,
     ) -> Result<ProvisionalPlanExpansionTail, ProvisionalExpandError<'_>> {
         let mut tail = Vec::new();
         if let Some(ref planner) = self.next {
             let context = PlanningContext {
                 game,
                 start: self.segment.end(),
+                start_time: start_time + self.segment.duration(),
                 ball_prediction,
             };
             let mut log = Vec::new();
-            let mut dump = PlanningDump { log: &mut log };
+            let mut dump = PlanningDump::new(&mut log);
             match Self::expand_round(&**planner, &context, &mut dump, |s| tail.push(s)) {
                 Ok(()) => {}
                 Err((planner_name, error)) => {
@@ -347,7 +423,8 @@ impl RoutePlan {
                 let ctx = PlanningContext {
                     game: ctx.game,
                     start: state,
-                    ball_prediction: &ctx.ball_prediction.hacky_expensive_slice(duration),
+                    start_time: ctx.start_time + duration,
+                    ball_prediction: ctx.ball_prediction.slice(duration),
                 };
                 Self::expand_round(&*planner, &ctx, dump, sink)
             }
@@ -367,6 +444,31 @@ pub trait SegmentPlan: SegmentPlanCloneBox + Send {
     fn start(&self) -> CarState;
     fn end(&self) -> CarState;
     fn duration(&self) -> f32;
+
+    /// Estimate where the car will be `t` seconds after the segment starts.
+    /// The default is a linear interpolation between `start` and `end`.
+    fn position_at(&self, t: f32) -> Point3<f32> {
+        let start = self.start().loc;
+        let duration = self.duration();
+        if duration <= 0.0 {
+            return start;
+        }
+        let progress = (t / duration).max(0.0).min(1.0);
+        start + (self.end().loc - start) * progress
+    }
+
+    /// How long the car needs after the segment ends before it can act again,
+    /// e.g., to settle after landing from a dodge.
+    fn recovery_time(&self) -> f32 {
+        0.0
+    }
+
+    /// Whether it's safe to throw the segment away halfway through and replan.
+    /// Anything that leaves the ground has to be seen through to the end.
+    fn interruptible(&self) -> bool {
+        true
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner>;
     fn draw(&self, ctx: &mut Context<'_>);
 }
@@ -404,39 +506,37 @@ pub enum SegmentRunAction {
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        routing::models::{CarState, PlanningContext, PlanningDump, RoutePlanner},
-        strategy::Scenario,
-    };
+    use crate::routing::models::CarState;
     use common::prelude::*;
-    use nalgebra::{Point3, UnitComplex, Vector3};
-    use std::{f32::consts::PI, mem};
+    use nalgebra::{Point3, Rotation3, UnitComplex, UnitQuaternion, Vector3};
+    use std::f32::consts::PI;
 
     #[test]
-    #[ignore = "Use this as needed to debug a plan."]
-    fn debug_plan() {
-        // I'm very lucky that this works.
-        let scenario: Scenario<'_> = unsafe { mem::zeroed() };
-        let ball_prediction = unsafe { mem::zeroed() };
-
-        let planner: &dyn RoutePlanner = unsafe { mem::uninitialized() }; // Fill this in.
-        let ctx = PlanningContext {
-            game: &scenario.game,
-            start: CarState {
-                loc: Point3::new(0.0, -4608.0, 17.01),
-                rot: UnitComplex::new(PI / 2.0).around_z_axis(),
-                vel: Vector3::zeros(),
-                boost: 33.0,
-            },
-            ball_prediction: &ball_prediction,
+    fn on_flat_ground() {
+        let mut state = CarState {
+            loc: Point3::new(0.0, 0.0, 17.01),
+            rot: UnitComplex::new(PI / 2.0).around_z_axis(),
+            vel: Vector3::zeros(),
+            boost: 0.0,
         };
-        let mut log = Vec::new();
-        let mut dump = PlanningDump { log: &mut log };
-        planner
-            .plan(&ctx, &mut dump)
-            .unwrap()
-            .provisional_expand(&scenario)
-            .ok()
-            .unwrap();
+        assert!(state.on_flat_ground());
+
+        // On the side wall, driving forward along the wall. The nose is level,
+        // but the car is rolled 90 degrees.
+        state.loc = Point3::new(4096.0 - 17.01, 0.0, 1000.0);
+        state.rot = UnitQuaternion::from_rotation_matrix(&Rotation3::from_unreal_angles(
+            0.0,
+            PI / 2.0,
+            PI / 2.0,
+        ));
+        assert!(!state.on_flat_ground());
+
+        // Driving straight up the side wall.
+        state.rot = UnitQuaternion::from_rotation_matrix(&Rotation3::from_unreal_angles(
+            PI / 2.0 - 0.01,
+            0.0,
+            0.0,
+        ));
+        assert!(!state.on_flat_ground());
     }
 }
@@ -2,7 +2,7 @@ use crate::routing::{
     models::{PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
     plan::{
         ground_straight::GroundStraightPlanner, ground_turn::TurnPlanner,
-        higher_order::ChainedPlanner,
+        higher_order::ChainedPlanner, pathing::KeepOut,
     },
     recover::{IsSkidding, NotOnFlatGround},
     segments::StraightMode,
@@ -16,6 +16,8 @@ pub struct GroundDrive {
     end_chop: f32,
     straight_mode: StraightMode,
     always_prefer_dodge: bool,
+    target_end_speed: Option<f32>,
+    keep_out: Vec<KeepOut>,
 }
 
 impl GroundDrive {
@@ -25,6 +27,8 @@ impl GroundDrive {
             end_chop: 0.0,
             straight_mode: StraightMode::Asap,
             always_prefer_dodge: false,
+            target_end_speed: None,
+            keep_out: Vec::new(),
         }
     }
 
@@ -42,6 +46,19 @@ impl GroundDrive {
         self.always_prefer_dodge = always_prefer_dodge;
         self
     }
+
+    /// See `GroundStraightPlanner::target_end_speed`.
+    pub fn target_end_speed(mut self, speed: f32) -> Self {
+        self.target_end_speed = Some(speed);
+        self
+    }
+
+    /// See `GroundStraightPlanner::keep_out`. The turn at the start of the
+    /// route doesn't know about the region.
+    pub fn keep_out(mut self, region: KeepOut) -> Self {
+        self.keep_out.push(region);
+        self
+    }
 }
 
 impl RoutePlanner for GroundDrive {
@@ -66,10 +83,21 @@ impl RoutePlanner for GroundDrive {
             recover_target_loc: self.target_loc,
         });
 
-        let turn = TurnPlanner::new(self.target_loc, None).plan(ctx, dump)?;
-        let straight = GroundStraightPlanner::new(self.target_loc, self.straight_mode)
+        let turn = TurnPlanner::new(self.target_loc, None)
+            .allow_half_flip(true)
+            .prefer_arc(true)
+            .plan(ctx, dump)?;
+        let mut straight = GroundStraightPlanner::new(self.target_loc, self.straight_mode)
             .always_prefer_dodge(self.always_prefer_dodge)
-            .end_chop(self.end_chop);
+            .end_chop(self.end_chop)
+            .avoid_teammates(true)
+            .allow_arc(true);
+        if let Some(speed) = self.target_end_speed {
+            straight = straight.target_end_speed(speed);
+        }
+        for region in &self.keep_out {
+            straight = straight.keep_out(region.clone());
+        }
         Ok(ChainedPlanner::join_planner(turn, Some(Box::new(straight))))
     }
 }
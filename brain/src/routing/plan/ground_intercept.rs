@@ -1,12 +1,13 @@
 use crate::{
     behavior::strike::GroundedHit,
     helpers::{
-        ball::{BallFrame, BallTrajectory},
+        ball::{BallFrame, BallTrajectorySlice},
         intercept::{naive_ground_intercept_2, naive_intercept_penalty},
     },
     routing::{
         models::{
-            CarState, PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner,
+            ApproachRecord, CarState, PlanningContext, PlanningDump, RoutePlan, RoutePlanError,
+            RoutePlanner,
         },
         plan::{
             ground_straight::GroundStraightPlanner, ground_turn::TurnPlanner,
@@ -18,12 +19,43 @@ use crate::{
 };
 use common::{prelude::*, Time};
 use derive_new::new;
+use nalgebra::{Point2, Unit, Vector2};
 use nameof::name_of_type;
 
 #[derive(Clone, new)]
 pub struct GroundIntercept {
     #[new(value = "GroundInterceptAllowDodging::OnlyIfSlow")]
     allow_dodging: GroundInterceptAllowDodging,
+    #[new(default)]
+    target: Option<GroundInterceptTarget>,
+    #[new(default)]
+    approach_cone: Option<ApproachCone>,
+    #[new(value = "GroundedHit::MAX_BALL_Z")]
+    max_ball_z: f32,
+}
+
+/// A ball frame chosen ahead of time, which the car must arrive at within
+/// `tolerance` seconds. The frame's `t` is relative to `predicted_at`, the game
+/// time of the prediction it came from.
+#[derive(Clone)]
+struct GroundInterceptTarget {
+    frame: BallFrame,
+    predicted_at: f32,
+    tolerance: f32,
+}
+
+/// The car must approach the ball within `max_angle` of `dir`, or else it'll
+/// send the ball the wrong way.
+#[derive(Copy, Clone)]
+struct ApproachCone {
+    dir: Unit<Vector2<f32>>,
+    max_angle: f32,
+}
+
+impl ApproachCone {
+    fn admits(&self, car_loc: Point2<f32>, ball_loc: Point2<f32>) -> bool {
+        self.dir.angle_to(&(ball_loc - car_loc)).abs() <= self.max_angle
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -42,6 +74,42 @@ impl GroundIntercept {
         };
         self
     }
+
+    /// Intercept the ball at a specific frame instead of the earliest one we
+    /// can reach. `predicted_at` is the game time of the prediction the frame
+    /// came from, so the timing stays right when the route is replanned later.
+    /// Planning fails if we can't get there within `tolerance` seconds of the
+    /// frame's time.
+    pub fn target_frame(mut self, frame: BallFrame, predicted_at: f32, tolerance: f32) -> Self {
+        assert!(tolerance >= 0.0);
+        self.target = Some(GroundInterceptTarget {
+            frame,
+            predicted_at,
+            tolerance,
+        });
+        self
+    }
+
+    /// Only accept intercepts where we'd be driving within `max_angle` of
+    /// `desired_dir` when we reach the ball. Intercepts we'd reach from
+    /// outside the cone are skipped in favor of later ones, and if there are
+    /// none, planning fails.
+    pub fn with_approach_cone(mut self, desired_dir: Unit<Vector2<f32>>, max_angle: f32) -> Self {
+        assert!(max_angle >= 0.0);
+        self.approach_cone = Some(ApproachCone {
+            dir: desired_dir,
+            max_angle,
+        });
+        self
+    }
+
+    /// Skip over intercepts where the ball is higher than `max_ball_z`. This
+    /// can only lower the limit; anything above `GroundedHit::MAX_BALL_Z` is
+    /// out of reach from the ground anyway.
+    pub fn max_ball_z(mut self, max_ball_z: f32) -> Self {
+        self.max_ball_z = max_ball_z.min(GroundedHit::MAX_BALL_Z);
+        self
+    }
 }
 
 impl RoutePlanner for GroundIntercept {
@@ -62,18 +130,31 @@ impl RoutePlanner for GroundIntercept {
             RoutePlanError::MustBeOnFlatGround,
         );
 
-        // Naive first pass to get a rough location.
-        let guess = Self::calc_intercept(&ctx.start, ctx.ball_prediction)
-            .ok_or_else(|| RoutePlanError::UnknownIntercept)?;
+        // `guess_t` is relative to the start of planning, even if the target
+        // frame is from an older prediction.
+        let (guess, guess_t) = match self.target {
+            Some(ref target) => {
+                let elapsed = ctx.game.time() + ctx.start_time - target.predicted_at;
+                (target.frame.clone(), target.frame.t - elapsed)
+            }
+            None => {
+                // Naive first pass to get a rough location.
+                let guess = self
+                    .calc_intercept_in_cone(&ctx.start, &ctx.ball_prediction)
+                    .ok_or_else(|| RoutePlanError::UnknownIntercept)?;
+                let guess_t = guess.t;
+                (guess, guess_t)
+            }
+        };
 
         guard!(ctx.start, IsSkidding, RoutePlanError::MustNotBeSkidding {
             recover_target_loc: guess.loc.to_2d(),
         });
 
-        dump.log_pretty(self, "guess ball time", Time(guess.t));
+        dump.log_pretty(self, "guess ball time", Time(guess_t));
         dump.log_pretty(self, "guess ball loc", guess.loc.to_2d());
 
-        if is_ball_directly_behind_car(ctx.ball_prediction, &ctx.start) {
+        if is_ball_directly_behind_car(ctx.ball_prediction.start(), &ctx.start) {
             dump.log(self, "the ball is directly behind us; I can't even");
             return Err(RoutePlanError::TurningRadiusTooTight);
         }
@@ -86,9 +167,28 @@ impl RoutePlanner for GroundIntercept {
             };
         let turn = TurnPlanner::new(guess.loc.to_2d(), None)
             .reverse_angle_hint(reverse_angle_hint.to_axis())
+            .allow_half_flip(true)
             .plan(ctx, dump)?;
 
-        let mut straight_time = guess.t - turn.segment.duration();
+        dump.log_approach(self, ApproachRecord {
+            ball_loc: guess.loc.to_2d(),
+            approach_dir: (guess.loc.to_2d() - turn.segment.end().loc_2d()).to_axis(),
+            hit_dir: reverse_angle_hint.to_axis(),
+        });
+
+        let mut straight_time = guess_t - turn.segment.duration();
+        // Any lateness from the turn eats into the tolerance left for the
+        // straight.
+        let straight_tolerance = self
+            .target
+            .as_ref()
+            .map(|target| target.tolerance + straight_time.min(0.0));
+        if let Some(tolerance) = straight_tolerance {
+            if tolerance < 0.0 {
+                dump.log(self, "the turn alone makes us miss the target frame");
+                return Err(RoutePlanError::CannotArriveOnTime);
+            }
+        }
         if straight_time < 0.0 {
             dump.log(
                 self,
@@ -107,25 +207,244 @@ impl RoutePlanner for GroundIntercept {
                     && car_speed_towards_ball * 1.1 < ctx.start.vel_2d().norm()
             }
         };
-        let straight = GroundStraightPlanner::new(guess.loc.to_2d(), StraightMode::Fake)
+        let mut straight = GroundStraightPlanner::new(guess.loc.to_2d(), StraightMode::Fake)
             .target_time(straight_time)
             .end_chop(0.5)
             .allow_dodging(allow_dodging);
+        if let Some(tolerance) = straight_tolerance {
+            straight = straight.arrival_tolerance(tolerance);
+        }
 
         Ok(ChainedPlanner::join_planner(turn, Some(Box::new(straight))))
     }
 }
 
 impl GroundIntercept {
-    pub fn calc_intercept<'ball>(
+    pub fn calc_intercept(
         start: &CarState,
-        ball_prediction: &'ball BallTrajectory,
-    ) -> Option<&'ball BallFrame> {
-        let intercept = naive_ground_intercept_2(start, ball_prediction, |ball| {
+        ball_prediction: &BallTrajectorySlice<'_>,
+    ) -> Option<BallFrame> {
+        Self::calc_intercept_where(start, ball_prediction, |ball| {
             ball.loc.z < GroundedHit::MAX_BALL_Z
-        })?;
+        })
+    }
+
+    fn calc_intercept_in_cone(
+        &self,
+        start: &CarState,
+        ball_prediction: &BallTrajectorySlice<'_>,
+    ) -> Option<BallFrame> {
+        Self::calc_intercept_where(start, ball_prediction, |ball| {
+            let in_cone = match self.approach_cone {
+                Some(cone) => cone.admits(start.loc_2d(), ball.loc.to_2d()),
+                None => true,
+            };
+            ball.loc.z < self.max_ball_z && in_cone
+        })
+    }
+
+    fn calc_intercept_where(
+        start: &CarState,
+        ball_prediction: &BallTrajectorySlice<'_>,
+        predicate: impl Fn(&BallFrame) -> bool,
+    ) -> Option<BallFrame> {
+        let intercept = naive_ground_intercept_2(start, ball_prediction.iter(), predicate)?;
         let intercept = ball_prediction.at_time(intercept.time).unwrap();
-        let penalty = naive_intercept_penalty(start, intercept);
+        let penalty = naive_intercept_penalty(start, &intercept);
         Some(ball_prediction.at_time_or_last(intercept.t + penalty))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::ball::{BallFrame, BallTrajectory},
+        routing::{models::CarState, plan::GroundIntercept},
+    };
+    use nalgebra::{Point3, Unit, UnitQuaternion, Vector2, Vector3};
+    use std::f32::consts::PI;
+
+    /// The ball rolls from left to right across the field, in front of the car.
+    fn crossing_ball() -> BallTrajectory {
+        let frames = (0..360)
+            .map(|i| {
+                let t = i as f32 / 60.0;
+                BallFrame::new(
+                    t,
+                    1.0 / 60.0,
+                    Point3::new(-1500.0 + t * 1000.0, 1500.0, 92.74),
+                    Vector3::new(1000.0, 0.0, 0.0),
+                )
+            })
+            .collect();
+        BallTrajectory::new(frames)
+    }
+
+    #[test]
+    fn reject_intercept_outside_approach_cone() {
+        let start = CarState {
+            loc: Point3::new(0.0, 0.0, 17.01),
+            rot: UnitQuaternion::from_axis_angle(&Vector3::z_axis(), PI / 2.0),
+            vel: Vector3::zeros(),
+            boost: 100.0,
+        };
+        let ball_prediction = crossing_ball();
+        let ball_prediction = ball_prediction.slice(0.0);
+
+        // Left alone, we'd meet the ball head-on and knock it back upfield.
+        let direct = GroundIntercept::calc_intercept(&start, &ball_prediction).unwrap();
+        assert!(direct.loc.x < 1500.0, "{:?}", direct.loc);
+
+        // To push the ball to the right, we have to wait for it to roll past.
+        let planner = GroundIntercept::new()
+            .with_approach_cone(Unit::new_normalize(Vector2::new(1.0, 0.0)), PI / 4.0);
+        let clean = planner
+            .calc_intercept_in_cone(&start, &ball_prediction)
+            .unwrap();
+        assert!(clean.loc.x >= 1500.0, "{:?}", clean.loc);
+        assert!(clean.t > direct.t);
+    }
+
+    #[test]
+    fn fail_without_approach_in_cone() {
+        let start = CarState {
+            loc: Point3::new(0.0, 0.0, 17.01),
+            rot: UnitQuaternion::from_axis_angle(&Vector3::z_axis(), PI / 2.0),
+            vel: Vector3::zeros(),
+            boost: 100.0,
+        };
+        // The ball stays upfield the whole time, so we can never approach it
+        // heading back downfield.
+        let planner = GroundIntercept::new()
+            .with_approach_cone(Unit::new_normalize(Vector2::new(0.0, -1.0)), PI / 4.0);
+        assert!(planner
+            .calc_intercept_in_cone(&start, &crossing_ball().slice(0.0))
+            .is_none());
+    }
+
+    #[test]
+    fn skip_intercepts_above_max_ball_z() {
+        let start = CarState {
+            loc: Point3::new(0.0, 0.0, 17.01),
+            rot: UnitQuaternion::from_axis_angle(&Vector3::z_axis(), PI / 2.0),
+            vel: Vector3::zeros(),
+            boost: 100.0,
+        };
+        // The ball sits in place, slowly sinking from chest height to the
+        // ground.
+        let frames = (0..360)
+            .map(|i| {
+                let t = i as f32 / 60.0;
+                BallFrame::new(
+                    t,
+                    1.0 / 60.0,
+                    Point3::new(0.0, 1000.0, 200.0 - t * 20.0),
+                    Vector3::new(0.0, 0.0, -20.0),
+                )
+            })
+            .collect();
+        let ball_prediction = BallTrajectory::new(frames);
+        let ball_prediction = ball_prediction.slice(0.0);
+
+        let direct = GroundIntercept::new()
+            .calc_intercept_in_cone(&start, &ball_prediction)
+            .unwrap();
+        let capped = GroundIntercept::new()
+            .max_ball_z(150.0)
+            .calc_intercept_in_cone(&start, &ball_prediction)
+            .unwrap();
+        assert!(capped.loc.z < 150.0, "{:?}", capped.loc);
+        assert!(capped.t > direct.t);
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::{
+            higher_order::Chain,
+            strike::{GroundedHit, GroundedHitTarget, GroundedHitTargetAdjust},
+        },
+        integration_tests::{TestRunner, TestScenario},
+        routing::{behavior::FollowRoute, plan::GroundIntercept},
+        strategy::{Action, Behavior, Context, Priority},
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point2, Point3, Vector3};
+
+    /// Pick the ball frame `t` seconds in the future, then route there and hit
+    /// it.
+    struct HitAtTime {
+        t: f32,
+        tolerance: f32,
+    }
+
+    impl Behavior for HitAtTime {
+        fn name(&self) -> &str {
+            stringify!(HitAtTime)
+        }
+
+        fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+            let frame = ctx.scenario.ball_prediction().at_time_or_last(self.t);
+            let planner = GroundIntercept::new().target_frame(
+                frame.clone(),
+                ctx.packet.GameInfo.TimeSeconds,
+                self.tolerance,
+            );
+            Action::tail_call(Chain::new(Priority::Strike, vec![
+                Box::new(FollowRoute::new(planner)),
+                Box::new(GroundedHit::hit_towards(|ctx| {
+                    Ok(GroundedHitTarget::new(
+                        ctx.intercept_time,
+                        GroundedHitTargetAdjust::RoughAim,
+                        Point2::new(0.0, rl::FIELD_MAX_Y),
+                    ))
+                })),
+            ]))
+        }
+    }
+
+    #[test]
+    fn hit_pre_chosen_frame() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(-1500.0, 2500.0, 92.74),
+                ball_vel: Vector3::new(500.0, 0.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(HitAtTime {
+                t: 3.0,
+                tolerance: 0.25,
+            })
+            .run_for_millis(2500);
+
+        // We shouldn't have touched the ball before the chosen time.
+        let packet = test.sniff_packet();
+        assert!(packet.GameBall.Physics.vel().y.abs() < 100.0);
+
+        test.sleep_millis(1000);
+
+        let packet = test.sniff_packet();
+        assert!(packet.GameBall.Physics.vel().y > 500.0);
+    }
+
+    #[test]
+    fn reject_unreachable_frame() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, 4000.0, 92.74),
+                boost: 0,
+                ..Default::default()
+            })
+            .behavior(HitAtTime {
+                t: 0.5,
+                tolerance: 0.25,
+            })
+            .run_for_millis(1000);
+
+        // The route can't be planned, so we should never have started moving.
+        let packet = test.sniff_packet();
+        let car_loc = packet.GameCars[0].Physics.loc();
+        assert!(car_loc.y < 500.0);
+    }
+}
@@ -3,16 +3,18 @@ use crate::routing::{
         CarState, CarState2D, PlanningContext, PlanningDump, RoutePlan, RoutePlanError,
         RoutePlanner, SegmentPlan,
     },
+    plan::{ground_turn::PathingUnawareTurnPlanner, higher_order::ChainedPlanner, KeepOut},
     recover::{IsSkidding, NotFacingTarget2D, NotOnFlatGround},
-    segments::{Brake, Chain, ForwardDodge, Straight, StraightMode},
+    segments::{Brake, Chain, ForwardDodge, SimpleArc, Straight, StraightMode},
 };
 use arrayvec::ArrayVec;
-use common::prelude::*;
+use common::{prelude::*, Time};
 use derive_new::new;
-use nalgebra::Point2;
+use nalgebra::{Point2, Vector2};
 use nameof::name_of_type;
 use ordered_float::NotNan;
 use simulate::{Car1D, CarForwardDodge, CarForwardDodge1D};
+use std::f32::consts::PI;
 
 /// Drive straight. Requires the car to already be facing the target (i.e., it
 /// won't steer left or right).
@@ -28,9 +30,25 @@ pub struct GroundStraightPlanner {
     allow_dodging: bool,
     allow_boost: bool,
     always_prefer_dodge: bool,
+    /// If set, planning fails unless the car can reach `target_loc` within
+    /// this many seconds of `target_time`.
+    arrival_tolerance: Option<f32>,
+    /// Regions to route around on the way to `target_loc`.
+    keep_out: Vec<KeepOut>,
+    /// If set, brake near the end so we arrive going about this fast.
+    target_end_speed: Option<f32>,
+    avoid_teammates: bool,
+    allow_arc: bool,
 }
 
 impl GroundStraightPlanner {
+    /// How far to stay clear of the corners of a `KeepOut` region.
+    const KEEP_OUT_MARGIN: f32 = 150.0;
+
+    /// When we're about to hit a teammate, keep out of a box this far in each
+    /// direction from where they'll be.
+    const TEAMMATE_CLEARANCE: f32 = 200.0;
+
     pub fn new(target_loc: Point2<f32>, mode: StraightMode) -> Self {
         Self {
             target_loc,
@@ -40,6 +58,11 @@ impl GroundStraightPlanner {
             allow_dodging: true,
             allow_boost: true,
             always_prefer_dodge: true,
+            arrival_tolerance: None,
+            keep_out: Vec::new(),
+            target_end_speed: None,
+            avoid_teammates: false,
+            allow_arc: false,
         }
     }
 
@@ -68,6 +91,174 @@ impl GroundStraightPlanner {
         self.always_prefer_dodge = always_prefer_dodge;
         self
     }
+
+    /// Require that the car arrive at `target_time`, give or take `tolerance`
+    /// seconds. Must be combined with `target_time`.
+    pub fn arrival_tolerance(mut self, tolerance: f32) -> Self {
+        assert!(tolerance >= 0.0);
+        self.arrival_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Don't drive through `region`. If it's in the way, detour around one of
+    /// its corners. Detours are planned without regard for `target_time`.
+    pub fn keep_out(mut self, region: KeepOut) -> Self {
+        self.keep_out.push(region);
+        self
+    }
+
+    /// Arrive going about `speed` instead of as fast as possible, e.g. to hit
+    /// the ball at a chosen speed. If we can't get up to `speed` by the end,
+    /// this has no effect. Only applies to `StraightMode::Asap`, and rules out
+    /// dodging, since a dodge would blow right past the speed.
+    pub fn target_end_speed(mut self, speed: f32) -> Self {
+        assert!(speed >= 0.0);
+        self.target_end_speed = Some(speed);
+        self
+    }
+
+    /// If we're about to run into a teammate, detour around them. This is off
+    /// by default, and has no effect when `target_time` is set, since the
+    /// detour would throw away the timing (see `keep_out`).
+    pub fn avoid_teammates(mut self, avoid_teammates: bool) -> Self {
+        self.avoid_teammates = avoid_teammates;
+        self
+    }
+
+    /// If we're not quite lined up with the target, consider curving in on a
+    /// single constant-curvature arc instead of steering along a straight
+    /// line. Whichever is faster wins. This is off by default. The arc is
+    /// driven flat out to the very end, so it's skipped when `end_chop`,
+    /// `target_time` or `target_end_speed` are set, or in `StraightMode::Fake`.
+    pub fn allow_arc(mut self, allow_arc: bool) -> Self {
+        self.allow_arc = allow_arc;
+        self
+    }
+
+    /// If a `KeepOut` region is in the way, plan a detour around it. The rest
+    /// of the route is this same planner, starting from the detour waypoint,
+    /// so any other regions still get avoided. Regions that we start in or
+    /// that cover the target can't be avoided, so they're ignored.
+    fn plan_detour(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Option<Result<RoutePlan, RoutePlanError>> {
+        let start_loc = ctx.start.loc.to_2d();
+        let waypoint = self
+            .keep_out
+            .iter()
+            .filter(|region| !region.contains(start_loc) && !region.contains(self.target_loc))
+            .find_map(|region| {
+                region.detour_waypoint(start_loc, self.target_loc, Self::KEEP_OUT_MARGIN)
+            })?;
+        dump.log_pretty(self, "keep-out detour", waypoint);
+
+        // The original timing doesn't mean much once we've taken a detour.
+        let mut rest = self.clone();
+        rest.target_time = None;
+        rest.arrival_tolerance = None;
+
+        let detour = ChainedPlanner::chain(vec![
+            Box::new(PathingUnawareTurnPlanner::new(waypoint, None)),
+            Box::new(GroundStraightPlanner::new(waypoint, StraightMode::Asap)),
+            Box::new(PathingUnawareTurnPlanner::new(self.target_loc, None)),
+            Box::new(rest),
+        ]);
+        Some(detour.plan(ctx, dump))
+    }
+
+    /// If we're about to run into a teammate, treat the spot where we'd meet
+    /// them as a `KeepOut` region, and detour around it. Meeting them right at
+    /// the target doesn't count, since there's no way around that.
+    fn plan_teammate_detour(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Option<Result<RoutePlan, RoutePlanError>> {
+        if !self.avoid_teammates || self.target_time.is_some() {
+            return None;
+        }
+        let collision = ctx
+            .teammates()
+            .find_map(|teammate| teammate_collision(&ctx.start, self.target_loc, &teammate))?;
+        if (self.target_loc - collision).norm() < Self::TEAMMATE_CLEARANCE * 2.0 {
+            return None;
+        }
+        dump.log_pretty(self, "teammate collision", collision);
+
+        let clearance = Vector2::new(Self::TEAMMATE_CLEARANCE, Self::TEAMMATE_CLEARANCE);
+        let mut avoiding = self.clone();
+        avoiding.avoid_teammates = false;
+        avoiding
+            .keep_out
+            .push(KeepOut::rect(collision - clearance, collision + clearance));
+        avoiding.plan_detour(ctx, dump)
+    }
+
+    /// Check whether the car could reach the target in time if it drove there
+    /// as fast as possible. Arriving early is never a problem, since we can
+    /// always brake or coast.
+    fn check_arrival_time(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<(), RoutePlanError> {
+        let tolerance = some_or_else!(self.arrival_tolerance, {
+            return Ok(());
+        });
+        let target_time = self
+            .target_time
+            .expect("arrival_tolerance requires target_time");
+
+        let target_dist = (self.target_loc - ctx.start.loc.to_2d()).norm();
+        let mut blitz = Car1D::new()
+            .with_speed(ctx.start.vel.to_2d().norm())
+            .with_boost(ctx.start.boost);
+        blitz.advance_by_distance(target_dist, 1.0, self.allow_boost);
+
+        dump.log_pretty(self, "target_time", Time(target_time));
+        dump.log_pretty(self, "fastest arrival", Time(blitz.time()));
+
+        if blitz.time() > target_time + tolerance {
+            return Err(RoutePlanError::CannotArriveOnTime);
+        }
+        Ok(())
+    }
+
+    fn plan_arc(&self, ctx: &PlanningContext<'_, '_>) -> Option<RoutePlan> {
+        if !self.allow_arc
+            || self.end_chop != 0.0
+            || self.target_time.is_some()
+            || self.target_end_speed.is_some()
+        {
+            return None;
+        }
+        if let StraightMode::Fake = self.mode {
+            return None;
+        }
+
+        let start = CarState2D {
+            loc: ctx.start.loc.to_2d(),
+            rot: ctx.start.rot.to_2d(),
+            vel: ctx.start.vel.to_2d(),
+            boost: ctx.start.boost,
+        };
+        // We keep the throttle down through the arc, so make sure it's still
+        // drivable at the speed we'd reach by the end.
+        let target_dist = (self.target_loc - start.loc).norm();
+        let mut blitz = Car1D::new()
+            .with_speed(start.vel.norm())
+            .with_boost(start.boost);
+        blitz.advance_by_distance(target_dist, 1.0, self.allow_boost);
+        let min_radius = 1.0 / chip::max_curvature(blitz.speed());
+
+        let arc = arc_approach(&start, self.target_loc, min_radius)?;
+        Some(RoutePlan {
+            segment: Box::new(arc),
+            next: None,
+        })
+    }
 }
 
 impl RoutePlanner for GroundStraightPlanner {
@@ -92,6 +283,14 @@ impl RoutePlanner for GroundStraightPlanner {
         guard!(ctx.start, IsSkidding, RoutePlanError::MustNotBeSkidding {
             recover_target_loc: self.target_loc,
         });
+        self.check_arrival_time(ctx, dump)?;
+
+        if let Some(detour) = self.plan_detour(ctx, dump) {
+            return detour;
+        }
+        if let Some(detour) = self.plan_teammate_detour(ctx, dump) {
+            return detour;
+        }
 
         let straight = StraightSimple::new(
             self.target_loc,
@@ -99,12 +298,18 @@ impl RoutePlanner for GroundStraightPlanner {
             self.end_chop,
             self.mode,
             self.allow_boost,
+            self.target_end_speed,
         );
         let straight = straight.plan(ctx, dump);
 
-        let dodge = if self.allow_dodging {
-            let planner =
-                StraightWithDodge::new(self.target_loc, self.target_time, self.end_chop, self.mode);
+        let dodge = if self.allow_dodging && self.target_end_speed.is_none() {
+            let planner = StraightWithDodge::new(
+                self.target_loc,
+                self.target_time,
+                self.end_chop,
+                self.mode,
+                self.arrival_tolerance,
+            );
             Some(planner.plan(ctx, dump))
         } else {
             None
@@ -123,6 +328,9 @@ impl RoutePlanner for GroundStraightPlanner {
         if let Some(dodge) = dodge {
             plans.push(dodge);
         }
+        if let Some(arc) = self.plan_arc(ctx) {
+            plans.push(Ok(arc));
+        }
         Ok(fastest(at_least_one_ok(plans)?))
     }
 }
@@ -143,10 +351,12 @@ fn at_least_one_ok<T, E>(results: impl IntoIterator<Item = Result<T, E>>) -> Res
     }
 }
 
+/// Returns the plan that leaves us ready for the next action soonest. A dodge
+/// that arrives first but leaves us still recovering isn't actually faster.
 fn fastest(steps: impl IntoIterator<Item = RoutePlan>) -> RoutePlan {
     steps
         .into_iter()
-        .min_by_key(|s| NotNan::new(s.segment.duration()).unwrap())
+        .min_by_key(|s| NotNan::new(s.segment.duration() + s.segment.recovery_time()).unwrap())
         .unwrap()
 }
 
@@ -160,6 +370,7 @@ struct StraightSimple {
     end_chop: f32,
     mode: StraightMode,
     allow_boost: bool,
+    target_end_speed: Option<f32>,
 }
 
 impl RoutePlanner for StraightSimple {
@@ -201,13 +412,25 @@ impl RoutePlanner for StraightSimple {
             });
         }
 
+        let start = CarState2D {
+            loc: ctx.start.loc.to_2d(),
+            rot: ctx.start.rot.to_2d(),
+            vel: ctx.start.vel.to_2d(),
+            boost: ctx.start.boost,
+        };
+        if let (StraightMode::Asap, Some(target_end_speed)) = (self.mode, self.target_end_speed) {
+            if let Some((approach, brake)) =
+                approach_then_brake(&start, self.target_loc, target_end_speed, self.allow_boost)
+            {
+                return Ok(RoutePlan {
+                    segment: Box::new(Chain::new(vec![Box::new(approach), Box::new(brake)])),
+                    next: None,
+                });
+            }
+        }
+
         let segment = Straight::new(
-            CarState2D {
-                loc: ctx.start.loc.to_2d(),
-                rot: ctx.start.rot.to_2d(),
-                vel: ctx.start.vel.to_2d(),
-                boost: ctx.start.boost,
-            },
+            start,
             self.target_loc,
             self.end_chop,
             self.mode,
@@ -235,6 +458,133 @@ impl StraightSimple {
     }
 }
 
+/// A single constant-curvature arc from `start` to `target_loc`, which blends
+/// away the heading error instead of steering it out up front. Returns `None`
+/// if we're already lined up, if the heading error is too big for a gentle
+/// curve, or if the arc would be tighter than `min_radius`.
+fn arc_approach(start: &CarState2D, target_loc: Point2<f32>, min_radius: f32) -> Option<SimpleArc> {
+    // Below this, driving straight is just as good.
+    const MIN_ANGLE: f32 = PI / 90.0;
+    // Past this, turning in place first is the better plan.
+    const MAX_ANGLE: f32 = PI / 6.0;
+
+    let forward = start.forward_axis();
+    let to_target = target_loc - start.loc;
+    let angle = forward.angle_to(&to_target);
+    if angle.abs() < MIN_ANGLE || angle.abs() >= MAX_ANGLE {
+        return None;
+    }
+
+    // The circle tangent to our heading that passes through the target. The
+    // chord to the target subtends twice the heading error.
+    let radius = to_target.norm() / (2.0 * angle.sin().abs());
+    if radius < min_radius {
+        return None;
+    }
+    let left = Vector2::new(-forward.y, forward.x);
+    let center = start.loc + left * angle.signum() * radius;
+
+    SimpleArc::new(
+        center,
+        radius,
+        start.loc,
+        forward.into_inner() * start.vel.norm(),
+        start.boost,
+        target_loc,
+    )
+    .ok()
+}
+
+/// If driving flat out from `start` towards `target_loc` would bring us within
+/// a car's length of `teammate` in the next moment, returns where the teammate
+/// will be at the time. The teammate is assumed to keep its current velocity.
+fn teammate_collision(
+    start: &CarState,
+    target_loc: Point2<f32>,
+    teammate: &CarState,
+) -> Option<Point2<f32>> {
+    const HORIZON: f32 = 1.0;
+    const STEP: f32 = 0.05;
+    const COLLISION_DIST: f32 = 250.0;
+
+    let start_loc = start.loc.to_2d();
+    let target_dist = (target_loc - start_loc).norm();
+    let dir = (target_loc - start_loc).try_normalize(1.0)?;
+    let mut car = Car1D::new()
+        .with_speed(start.vel.to_2d().norm())
+        .with_boost(start.boost);
+
+    let mut t = 0.0;
+    while t < HORIZON && car.distance() < target_dist {
+        car.advance(STEP, 1.0, true);
+        t += STEP;
+        let me_loc = start_loc + dir * car.distance().min(target_dist);
+        let teammate_loc = teammate.loc.to_2d() + teammate.vel.to_2d() * t;
+        if (teammate_loc - me_loc).norm() < COLLISION_DIST {
+            return Some(teammate_loc);
+        }
+    }
+    None
+}
+
+/// Drive flat out towards `target_loc`, then brake so that we reach it going
+/// `target_end_speed`. Returns `None` if we wouldn't be going any faster than
+/// that anyway.
+fn approach_then_brake(
+    start: &CarState2D,
+    target_loc: Point2<f32>,
+    target_end_speed: f32,
+    allow_boost: bool,
+) -> Option<(Straight, Brake)> {
+    // Don't bother braking for less than this.
+    const SPEED_TOLERANCE: f32 = 50.0;
+
+    let target_dist = (target_loc - start.loc).norm();
+    let dir = (target_loc - start.loc).try_normalize(1.0)?;
+
+    let approach = |approach_dist: f32| {
+        Straight::new(
+            start.clone(),
+            start.loc + dir * approach_dist,
+            0.0,
+            StraightMode::Asap,
+            allow_boost,
+        )
+    };
+    let brake = |approach: &Straight| {
+        let approach_end = approach.end().to_2d_assume();
+        if approach_end.vel.norm() <= target_end_speed {
+            return None;
+        }
+        Some(Brake::new(approach_end, target_end_speed))
+    };
+
+    let flat_out = approach(target_dist);
+    if flat_out.end().vel.norm() < target_end_speed + SPEED_TOLERANCE {
+        return None;
+    }
+
+    // Find the latest point we can start braking and still slow down in time.
+    let mut lo = 0.0;
+    let mut hi = target_dist;
+    for _ in 0..16 {
+        let mid = (lo + hi) / 2.0;
+        let end_loc = match brake(&approach(mid)) {
+            Some(brake) => brake.end().loc.to_2d(),
+            None => start.loc + dir * mid,
+        };
+        if (end_loc - start.loc).dot(&dir) > target_dist {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    let approach = approach(lo);
+    let brake = brake(&approach)?;
+    Some((approach, brake))
+}
+
 /// Calculate a ground interception of the ball with a single dodge.
 #[derive(Clone, new)]
 struct StraightWithDodge {
@@ -245,6 +595,7 @@ struct StraightWithDodge {
     /// shoot, position itself, etc.
     end_chop: f32,
     mode: StraightMode,
+    arrival_tolerance: Option<f32>,
 }
 
 impl RoutePlanner for StraightWithDodge {
@@ -297,11 +648,21 @@ impl RoutePlanner for StraightWithDodge {
             true,
         );
 
-        let dodge = ForwardDodge::new(before.end(), dodge.dodge);
+        let mut dodge = ForwardDodge::new(before.end(), dodge.dodge, ctx.game.gravity());
+        if before.duration() == 0.0 {
+            // We're dodging right away, so we won't have had a chance to steer
+            // towards the target. Aim the dodge itself to make up the difference.
+            let start = before.end();
+            let correction = start
+                .forward_axis_2d()
+                .rotation_to(&(self.target_loc - start.loc.to_2d()).to_axis());
+            dodge = dodge.angle(correction);
+        }
 
         let mut after = GroundStraightPlanner::new(self.target_loc, self.mode);
         after.target_time = self.target_time;
         after.end_chop = self.end_chop;
+        after.arrival_tolerance = self.arrival_tolerance;
 
         let segment = Chain::new(vec![Box::new(before), Box::new(dodge)]);
         Ok(RoutePlan {
@@ -312,6 +673,8 @@ impl RoutePlanner for StraightWithDodge {
 }
 
 /// Calculate motions consisting of straight, then dodge, then straight again.
+/// Each candidate is scored as if it were followed by the best chain of up to
+/// `MAX_DODGES - 1` more dodges, so long approaches aren't undersold.
 #[derive(new)]
 struct StraightDodgeCalculator {
     start: CarState,
@@ -321,35 +684,44 @@ struct StraightDodgeCalculator {
 }
 
 impl StraightDodgeCalculator {
+    // Performance knobs
+    const GRANULARITY: f32 = 0.125;
+    const MAX_DODGES: usize = 2;
+
     pub fn collect(&self) -> Vec<StraightDodge> {
-        // Performance knob
-        const GRANULARITY: f32 = 0.125;
+        let from = Progress {
+            time: 0.0,
+            distance: 0.0,
+            speed: self.start.vel.to_2d().norm(),
+            boost: self.start.boost,
+        };
+        self.collect_from(from, Self::MAX_DODGES)
+    }
 
+    fn collect_from(&self, from: Progress, dodges: usize) -> Vec<StraightDodge> {
         let mut result = Vec::new();
 
         let mut t = 0.0;
         loop {
             if let Some(target_time) = self.target_time {
-                if t >= target_time {
+                if from.time + t >= target_time {
                     break;
                 }
             }
 
-            match self.evaluate(t) {
+            match self.evaluate(from, t, dodges) {
                 Some(dodge) => result.push(dodge),
                 None => break,
             }
 
-            t += GRANULARITY;
+            t += Self::GRANULARITY;
         }
 
         result
     }
 
-    fn evaluate(&self, approach_time: f32) -> Option<StraightDodge> {
-        let mut approach = Car1D::new()
-            .with_speed(self.start.vel.to_2d().norm())
-            .with_boost(self.start.boost);
+    fn evaluate(&self, from: Progress, approach_time: f32, dodges: usize) -> Option<StraightDodge> {
+        let mut approach = Car1D::new().with_speed(from.speed).with_boost(from.boost);
         approach.advance(approach_time, 1.0, true);
 
         let dodge = CarForwardDodge::calc_1d(approach.speed());
@@ -365,7 +737,13 @@ impl StraightDodgeCalculator {
         // requirements:
 
         // Check if we can even complete the dodge by the target time.
-        let total_time = approach.time() + dodge.duration() + landing.time();
+        let landed = Progress {
+            time: from.time + approach.time() + dodge.duration(),
+            distance: from.distance + approach.distance() + dodge.end_dist,
+            speed: dodge.end_speed,
+            boost: approach.boost(),
+        };
+        let total_time = landed.time + landing.time();
         if let Some(target_time) = self.target_time {
             if total_time > target_time {
                 return None;
@@ -374,7 +752,7 @@ impl StraightDodgeCalculator {
 
         // Check that we don't land past the target.
         let target_traveled = (self.target_loc - self.start.loc.to_2d()).norm();
-        let total_dist = approach.distance() + dodge.end_dist + landing.distance();
+        let total_dist = landed.distance + landing.distance();
         if total_dist >= target_traveled {
             return None;
         }
@@ -398,33 +776,211 @@ impl StraightDodgeCalculator {
             .with_speed(dodge.end_speed)
             .with_boost(approach.boost());
         blitz.advance_by_distance(target_traveled - total_dist, 1.0, false);
-        let score = total_time + blitz.time();
+        let mut score = total_time + blitz.time();
+
+        // If another dodge after landing would get there sooner, this dodge deserves
+        // the credit for setting it up.
+        if dodges > 1 {
+            let followups = self.collect_from(landed, dodges - 1);
+            if let Some(best) = followups
+                .iter()
+                .map(|d| d.score)
+                .min_by_key(|&s| NotNan::new(s).unwrap())
+            {
+                score = score.min(best);
+            }
+        }
 
         Some(StraightDodge {
-            approach_distance: approach.distance(),
+            approach_distance: from.distance + approach.distance(),
             dodge,
             score,
         })
     }
 }
 
+/// A car's progress along the straight line to the target.
+#[derive(Copy, Clone)]
+struct Progress {
+    time: f32,
+    distance: f32,
+    speed: f32,
+    boost: f32,
+}
+
 struct StraightDodge {
     approach_distance: f32,
     dodge: CarForwardDodge1D,
     score: f32,
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        models::{CarState, CarState2D, RoutePlan, SegmentPlan},
+        plan::ground_straight::{
+            approach_then_brake, arc_approach, fastest, teammate_collision, Progress,
+            StraightDodgeCalculator,
+        },
+        segments::{Chain, ForwardDodge, Straight, StraightMode},
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point2, UnitComplex, Vector2};
+    use simulate::CarForwardDodge;
+    use std::f32::consts::PI;
+
+    fn start(speed: f32, boost: f32) -> CarState {
+        CarState2D {
+            loc: Point2::origin(),
+            rot: UnitComplex::new(PI / 2.0),
+            vel: Vector2::new(0.0, speed),
+            boost,
+        }
+        .to_3d()
+    }
+
+    #[test]
+    fn brake_to_target_end_speed() {
+        let start = start(500.0, 50.0).to_2d_assume();
+        let target_loc = Point2::new(0.0, 3000.0);
+        let (approach, brake) = approach_then_brake(&start, target_loc, 1000.0, true).unwrap();
+        assert!(approach.end().vel.norm() > 1000.0);
+        assert!((brake.end().vel.norm() - 1000.0).abs() < 1.0);
+        assert!((brake.end().loc.to_2d() - target_loc).norm() < 10.0);
+    }
+
+    #[test]
+    fn target_end_speed_out_of_reach() {
+        let start = start(0.0, 0.0).to_2d_assume();
+        let target_loc = Point2::new(0.0, 500.0);
+        assert!(approach_then_brake(&start, target_loc, 2000.0, true).is_none());
+    }
+
+    #[test]
+    fn arc_approach_ends_at_target() {
+        let start = start(1000.0, 0.0).to_2d_assume();
+        // 20° to the left of straight ahead.
+        let target_loc = Point2::new(-1000.0 * 0.342, 1000.0 * 0.940);
+        let arc = arc_approach(&start, target_loc, 500.0).unwrap();
+        assert!((arc.end().loc.to_2d() - target_loc).norm() < 1.0);
+        let radius = arc.length() / (arc.end_angle() - arc.start_angle()).abs();
+        assert!((radius - 1000.0 / (2.0 * 0.342)).abs() < 5.0);
+        // We arrive having turned through twice the heading error.
+        let end_heading = arc.end().vel.to_2d();
+        assert!((end_heading.angle_to(&Vector2::y()) + 40.0_f32.to_radians()).abs() < 0.01);
+        assert!(arc.duration() > 1.0);
+    }
+
+    #[test]
+    fn arc_approach_only_for_shallow_angles() {
+        let start = start(1000.0, 0.0).to_2d_assume();
+        // Already lined up.
+        assert!(arc_approach(&start, Point2::new(0.0, 1000.0), 500.0).is_none());
+        // Too far off to curve in gently.
+        assert!(arc_approach(&start, Point2::new(1000.0, 1000.0), 500.0).is_none());
+        // Would need a tighter turn than the car can make.
+        assert!(arc_approach(&start, Point2::new(-100.0, 250.0), 500.0).is_none());
+    }
+
+    #[test]
+    fn crossing_teammate_collides() {
+        let start = start(1000.0, 0.0);
+        let teammate = CarState2D {
+            loc: Point2::new(-1000.0, 1000.0),
+            rot: UnitComplex::identity(),
+            vel: Vector2::new(1000.0, 0.0),
+            boost: 0.0,
+        }
+        .to_3d();
+        let collision = teammate_collision(&start, Point2::new(0.0, 3000.0), &teammate).unwrap();
+        assert!(collision.x.abs() < 250.0);
+        assert!((collision.y - 1000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn teammate_behind_is_no_problem() {
+        let start = start(1000.0, 0.0);
+        let teammate = CarState2D {
+            loc: Point2::new(0.0, -1000.0),
+            rot: UnitComplex::new(PI / 2.0),
+            vel: Vector2::new(0.0, 500.0),
+            boost: 0.0,
+        }
+        .to_3d();
+        assert!(teammate_collision(&start, Point2::new(0.0, 3000.0), &teammate).is_none());
+    }
+
+    #[test]
+    fn second_dodge_improves_long_approach() {
+        let start = start(1000.0, 0.0);
+        let calc = StraightDodgeCalculator::new(start, Point2::new(0.0, 6000.0), None, 0.0);
+        let best = |dodges| {
+            let from = Progress {
+                time: 0.0,
+                distance: 0.0,
+                speed: 1000.0,
+                boost: 0.0,
+            };
+            calc.collect_from(from, dodges)
+                .into_iter()
+                .map(|d| d.score)
+                .fold(f32::INFINITY, f32::min)
+        };
+        let single = best(1);
+        let chained = best(2);
+        assert!(chained < single);
+    }
+
+    #[test]
+    fn dodge_recovery_counts_against_dodge() {
+        // With a little boost, driving is a bit slower than dodging, but not
+        // once the dodge's landing is accounted for.
+        let start = start(1000.0, 10.0);
+        let dodge = ForwardDodge::new(start.clone(), CarForwardDodge::calc_1d(1000.0), rl::GRAVITY);
+        let straight = Straight::new(
+            start.to_2d_assume(),
+            dodge.end().loc.to_2d(),
+            0.0,
+            StraightMode::Asap,
+            true,
+        );
+        assert!(dodge.duration() < straight.duration());
+
+        let plans = vec![
+            RoutePlan {
+                segment: Box::new(Chain::new(vec![Box::new(dodge)])),
+                next: None,
+            },
+            RoutePlan {
+                segment: Box::new(straight),
+                next: None,
+            },
+        ];
+        let best = fastest(plans);
+        assert_eq!(best.segment.name(), "Straight");
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use crate::{
+        behavior::higher_order::Chain,
         integration_tests::{TestRunner, TestScenario},
         routing::{
-            behavior::FollowRoute, plan::ground_straight::GroundStraightPlanner,
+            behavior::FollowRoute,
+            plan::{ground_straight::GroundStraightPlanner, KeepOut},
             segments::StraightMode,
         },
+        strategy::{Action, Behavior, Context, Priority},
     };
     use common::prelude::*;
-    use nalgebra::{Point2, Point3, Vector3};
+    use nalgebra::{Point2, Point3, Rotation3, Vector3};
+    use nameof::name_of_type;
+    use std::{
+        f32::consts::PI,
+        sync::{Arc, Mutex},
+    };
+    use vec_box::vec_box;
 
     #[test]
     fn brake_when_going_too_fast() {
@@ -444,4 +1000,158 @@ mod integration_tests {
         let loc = packet.GameCars[0].Physics.loc();
         assert!(loc.y < 1000.0);
     }
+
+    #[test]
+    fn arrive_on_time() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(1000.0, 0.0, 0.0),
+                car_vel: Vector3::new(0.0, 500.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(FollowRoute::new(
+                GroundStraightPlanner::new(Point2::new(0.0, 2000.0), StraightMode::Asap)
+                    .target_time(2.0)
+                    .arrival_tolerance(0.25),
+            ))
+            .run_for_millis(2250);
+
+        let packet = test.sniff_packet();
+        let loc = packet.GameCars[0].Physics.loc();
+        assert!(loc.y >= 1900.0);
+    }
+
+    struct RecordSpeed(Arc<Mutex<Option<f32>>>);
+
+    impl Behavior for RecordSpeed {
+        fn name(&self) -> &str {
+            name_of_type!(RecordSpeed)
+        }
+
+        fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+            let mut speed = self.0.lock().unwrap();
+            if speed.is_none() {
+                *speed = Some(ctx.me().Physics.vel_2d().norm());
+            }
+            Action::Yield(Default::default())
+        }
+    }
+
+    #[test]
+    fn arrive_at_target_end_speed() {
+        let end_speed = Arc::new(Mutex::new(None));
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(1000.0, 0.0, 0.0),
+                car_vel: Vector3::new(0.0, 500.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(Chain::new(Priority::Idle, vec_box![
+                FollowRoute::new(
+                    GroundStraightPlanner::new(Point2::new(0.0, 3000.0), StraightMode::Asap)
+                        .target_end_speed(1000.0),
+                ),
+                RecordSpeed(end_speed.clone()),
+            ]))
+            .run_for_millis(4000);
+
+        let packet = test.sniff_packet();
+        let loc = packet.GameCars[0].Physics.loc();
+        let end_speed = end_speed.lock().unwrap().unwrap();
+        println!("loc = {:?}, end_speed = {:.0}", loc, end_speed);
+        assert!((end_speed - 1000.0).abs() < 150.0);
+        assert!(loc.y >= 2500.0);
+    }
+
+    #[test]
+    fn teammates_avoid_each_other() {
+        let route = |target_loc| {
+            FollowRoute::new(
+                GroundStraightPlanner::new(target_loc, StraightMode::Asap)
+                    .allow_dodging(false)
+                    .avoid_teammates(true),
+            )
+            .replan_interval(0.2)
+        };
+        // Two routes that cross at center field, at about the same time.
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(3000.0, 3000.0, 92.74),
+                car_loc: Point3::new(-2000.0, 0.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, 0.0, 0.0),
+                enemy_loc: Point3::new(3000.0, 4000.0, 17.01),
+                ..Default::default()
+            })
+            .teammate(
+                Point3::new(0.0, -2000.0, 17.01),
+                Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                route(Point2::new(0.0, 2000.0)),
+            )
+            .behavior(route(Point2::new(2000.0, 0.0)))
+            .run();
+
+        let mut closest = f32::INFINITY;
+        for _ in 0..60 {
+            let packet = test.sniff_packet();
+            let me = packet.GameCars[0].Physics.loc_2d();
+            let teammate = packet.GameCars[2].Physics.loc_2d();
+            closest = closest.min((me - teammate).norm());
+            test.sleep_millis(50);
+        }
+
+        println!("closest = {:.0}", closest);
+        assert!(closest >= 200.0);
+    }
+
+    #[test]
+    fn fail_if_cannot_arrive_on_time() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(1000.0, 0.0, 0.0),
+                car_vel: Vector3::new(0.0, 0.0, 0.0),
+                boost: 0,
+                ..Default::default()
+            })
+            .behavior(FollowRoute::new(
+                GroundStraightPlanner::new(Point2::new(0.0, 4000.0), StraightMode::Asap)
+                    .target_time(1.0)
+                    .arrival_tolerance(0.25),
+            ))
+            .run_for_millis(1000);
+
+        // The route can't be planned, so we should never have started moving.
+        let packet = test.sniff_packet();
+        let loc = packet.GameCars[0].Physics.loc();
+        assert!(loc.y < 500.0);
+    }
+
+    #[test]
+    fn route_around_keep_out_box() {
+        let region = KeepOut::rect(Point2::new(-300.0, 800.0), Point2::new(300.0, 1200.0));
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(3000.0, 0.0, 92.74),
+                car_vel: Vector3::new(0.0, 500.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(FollowRoute::new(
+                GroundStraightPlanner::new(Point2::new(0.0, 2500.0), StraightMode::Asap)
+                    .keep_out(region.clone()),
+            ))
+            .run();
+
+        let mut trespassed = false;
+        for _ in 0..40 {
+            let loc = test.sniff_packet().GameCars[0].Physics.loc_2d();
+            if region.contains(loc) {
+                trespassed = true;
+            }
+            test.sleep_millis(100);
+        }
+
+        let packet = test.sniff_packet();
+        let loc = packet.GameCars[0].Physics.loc_2d();
+        assert!(!trespassed);
+        assert!((loc - Point2::new(0.0, 2500.0)).norm() < 500.0);
+    }
 }
@@ -5,7 +5,8 @@ use crate::{
             RoutePlanner,
         },
         plan::{
-            ground_powerslide::GroundSimplePowerslideTurn, higher_order::ChainedPlanner, pathing,
+            ground_powerslide::GroundSimplePowerslideTurn, half_flip::HalfFlipPlanner,
+            higher_order::ChainedPlanner, pathing,
         },
         recover::{IsSkidding, NotOnFlatGround},
         segments::{NullSegment, SimpleArc, Turn},
@@ -27,6 +28,8 @@ pub struct TurnPlanner {
     target_face: Point2<f32>,
     next: Option<Box<dyn RoutePlanner>>,
     reverse_angle_hint: Option<Unit<Vector2<f32>>>,
+    prefer_arc: bool,
+    allow_half_flip: bool,
 }
 
 impl TurnPlanner {
@@ -35,6 +38,8 @@ impl TurnPlanner {
             target_face,
             next,
             reverse_angle_hint: None,
+            prefer_arc: false,
+            allow_half_flip: false,
         }
     }
 
@@ -42,6 +47,43 @@ impl TurnPlanner {
         self.reverse_angle_hint = Some(reverse_angle_hint);
         self
     }
+
+    /// Plan the turn as a constant-speed `SimpleArc` when possible, instead of
+    /// a `Turn`. This is off by default. It falls back to the usual planning if
+    /// an arc doesn't work out (e.g., if we're moving too slowly).
+    pub fn prefer_arc(mut self, prefer_arc: bool) -> Self {
+        self.prefer_arc = prefer_arc;
+        self
+    }
+
+    /// If the target is well behind us, half flip to face it instead of
+    /// turning all the way around. This falls back to the usual planning if a
+    /// half flip doesn't fit (e.g., if we're driving away too fast).
+    pub fn allow_half_flip(mut self, allow_half_flip: bool) -> Self {
+        self.allow_half_flip = allow_half_flip;
+        self
+    }
+
+    fn plan_turn(
+        &self,
+        pathing_unaware_planner: &PathingUnawareTurnPlanner,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        if self.allow_half_flip {
+            match HalfFlipPlanner::new(self.target_face).plan(ctx, dump) {
+                Ok(plan) => return Ok(plan),
+                Err(err) => dump.log(self, format!("half flip failed: {:?}", err)),
+            }
+        }
+        if self.prefer_arc {
+            match ArcTowards::new(self.target_face, None).plan(ctx, dump) {
+                Ok(plan) => return Ok(plan),
+                Err(err) => dump.log(self, format!("arc failed: {:?}", err)),
+            }
+        }
+        pathing_unaware_planner.plan(ctx, dump)
+    }
 }
 
 impl RoutePlanner for TurnPlanner {
@@ -59,7 +101,7 @@ impl RoutePlanner for TurnPlanner {
 
         let pathing_unaware_planner =
             PathingUnawareTurnPlanner::new(self.target_face, self.reverse_angle_hint);
-        let turn = pathing_unaware_planner.plan(ctx, dump)?;
+        let turn = self.plan_turn(&pathing_unaware_planner, ctx, dump)?;
         dump.log_plan(self, &turn);
         let plan =
             match pathing::avoid_plowing_into_goal_wall(&turn.segment.end(), self.target_face) {
@@ -204,7 +246,6 @@ impl RoutePlanner for SimpleTurnPlanner {
     }
 }
 
-#[allow(dead_code)] // This basically works but I can't make use of it just yet.
 #[derive(Clone, new)]
 struct ArcTowards {
     target_loc: Point2<f32>,
@@ -0,0 +1,159 @@
+use crate::routing::{
+    models::{CarState, PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner},
+    plan::ground_turn::PathingUnawareTurnPlanner,
+    recover::{IsSkidding, NotOnFlatGround},
+    segments::HalfFlip,
+};
+use common::prelude::*;
+use derive_new::new;
+use nalgebra::Point2;
+use nameof::name_of_type;
+use std::f32::consts::PI;
+
+/// Turn around with a half flip when the target is well behind us. Any angle
+/// left over after landing is cleaned up with an ordinary turn.
+#[derive(Clone, new)]
+pub struct HalfFlipPlanner {
+    target_face: Point2<f32>,
+}
+
+impl HalfFlipPlanner {
+    /// Only flip if the target is at least this far around from our nose.
+    /// Anything less is quicker to just turn.
+    const MIN_ANGLE: f32 = PI * 3.0 / 4.0;
+
+    /// If we're driving forwards faster than this, the dodge can't cancel out
+    /// our momentum, and we'd land still sliding away from the target.
+    const MAX_FORWARD_SPEED: f32 = HalfFlip::IMPULSE;
+}
+
+impl RoutePlanner for HalfFlipPlanner {
+    fn name(&self) -> &'static str {
+        name_of_type!(HalfFlipPlanner)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+        dump.log_pretty(self, "target_face", self.target_face);
+
+        guard!(
+            ctx.start,
+            NotOnFlatGround,
+            RoutePlanError::MustBeOnFlatGround,
+        );
+        guard!(ctx.start, IsSkidding, RoutePlanError::MustNotBeSkidding {
+            recover_target_loc: self.target_face,
+        });
+        check_half_flip(&ctx.start, self.target_face)?;
+
+        Ok(RoutePlan {
+            segment: Box::new(HalfFlip::new(ctx.start.clone(), ctx.game.gravity())),
+            next: Some(Box::new(PathingUnawareTurnPlanner::new(
+                self.target_face,
+                None,
+            ))),
+        })
+    }
+}
+
+fn check_half_flip(start: &CarState, target_face: Point2<f32>) -> Result<(), RoutePlanError> {
+    let forward = start.forward_axis_2d();
+    let angle = forward.angle_to(&(target_face - start.loc.to_2d()));
+    if angle.abs() < HalfFlipPlanner::MIN_ANGLE {
+        return Err(RoutePlanError::OtherError("target is not behind us"));
+    }
+
+    let forward_speed = start.vel.to_2d().dot(&forward);
+    if forward_speed > HalfFlipPlanner::MAX_FORWARD_SPEED {
+        return Err(RoutePlanError::MovingTooFast);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{models::CarState2D, plan::half_flip::check_half_flip};
+    use nalgebra::{Point2, UnitComplex, Vector2};
+
+    fn start(speed: f32) -> CarState2D {
+        CarState2D {
+            loc: Point2::origin(),
+            rot: UnitComplex::identity(),
+            vel: Vector2::new(speed, 0.0),
+            boost: 0.0,
+        }
+    }
+
+    #[test]
+    fn flip_when_target_is_behind() {
+        let start = start(0.0).to_3d();
+        assert!(check_half_flip(&start, Point2::new(-1000.0, 100.0)).is_ok());
+        assert!(check_half_flip(&start, Point2::new(-1000.0, -900.0)).is_ok());
+    }
+
+    #[test]
+    fn turn_instead_when_target_is_beside() {
+        let start = start(0.0).to_3d();
+        assert!(check_half_flip(&start, Point2::new(0.0, 1000.0)).is_err());
+        assert!(check_half_flip(&start, Point2::new(-500.0, 1000.0)).is_err());
+    }
+
+    #[test]
+    fn not_while_driving_forward_fast() {
+        let target = Point2::new(-1000.0, 0.0);
+        assert!(check_half_flip(&start(-1000.0).to_3d(), target).is_ok());
+        assert!(check_half_flip(&start(1000.0).to_3d(), target).is_err());
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        integration_tests::{TestRunner, TestScenario},
+        routing::{
+            behavior::FollowRoute,
+            plan::{GroundStraightPlanner, TurnPlanner},
+            segments::StraightMode,
+        },
+    };
+    use common::prelude::*;
+    use nalgebra::{Point2, Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn half_flip_towards_target_behind() {
+        let target_loc = Point2::new(0.0, 3000.0);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                car_loc: Point3::new(0.0, 0.0, 17.01),
+                // Facing directly away from the target.
+                car_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, 0.0),
+                car_vel: Vector3::new(0.0, 0.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(FollowRoute::new(
+                TurnPlanner::new(
+                    target_loc,
+                    Some(Box::new(GroundStraightPlanner::new(
+                        target_loc,
+                        StraightMode::Asap,
+                    ))),
+                )
+                .allow_half_flip(true),
+            ))
+            .run_for_millis(1500);
+
+        let packet = test.sniff_packet();
+        let car = &packet.GameCars[0];
+        println!("forward = {:?}", car.Physics.forward_axis());
+        println!("vel = {:?}", car.Physics.vel());
+        assert!(car.OnGround);
+        assert!(car.Physics.forward_axis().y >= 0.9);
+        assert!(car.Physics.vel().y >= 500.0);
+    }
+}
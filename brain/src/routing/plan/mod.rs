@@ -1,7 +1,7 @@
 pub use self::{
     boost::GetDollar, ground_drive::GroundDrive, ground_intercept::GroundIntercept,
     ground_straight::GroundStraightPlanner, ground_turn::TurnPlanner, higher_order::ChainedPlanner,
-    pathing::avoid_goal_wall_waypoint, wall_intercept::WallIntercept,
+    pathing::{avoid_goal_wall_waypoint, KeepOut}, wall_intercept::WallIntercept,
 };
 
 macro_rules! guard {
@@ -19,6 +19,7 @@ mod ground_jump_and_dodge;
 mod ground_powerslide;
 mod ground_straight;
 mod ground_turn;
+mod half_flip;
 mod higher_order;
 mod pathing;
 mod wall_intercept;
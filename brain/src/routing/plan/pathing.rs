@@ -7,7 +7,9 @@ use crate::routing::{
     segments::StraightMode,
 };
 use common::{physics, prelude::*, rl};
-use nalgebra::Point2;
+use nalgebra::{Point2, Vector2};
+use ordered_float::NotNan;
+use simulate::linear_interpolate;
 
 /// Calculate whether driving straight to `target_loc` would intersect the goal
 /// wall. If so, return the route we should follow to get outside the goal.
@@ -51,9 +53,16 @@ pub fn avoid_goal_wall_waypoint(start: &CarState, target_loc: Point2<f32>) -> Op
     }
 
     let brink = (rl::FIELD_MAX_Y - 50.0) * start.loc.y.signum();
-    let ray = physics::car_forward_axis_2d(start.rot.to_2d());
+    let ray = travel_direction(start);
     let toi = (brink - start.loc.y) / ray.y;
-    let cross_x = start.loc.x + toi * ray.x;
+    // If we'd only cross the brink behind us (or right where we are), we're
+    // headed out of the goal already. Judge by where we are now, rather than
+    // sending ourselves back to a waypoint we've already passed.
+    let cross_x = if toi >= 1.0 {
+        start.loc.x + toi * ray.x
+    } else {
+        start.loc.x
+    };
     if cross_x.abs() >= rl::GOALPOST_X - margin {
         Some(Point2::new(
             (rl::GOALPOST_X - margin) * cross_x.signum(),
@@ -63,3 +72,226 @@ pub fn avoid_goal_wall_waypoint(start: &CarState, target_loc: Point2<f32>) -> Op
         None
     }
 }
+
+/// The direction the car is going to cross the goal line in. At speed, the car
+/// keeps going where it's moving no matter where the nose points, so lean
+/// towards the velocity.
+fn travel_direction(start: &CarState) -> Vector2<f32> {
+    let heading = physics::car_forward_axis_2d(start.rot.to_2d()).into_inner();
+    let vel = start.vel.to_2d();
+    let speed = vel.norm();
+    if speed < 1.0 {
+        return heading;
+    }
+    let weight = linear_interpolate(&[500.0, 1500.0], &[0.0, 1.0], speed);
+    let blend = heading * (1.0 - weight) + vel / speed * weight;
+    // If we're reversing at just the right speed, the two cancel out.
+    if blend.norm() < 0.1 {
+        return vel / speed;
+    }
+    blend.normalize()
+}
+
+/// A convex region of the field that routes should steer clear of (e.g., a
+/// goalpost, or a teammate parked in the way).
+#[derive(Clone, Debug)]
+pub struct KeepOut {
+    /// The corners of the region, in counter-clockwise order.
+    vertices: Vec<Point2<f32>>,
+}
+
+impl KeepOut {
+    /// Create a region from the corners of a convex polygon, in
+    /// counter-clockwise order.
+    pub fn polygon(vertices: Vec<Point2<f32>>) -> Self {
+        assert!(vertices.len() >= 3);
+        Self { vertices }
+    }
+
+    /// Create an axis-aligned box with the given opposite corners.
+    pub fn rect(min: Point2<f32>, max: Point2<f32>) -> Self {
+        assert!(min.x < max.x && min.y < max.y);
+        Self::polygon(vec![
+            min,
+            Point2::new(max.x, min.y),
+            max,
+            Point2::new(min.x, max.y),
+        ])
+    }
+
+    pub fn contains(&self, point: Point2<f32>) -> bool {
+        self.edges()
+            .all(|(p, normal)| normal.dot(&(point - p)) < 0.0)
+    }
+
+    /// Returns true if the segment from `a` to `b` passes through the
+    /// interior of the region. Merely grazing an edge or a corner doesn't
+    /// count.
+    pub fn intersects_segment(&self, a: Point2<f32>, b: Point2<f32>) -> bool {
+        // Clip the segment against each edge's half-plane in turn (this is the
+        // Cyrus-Beck algorithm).
+        let dir = b - a;
+        let mut enter = 0.0_f32;
+        let mut exit = 1.0_f32;
+        for (p, normal) in self.edges() {
+            let dist = normal.dot(&(a - p));
+            let rate = normal.dot(&dir);
+            if rate == 0.0 {
+                if dist >= 0.0 {
+                    return false;
+                }
+                continue;
+            }
+            let t = -dist / rate;
+            if rate < 0.0 {
+                enter = enter.max(t);
+            } else {
+                exit = exit.min(t);
+            }
+        }
+        enter < exit
+    }
+
+    /// If driving straight from `start` to `target` would cut through the
+    /// region, returns a waypoint just past one of its corners that we can
+    /// drive to first instead. The waypoint is `margin` units clear of the
+    /// corner, and is chosen to make the total trip as short as possible.
+    pub fn detour_waypoint(
+        &self,
+        start: Point2<f32>,
+        target: Point2<f32>,
+        margin: f32,
+    ) -> Option<Point2<f32>> {
+        if !self.intersects_segment(start, target) {
+            return None;
+        }
+
+        let centroid = self.centroid();
+        self.vertices
+            .iter()
+            .map(|&v| v + (v - centroid).normalize() * margin)
+            .filter(|&w| !self.intersects_segment(start, w))
+            .min_by_key(|&w| NotNan::new((w - start).norm() + (target - w).norm()).unwrap())
+    }
+
+    fn centroid(&self) -> Point2<f32> {
+        let sum = self
+            .vertices
+            .iter()
+            .fold(Vector2::zeros(), |acc, v| acc + v.coords);
+        Point2::from(sum / self.vertices.len() as f32)
+    }
+
+    /// Iterate over the edges as `(start_point, outward_normal)` pairs.
+    fn edges<'a>(&'a self) -> impl Iterator<Item = (Point2<f32>, Vector2<f32>)> + 'a {
+        let n = self.vertices.len();
+        (0..n).map(move |i| {
+            let p = self.vertices[i];
+            let q = self.vertices[(i + 1) % n];
+            let edge = q - p;
+            (p, Vector2::new(edge.y, -edge.x))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        models::CarState2D,
+        plan::pathing::{avoid_goal_wall_waypoint, KeepOut},
+    };
+    use nalgebra::{Point2, UnitComplex, Vector2};
+
+    fn unit_box() -> KeepOut {
+        KeepOut::rect(Point2::new(-100.0, -100.0), Point2::new(100.0, 100.0))
+    }
+
+    #[test]
+    fn contains() {
+        let region = unit_box();
+        assert!(region.contains(Point2::origin()));
+        assert!(!region.contains(Point2::new(150.0, 0.0)));
+    }
+
+    #[test]
+    fn intersects_segment() {
+        let region = unit_box();
+        assert!(region.intersects_segment(Point2::new(-500.0, 0.0), Point2::new(500.0, 0.0)));
+        assert!(region.intersects_segment(Point2::new(-500.0, 0.0), Point2::new(0.0, 0.0)));
+        assert!(!region.intersects_segment(Point2::new(-500.0, 0.0), Point2::new(-200.0, 0.0)));
+        assert!(!region.intersects_segment(Point2::new(-500.0, 200.0), Point2::new(500.0, 200.0)));
+        // Grazing an edge doesn't count.
+        assert!(!region.intersects_segment(Point2::new(-500.0, 100.0), Point2::new(500.0, 100.0)));
+    }
+
+    #[test]
+    fn detour_around_box() {
+        let region = unit_box();
+        let start = Point2::new(-500.0, 10.0);
+        let target = Point2::new(500.0, 10.0);
+        let waypoint = region.detour_waypoint(start, target, 50.0).unwrap();
+        // Go around the near corner on the side we're already leaning towards.
+        assert!(waypoint.x < 0.0);
+        assert!(waypoint.y > 100.0);
+        assert!(!region.intersects_segment(start, waypoint));
+    }
+
+    #[test]
+    fn no_detour_when_clear() {
+        let region = unit_box();
+        let start = Point2::new(-500.0, 300.0);
+        let target = Point2::new(500.0, 300.0);
+        assert_eq!(region.detour_waypoint(start, target, 50.0), None);
+    }
+
+    fn car(loc: Point2<f32>, heading: Vector2<f32>, vel: Vector2<f32>) -> CarState2D {
+        CarState2D {
+            loc,
+            rot: UnitComplex::rotation_between(&Vector2::x(), &heading),
+            vel,
+            boost: 0.0,
+        }
+    }
+
+    #[test]
+    fn goal_wall_waypoint_when_angling_into_the_post() {
+        let start = car(
+            Point2::new(0.0, 4000.0),
+            Vector2::new(0.7, 0.714),
+            Vector2::zeros(),
+        );
+        let waypoint = avoid_goal_wall_waypoint(&start.to_3d(), Point2::new(0.0, 5500.0));
+        let waypoint = waypoint.unwrap();
+        assert!(waypoint.x > 0.0);
+        assert!(waypoint.y < 5120.0);
+    }
+
+    #[test]
+    fn inside_goal_routes_out() {
+        // Partway inside the goal, nose pointed along the back of the net, but
+        // sliding out towards the field. The heading alone crosses the goal line
+        // behind us, past the post.
+        let loc = Point2::new(500.0, 5300.0);
+        let heading = Vector2::new(-1.0, 0.2);
+        let target = Point2::new(0.0, 0.0);
+
+        let parked = car(loc, heading, Vector2::zeros());
+        assert_eq!(avoid_goal_wall_waypoint(&parked.to_3d(), target), None);
+
+        let sliding = car(loc, heading, Vector2::new(0.0, -1500.0));
+        assert_eq!(avoid_goal_wall_waypoint(&sliding.to_3d(), target), None);
+    }
+
+    #[test]
+    fn goal_wall_uses_velocity_at_speed() {
+        // Pointed straight at the middle of the net, but drifting hard towards
+        // the post.
+        let start = car(
+            Point2::new(0.0, 4000.0),
+            Vector2::new(0.0, 1.0),
+            Vector2::new(1400.0, 1400.0),
+        );
+        let waypoint = avoid_goal_wall_waypoint(&start.to_3d(), Point2::new(0.0, 5500.0));
+        assert!(waypoint.is_some());
+    }
+}
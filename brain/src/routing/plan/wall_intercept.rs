@@ -26,6 +26,7 @@ pub struct WallIntercept {
     must_be_side_wall: bool,
     forbid_goal_walls: bool,
     must_be_vanilla_safe_offensive: bool,
+    max_ball_z: Option<f32>,
 }
 
 impl WallIntercept {
@@ -35,6 +36,7 @@ impl WallIntercept {
             must_be_side_wall: false,
             forbid_goal_walls: false,
             must_be_vanilla_safe_offensive: true,
+            max_ball_z: None,
         }
     }
 
@@ -52,6 +54,14 @@ impl WallIntercept {
         self.forbid_goal_walls = forbid_goal_walls;
         self
     }
+
+    /// Skip over intercepts where the ball is higher than `max_ball_z`. Past a
+    /// certain height, we'd have to drive so far up the wall that we'd fall
+    /// off before we could jump.
+    pub fn max_ball_z(mut self, max_ball_z: f32) -> Self {
+        self.max_ball_z = Some(max_ball_z);
+        self
+    }
 }
 
 impl RoutePlanner for WallIntercept {
@@ -76,24 +86,21 @@ impl RoutePlanner for WallIntercept {
 }
 
 impl WallIntercept {
-    pub fn calc_intercept<'ball>(
-        &self,
-        ctx: &Context2<'_, 'ball>,
-    ) -> Result<&'ball BallFrame, &'static str> {
+    pub fn calc_intercept(&self, ctx: &Context2<'_, '_>) -> Result<BallFrame, &'static str> {
         match self.calc_plan(&PlanningContext::from_context(ctx)) {
             Ok((ball, _plan)) => Ok(ball),
             Err(reason) => Err(reason),
         }
     }
 
-    fn calc_plan<'ball>(
+    fn calc_plan(
         &self,
-        ctx: &PlanningContext<'_, 'ball>,
-    ) -> Result<(&'ball BallFrame, RoutePlan), &'static str> {
+        ctx: &PlanningContext<'_, '_>,
+    ) -> Result<(BallFrame, RoutePlan), &'static str> {
         let mut balls = ctx.ball_prediction.iter();
         let mut fail_reason = None;
         while let Some(ball) = balls.next() {
-            match self.eval_intercept(ctx, ball) {
+            match self.eval_intercept(ctx, &ball) {
                 Ok(plan) => return Ok((ball, plan)),
                 Err((skip, reason)) => {
                     if skip == Skip::Yes {
@@ -135,7 +142,11 @@ impl WallIntercept {
             Ok((plan, _log)) => plan,
             Err(_) => return Err((Skip::Yes, None)),
         };
-        let tail = match plan.provisional_expand_2(ctx.game, ctx.ball_prediction) {
+        let tail = match plan.provisional_expand_2(
+            ctx.game,
+            ctx.ball_prediction.clone(),
+            ctx.start_time,
+        ) {
             Ok(tail) => tail,
             Err(_) => return Err((Skip::Yes, None)),
         };
@@ -175,6 +186,8 @@ impl WallIntercept {
         car_loc: Point3<f32>,
         intercept_loc: Point3<f32>,
     ) -> Result<(), &'static str> {
+        self.check_height(intercept_loc)?;
+
         if self.must_be_vanilla_safe_offensive
             && !Self::is_vanilla_safe_conservative(game, car_loc, intercept_loc)
         {
@@ -196,6 +209,13 @@ impl WallIntercept {
         Ok(())
     }
 
+    fn check_height(&self, intercept_loc: Point3<f32>) -> Result<(), &'static str> {
+        match self.max_ball_z {
+            Some(max_ball_z) if intercept_loc.z > max_ball_z => Err("too high to reach"),
+            _ => Ok(()),
+        }
+    }
+
     fn is_vanilla_safe_conservative(
         game: &Game<'_>,
         car_loc: Point3<f32>,
@@ -214,7 +234,7 @@ enum Skip {
     Yes,
 }
 
-fn skip_time<'a>(xs: &mut impl Iterator<Item = &'a BallFrame>, time: f32) {
+fn skip_time(xs: &mut impl Iterator<Item = BallFrame>, time: f32) {
     let x = some_or_else!(xs.next(), {
         return;
     });
@@ -222,3 +242,28 @@ fn skip_time<'a>(xs: &mut impl Iterator<Item = &'a BallFrame>, time: f32) {
     assert!(num >= 1);
     for _ in xs.take(num as usize - 1) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::plan::WallIntercept;
+    use nalgebra::Point3;
+
+    #[test]
+    fn decline_high_wall_ball() {
+        let planner = WallIntercept::new().max_ball_z(1400.0);
+        assert!(planner
+            .check_height(Point3::new(4000.0, 0.0, 1000.0))
+            .is_ok());
+        assert!(planner
+            .check_height(Point3::new(4000.0, 0.0, 1800.0))
+            .is_err());
+    }
+
+    #[test]
+    fn no_height_limit_by_default() {
+        let planner = WallIntercept::new();
+        assert!(planner
+            .check_height(Point3::new(4000.0, 0.0, 1800.0))
+            .is_ok());
+    }
+}
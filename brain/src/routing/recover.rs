@@ -1,10 +1,12 @@
 use crate::{
     behavior::{
         higher_order::{Chain, Predicate, TimeLimit, TryChoose},
-        movement::{DriveTowards, GetToFlatGround, QuickJumpAndDodge, SkidRecover, Yielder},
+        movement::{
+            DriveTowards, GetToFlatGround, LandSmoothly, QuickJumpAndDodge, SkidRecover, Yielder,
+        },
         offense::ResetBehindBall,
     },
-    helpers::ball::BallTrajectory,
+    helpers::ball::BallFrame,
     routing::{
         behavior::FollowRoute,
         models::{CarState, RoutePlanError},
@@ -41,7 +43,10 @@ impl RoutePlanError {
 
                 let ball_loc = ctx.scenario.ball_prediction().at_time_or_last(2.5).loc;
                 let mut choices = Vec::<Box<dyn Behavior>>::new();
-                if !is_ball_directly_behind_car(ctx.scenario.ball_prediction(), &ctx.me().into()) {
+                if !is_ball_directly_behind_car(
+                    ctx.scenario.ball_prediction().start(),
+                    &ctx.me().into(),
+                ) {
                     choices.push(Box::new(
                         FollowRoute::new(
                             GroundDrive::new(ball_loc.to_2d())
@@ -56,7 +61,7 @@ impl RoutePlanError {
                     ResetBehindBall::behind_loc(ball_loc.to_2d(), 1600.0).never_recover(true),
                 ));
                 // What's going on? Last ditch effort, try to turn with a ReliefBot-style hop.
-                choices.push(Box::new(confused_jump_to_reorient()));
+                choices.push(Box::new(confused_jump_to_reorient(ball_loc.to_2d())));
                 Some(Box::new(TryChoose::new(Priority::Idle, choices)))
             }
             RoutePlanError::MustBeFacingTarget => {
@@ -76,6 +81,7 @@ impl RoutePlanError {
             | RoutePlanError::CannotOperateWall
             | RoutePlanError::NoWallIntercept
             | RoutePlanError::TurnAngleTooLarge
+            | RoutePlanError::CannotArriveOnTime
             | RoutePlanError::OtherError(_) => None,
         }
     }
@@ -108,32 +114,30 @@ fn check_easy_flip_recover(ctx: &mut Context<'_>) -> Option<Box<dyn Behavior>> {
     None
 }
 
-pub fn is_ball_directly_behind_car(ball: &BallTrajectory, start: &CarState) -> bool {
-    let ball_loc = ball.at_time(0.0).unwrap().loc;
-    let car_to_ball = ball_loc.to_2d() - start.loc_2d();
+pub fn is_ball_directly_behind_car(ball: &BallFrame, start: &CarState) -> bool {
+    let car_to_ball = ball.loc.to_2d() - start.loc_2d();
     start.forward_axis_2d().angle_to(&car_to_ball).abs() >= 150.0f32.to_radians()
         && car_to_ball.norm() < 750.0
 }
 
 // Yeah, this isn't great, but it's better than getting caught in an infinite
-// loop.
-pub fn confused_jump_to_reorient() -> impl Behavior {
-    Chain::new(Priority::Idle, vec_box![Yielder::new(
-        0.1,
-        common::halfway_house::PlayerInput {
+// loop. If we come down with a flip to spare, wavedash towards `target_loc`
+// to make the most of the hop.
+pub fn confused_jump_to_reorient(target_loc: Point2<f32>) -> impl Behavior {
+    Chain::new(Priority::Idle, vec_box![
+        Yielder::new(0.1, common::halfway_house::PlayerInput {
             Jump: true,
             ..Default::default()
-        }
-    )])
+        }),
+        LandSmoothly::new().towards(target_loc),
+    ])
 }
 
 pub struct NotOnFlatGround;
 
 impl NotOnFlatGround {
     pub fn evaluate(&mut self, state: &CarState) -> bool {
-        let rot = state.forward_axis();
-        let rot_flat = rot.to_2d().to_3d();
-        rot.angle(&rot_flat) >= 15.0_f32.to_radians()
+        !state.on_flat_ground()
     }
 }
 
@@ -3,6 +3,7 @@ use crate::{
     strategy::Context,
 };
 use derive_new::new;
+use nalgebra::Point3;
 use nameof::name_of_type;
 use std::collections::VecDeque;
 
@@ -28,6 +29,25 @@ impl SegmentPlan for Chain {
         self.segments.iter().map(|s| s.duration()).sum()
     }
 
+    fn position_at(&self, t: f32) -> Point3<f32> {
+        let mut t = t;
+        for segment in &self.segments {
+            if t <= segment.duration() {
+                return segment.position_at(t);
+            }
+            t -= segment.duration();
+        }
+        self.end().loc
+    }
+
+    fn recovery_time(&self) -> f32 {
+        self.segments.last().unwrap().recovery_time()
+    }
+
+    fn interruptible(&self) -> bool {
+        self.segments.iter().all(|s| s.interruptible())
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(Chainer::new(
             self.segments.iter().map(|s| s.run()).collect(),
@@ -67,3 +87,45 @@ impl SegmentRunner for Chainer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        models::{CarState2D, SegmentPlan},
+        segments::{Chain, NullSegment, SimpleArc},
+    };
+    use nalgebra::{Point2, UnitComplex, Vector2};
+
+    #[test]
+    fn position_at() {
+        let start = CarState2D {
+            loc: Point2::new(1000.0, 0.0),
+            rot: UnitComplex::identity(),
+            vel: Vector2::new(0.0, 1000.0),
+            boost: 0.0,
+        };
+        let arc = SimpleArc::new(
+            Point2::origin(),
+            1000.0,
+            start.loc,
+            start.vel,
+            start.boost,
+            Point2::new(0.0, 1000.0),
+        )
+        .ok()
+        .unwrap();
+        let chain = Chain::new(vec![Box::new(NullSegment::new(start.to_3d())), Box::new(arc)]);
+
+        let begin = chain.position_at(0.0);
+        assert!((begin.x - 1000.0).abs() < 1.0);
+        assert!(begin.y.abs() < 1.0);
+
+        let middle = chain.position_at(chain.duration() / 2.0).coords.xy();
+        assert!((middle.norm() - 1000.0).abs() < 1.0);
+        assert!((middle.x - middle.y).abs() < 1.0);
+
+        let end = chain.position_at(chain.duration());
+        assert!(end.x.abs() < 1.0);
+        assert!((end.y - 1000.0).abs() < 1.0);
+    }
+}
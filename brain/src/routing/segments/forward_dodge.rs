@@ -1,21 +1,36 @@
 use crate::{
     behavior::{
         higher_order::Chain,
-        movement::{Dodge, Yielder},
+        movement::{Dodge, GetToFlatGround, Yielder},
     },
     eeg::{color, Drawable},
     routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
     strategy::{Action, Behavior, Context, Priority},
 };
-use common::prelude::*;
+use common::{prelude::*, rl};
 use derive_new::new;
+use nalgebra::UnitComplex;
 use nameof::name_of_type;
-use simulate::CarForwardDodge1D;
+use simulate::{CarForwardDodge, CarForwardDodge1D};
 
 #[derive(Clone, new)]
 pub struct ForwardDodge {
     start: CarState,
     dodge: CarForwardDodge1D,
+    /// The match's gravity, for estimating the landing.
+    gravity: f32,
+    /// The direction of the dodge, relative to the car's nose.
+    #[new(value = "UnitComplex::identity()")]
+    angle: UnitComplex<f32>,
+}
+
+impl ForwardDodge {
+    /// Dodge at an angle instead of straight ahead, e.g., to correct course
+    /// slightly. 0° means straight forward.
+    pub fn angle(mut self, angle: UnitComplex<f32>) -> Self {
+        self.angle = angle;
+        self
+    }
 }
 
 impl SegmentPlan for ForwardDodge {
@@ -30,11 +45,17 @@ impl SegmentPlan for ForwardDodge {
     fn end(&self) -> CarState {
         assert!((self.start.vel.norm() - self.dodge.start_speed).abs() < 1.0);
         assert!(self.dodge.end_speed >= self.dodge.start_speed);
-        let forward_axis = self.start.forward_axis_2d().into_inner();
-        let vel =
-            self.start.vel.to_2d() + forward_axis * (self.dodge.end_speed - self.dodge.start_speed);
+        let start_vel = self.start.vel.to_2d();
+        let dodge_dir = self.angle * self.start.forward_axis_2d();
+        let mut vel = start_vel + dodge_dir.into_inner() * CarForwardDodge::IMPULSE;
+        if vel.norm() > rl::CAR_MAX_SPEED {
+            vel = vel.normalize() * rl::CAR_MAX_SPEED;
+        }
+        let before_dodge = self.dodge.jump_duration + self.dodge.wait_duration;
         CarState2D {
-            loc: self.start.loc.to_2d() + vel.normalize() * self.dodge.end_dist,
+            loc: self.start.loc.to_2d()
+                + start_vel * before_dodge
+                + vel * self.dodge.dodge_duration,
             rot: self.start.rot.to_2d(),
             vel,
             boost: self.start.boost,
@@ -46,6 +67,14 @@ impl SegmentPlan for ForwardDodge {
         self.dodge.duration()
     }
 
+    fn recovery_time(&self) -> f32 {
+        GetToFlatGround::estimated_recovery_time(&self.end(), true, self.gravity)
+    }
+
+    fn interruptible(&self) -> bool {
+        false
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(ForwardDodgeRunner::new(self.clone()))
     }
@@ -79,11 +108,7 @@ impl ForwardDodgeRunner {
                     ..Default::default()
                 },
             )),
-            Box::new(
-                Dodge::new()
-                    .towards(plan.end().loc.to_2d())
-                    .follow_through_time(0.0),
-            ),
+            Box::new(Dodge::new().angle(plan.angle).follow_through_time(0.0)),
             Box::new(Yielder::new(
                 plan.dodge.dodge_duration - 6.0 / 120.0,
                 common::halfway_house::PlayerInput {
@@ -116,6 +141,7 @@ mod tests {
     use super::*;
     use nalgebra::{Point2, UnitComplex, Vector2};
     use simulate::CarForwardDodge;
+    use std::f32::consts::PI;
 
     #[test]
     fn zero_vel() {
@@ -127,9 +153,46 @@ mod tests {
         }
         .to_3d();
         let dodge = CarForwardDodge::calc_1d(0.0);
-        let segment = ForwardDodge::new(start, dodge);
+        let segment = ForwardDodge::new(start, dodge, rl::GRAVITY);
         let end = segment.end();
         assert!(end.loc.x >= 500.0);
         assert_eq!(end.vel.x, 500.0);
     }
+
+    #[test]
+    fn angled() {
+        let start = CarState2D {
+            loc: Point2::origin(),
+            rot: UnitComplex::identity(),
+            vel: Vector2::zeros(),
+            boost: 0.0,
+        }
+        .to_3d();
+        let angle = UnitComplex::new(PI / 4.0);
+        let segment =
+            ForwardDodge::new(start, CarForwardDodge::calc_1d(0.0), rl::GRAVITY).angle(angle);
+        let end = segment.end();
+        let vel = end.vel.to_2d();
+        assert!((vel.norm() - 500.0).abs() < 1.0);
+        assert!((Vector2::x().angle_to(&vel) - PI / 4.0).abs() < 1e-3);
+        assert!((Vector2::x().angle_to(&end.loc.to_2d().coords) - PI / 4.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn angled_while_moving() {
+        // The dodge's sideways impulse bends our path, but doesn't undo the
+        // speed we already had.
+        let start = CarState2D {
+            loc: Point2::origin(),
+            rot: UnitComplex::identity(),
+            vel: Vector2::new(1000.0, 0.0),
+            boost: 0.0,
+        }
+        .to_3d();
+        let segment = ForwardDodge::new(start, CarForwardDodge::calc_1d(1000.0), rl::GRAVITY)
+            .angle(UnitComplex::new(PI / 2.0));
+        let vel = segment.end().vel.to_2d();
+        assert!((vel.x - 1000.0).abs() < 1.0);
+        assert!((vel.y - 500.0).abs() < 1.0);
+    }
 }
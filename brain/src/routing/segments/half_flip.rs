@@ -0,0 +1,147 @@
+use crate::{
+    behavior::movement::{self, GetToFlatGround},
+    eeg::{color, Drawable},
+    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+    strategy::{Action, Behavior, Context},
+};
+use common::{prelude::*, rl};
+use derive_new::new;
+use nalgebra::UnitComplex;
+use nameof::name_of_type;
+use simulate::CarForwardDodge;
+use std::f32::consts::PI;
+
+/// Reverse direction in a hurry: jump, dodge backwards, cancel the flip
+/// halfway through, and roll upright, landing facing the other way.
+#[derive(Clone, new)]
+pub struct HalfFlip {
+    start: CarState,
+    /// The match's gravity, for estimating the landing.
+    gravity: f32,
+}
+
+impl HalfFlip {
+    /// The time from the jump until we're back on our wheels. This is the
+    /// scripted inputs plus a rough allowance for the fall afterwards.
+    pub const DURATION: f32 = movement::HalfFlip::INPUT_TIME + 0.2;
+
+    /// The velocity change from the backwards dodge. It's close enough to the
+    /// forward dodge that the difference doesn't matter for planning.
+    pub const IMPULSE: f32 = CarForwardDodge::IMPULSE;
+}
+
+impl SegmentPlan for HalfFlip {
+    fn name(&self) -> &str {
+        name_of_type!(HalfFlip)
+    }
+
+    fn start(&self) -> CarState {
+        self.start.clone()
+    }
+
+    fn end(&self) -> CarState {
+        let start_vel = self.start.vel.to_2d();
+        let mut vel = start_vel - self.start.forward_axis_2d().into_inner() * Self::IMPULSE;
+        if vel.norm() > rl::CAR_MAX_SPEED {
+            vel = vel.normalize() * rl::CAR_MAX_SPEED;
+        }
+        let before_dodge = movement::HalfFlip::DODGE_TIME;
+        CarState2D {
+            loc: self.start.loc.to_2d()
+                + start_vel * before_dodge
+                + vel * (Self::DURATION - before_dodge),
+            rot: UnitComplex::new(PI) * self.start.rot.to_2d(),
+            vel,
+            boost: self.start.boost,
+        }
+        .to_3d()
+    }
+
+    fn duration(&self) -> f32 {
+        Self::DURATION
+    }
+
+    fn recovery_time(&self) -> f32 {
+        GetToFlatGround::estimated_recovery_time(&self.end(), true, self.gravity)
+    }
+
+    fn interruptible(&self) -> bool {
+        false
+    }
+
+    fn run(&self) -> Box<dyn SegmentRunner> {
+        Box::new(HalfFlipRunner::new())
+    }
+
+    fn draw(&self, ctx: &mut Context<'_>) {
+        ctx.eeg.draw(Drawable::Line(
+            self.start.loc.to_2d(),
+            self.end().loc.to_2d(),
+            color::GREEN,
+        ));
+    }
+}
+
+struct HalfFlipRunner {
+    behavior: Box<dyn Behavior>,
+}
+
+impl HalfFlipRunner {
+    pub fn new() -> Self {
+        Self {
+            behavior: Box::new(movement::HalfFlip::maneuver()),
+        }
+    }
+}
+
+impl SegmentRunner for HalfFlipRunner {
+    fn name(&self) -> &str {
+        name_of_type!(HalfFlipRunner)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
+        match self.behavior.execute_old(ctx) {
+            Action::Yield(i) => SegmentRunAction::Yield(i),
+            Action::TailCall(_) => panic!("TailCall not yet supported in SegmentRunner"),
+            Action::RootCall(_) => SegmentRunAction::Failure,
+            Action::Return => SegmentRunAction::Success,
+            Action::Abort => SegmentRunAction::Failure,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{Point2, Vector2};
+
+    #[test]
+    fn reverse_out_of_a_standstill() {
+        let start = CarState2D {
+            loc: Point2::origin(),
+            rot: UnitComplex::identity(),
+            vel: Vector2::zeros(),
+            boost: 0.0,
+        }
+        .to_3d();
+        let end = HalfFlip::new(start, rl::GRAVITY).end();
+        assert!((end.forward_axis_2d().into_inner() - -Vector2::x()).norm() < 1e-3);
+        assert!((end.vel.to_2d() - Vector2::new(-500.0, 0.0)).norm() < 1.0);
+        assert!(end.loc.x < -250.0);
+    }
+
+    #[test]
+    fn keep_the_speed_from_reversing() {
+        // Backing up at 500 uu/s, so after the flip we're going forwards at 1000.
+        let start = CarState2D {
+            loc: Point2::origin(),
+            rot: UnitComplex::identity(),
+            vel: Vector2::new(-500.0, 0.0),
+            boost: 0.0,
+        }
+        .to_3d();
+        let end = HalfFlip::new(start, rl::GRAVITY).end();
+        let forward_speed = end.vel.to_2d().dot(&end.forward_axis_2d());
+        assert!((forward_speed - 1000.0).abs() < 1.0);
+    }
+}
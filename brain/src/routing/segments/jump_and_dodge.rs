@@ -52,6 +52,10 @@ impl SegmentPlan for JumpAndDodge {
         JUMP_TIME + WAIT_TIME + FLOAT_TIME
     }
 
+    fn interruptible(&self) -> bool {
+        false
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(JumpAndDodgeRunner::new(self.clone()))
     }
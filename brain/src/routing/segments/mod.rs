@@ -2,6 +2,7 @@ pub use crate::routing::segments::{
     brake::Brake,
     chain::Chain,
     forward_dodge::ForwardDodge,
+    half_flip::HalfFlip,
     jump_and_dodge::JumpAndDodge,
     null::NullSegment,
     powerslide_turn::PowerslideTurn,
@@ -15,6 +16,7 @@ pub use crate::routing::segments::{
 mod brake;
 mod chain;
 mod forward_dodge;
+mod half_flip;
 mod jump_and_dodge;
 mod null;
 mod powerslide_turn;
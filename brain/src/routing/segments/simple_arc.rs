@@ -4,8 +4,8 @@ use crate::{
     routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
     strategy::Context,
 };
-use common::{physics::CAR_LOCAL_FORWARD_AXIS_2D, prelude::*};
-use nalgebra::{Point2, Unit, UnitComplex, Vector2};
+use common::{physics::CAR_LOCAL_FORWARD_AXIS_2D, prelude::*, rl};
+use nalgebra::{Point2, Point3, Unit, UnitComplex, Vector2};
 use nameof::name_of_type;
 use std::f32::consts::PI;
 
@@ -78,6 +78,27 @@ impl SimpleArc {
         })
     }
 
+    /// The angle of the starting point, as seen from the center.
+    pub fn start_angle(&self) -> f32 {
+        Vector2::x().angle_to(&(self.start_loc - self.center))
+    }
+
+    /// The angle of the ending point, as seen from the center. This is
+    /// `start_angle() + sweep`, so it can fall outside `[-π, π]`.
+    pub fn end_angle(&self) -> f32 {
+        self.start_angle() + self.sweep
+    }
+
+    /// The (constant) speed of the car along the arc.
+    pub fn speed(&self) -> f32 {
+        self.start_vel.norm()
+    }
+
+    /// The distance traveled along the arc.
+    pub fn length(&self) -> f32 {
+        self.radius * self.sweep.abs()
+    }
+
     /// Calculate a rotation of the given angle in this plan's direction.
     fn sweep_by_angle(&self, angle: f32) -> f32 {
         angle * self.sweep.signum()
@@ -141,7 +162,14 @@ impl SegmentPlan for SimpleArc {
     }
 
     fn duration(&self) -> f32 {
-        self.radius * self.sweep.abs() / self.start_vel.norm()
+        self.length() / self.speed()
+    }
+
+    fn position_at(&self, t: f32) -> Point3<f32> {
+        // Speed is constant, so the swept angle is proportional to time.
+        let progress = (t / self.duration()).max(0.0).min(1.0);
+        let rotation = UnitComplex::new(self.sweep * progress);
+        (self.center + rotation * (self.start_loc - self.center)).to_3d(rl::OCTANE_NEUTRAL_Z)
     }
 
     fn run(&self) -> Box<dyn SegmentRunner> {
@@ -149,8 +177,8 @@ impl SegmentPlan for SimpleArc {
     }
 
     fn draw(&self, ctx: &mut Context<'_>) {
-        let theta1 = Vector2::x().angle_to(&(self.start_loc - self.center));
-        let theta2 = theta1 + self.sweep;
+        let theta1 = self.start_angle();
+        let theta2 = self.end_angle();
         ctx.eeg.draw(Drawable::Arc(
             self.center,
             self.radius,
@@ -214,6 +242,61 @@ impl SegmentRunner for SimpleArcRunner {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::routing::{models::SegmentPlan, segments::SimpleArc};
+    use common::prelude::*;
+    use nalgebra::{Point2, Vector2};
+    use std::f32::consts::PI;
+
+    /// A counter-clockwise quarter turn around the origin.
+    fn quarter_turn() -> SimpleArc {
+        SimpleArc::new(
+            Point2::origin(),
+            1000.0,
+            Point2::new(1000.0, 0.0),
+            Vector2::new(0.0, 500.0),
+            0.0,
+            Point2::new(0.0, 1000.0),
+        )
+        .ok()
+        .unwrap()
+    }
+
+    #[test]
+    fn endpoints() {
+        let arc = quarter_turn();
+        assert!((arc.start_angle() - 0.0).abs() < 1e-4);
+        assert!((arc.end_angle() - PI / 2.0).abs() < 1e-4);
+        assert!((arc.end().loc.to_2d() - Point2::new(0.0, 1000.0)).norm() < 1.0);
+        assert!((arc.end().vel.to_2d() - Vector2::new(-500.0, 0.0)).norm() < 1.0);
+        assert!((arc.position_at(0.0).to_2d() - Point2::new(1000.0, 0.0)).norm() < 1.0);
+        let end = arc.position_at(arc.duration()).to_2d();
+        assert!((end - Point2::new(0.0, 1000.0)).norm() < 1.0);
+    }
+
+    #[test]
+    fn length_and_duration() {
+        let arc = quarter_turn();
+        assert!((arc.length() - 1000.0 * PI / 2.0).abs() < 1.0);
+        assert!((arc.duration() - arc.length() / 500.0).abs() < 1e-4);
+
+        // Walk along the arc and make sure every sample is on the circle, and
+        // that the samples add up to the expected length.
+        let samples = 100;
+        let mut prev = arc.position_at(0.0).to_2d();
+        let mut traveled = 0.0;
+        for i in 1..=samples {
+            let t = arc.duration() * i as f32 / samples as f32;
+            let loc = arc.position_at(t).to_2d();
+            assert!(((loc - arc.center).norm() - arc.radius).abs() < 1.0);
+            traveled += (loc - prev).norm();
+            prev = loc;
+        }
+        assert!((traveled - arc.length()).abs() < 5.0);
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use crate::{
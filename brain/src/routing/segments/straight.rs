@@ -5,7 +5,7 @@ use crate::{
     strategy::Context,
 };
 use common::{prelude::*, rl};
-use nalgebra::{Point2, Vector2};
+use nalgebra::{clamp, Point2, Point3, Vector2};
 use nameof::name_of_type;
 use simulate::Car1D;
 
@@ -18,6 +18,7 @@ pub struct Straight {
     duration: f32,
     mode: StraightMode,
     allow_boost: bool,
+    launch_ramp: f32,
 }
 
 /// This is a workaround for the lack of "arrive-at-time" behavior.
@@ -79,9 +80,21 @@ impl Straight {
             duration: sim.time(),
             mode,
             allow_boost,
+            launch_ramp: 0.0,
         }
     }
 
+    /// When starting from a standstill, ease the throttle in over this many
+    /// seconds instead of flooring it. This is only for tests, since
+    /// measurements (see the `LaunchRamp` collect scenario) haven't shown
+    /// a benefit. The segment's duration does not account for the ramp.
+    #[cfg(test)]
+    pub fn launch_ramp(mut self, ramp_time: f32) -> Self {
+        assert!(ramp_time >= 0.0);
+        self.launch_ramp = ramp_time;
+        self
+    }
+
     fn zero(start: CarState2D) -> Self {
         Self {
             start: start.clone(),
@@ -91,6 +104,7 @@ impl Straight {
             duration: 0.0,
             mode: StraightMode::Fake,
             allow_boost: true,
+            launch_ramp: 0.0,
         }
     }
 }
@@ -120,6 +134,20 @@ impl SegmentPlan for Straight {
         self.duration
     }
 
+    fn position_at(&self, t: f32) -> Point3<f32> {
+        let start_to_end = self.end_loc - self.start.loc;
+        if start_to_end.norm() < 1.0 {
+            return self.start.loc.to_3d(rl::OCTANE_NEUTRAL_Z);
+        }
+
+        let mut sim = Car1D::new()
+            .with_speed(self.start.vel.norm())
+            .with_boost(self.start.boost);
+        sim.advance(t.max(0.0).min(self.duration), 1.0, self.allow_boost);
+        let dist = sim.distance().min(start_to_end.norm());
+        (self.start.loc + start_to_end.normalize() * dist).to_3d(rl::OCTANE_NEUTRAL_Z)
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(StraightRunner::new(self.clone()))
     }
@@ -130,13 +158,38 @@ impl SegmentPlan for Straight {
     }
 }
 
+/// Below this speed, we consider the car to be at a standstill.
+const LAUNCH_MAX_SPEED: f32 = 50.0;
+
+/// The throttle to use `elapsed` seconds into a launch, if easing it in over
+/// `ramp_time` seconds.
+pub fn launch_throttle(elapsed: f32, ramp_time: f32) -> f32 {
+    if ramp_time <= 0.0 {
+        return 1.0;
+    }
+    clamp(elapsed / ramp_time, 0.0, 1.0)
+}
+
 struct StraightRunner {
     plan: Straight,
+    start_time: Option<f32>,
 }
 
 impl StraightRunner {
     pub fn new(plan: Straight) -> Self {
-        StraightRunner { plan }
+        StraightRunner {
+            plan,
+            start_time: None,
+        }
+    }
+
+    fn throttle(&mut self, ctx: &mut Context<'_>) -> f32 {
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let start_time = *self.start_time.get_or_insert(now);
+        if self.plan.start.vel.norm() >= LAUNCH_MAX_SPEED {
+            return 1.0;
+        }
+        launch_throttle(now - start_time, self.plan.launch_ramp)
     }
 }
 
@@ -175,13 +228,125 @@ impl SegmentRunner for StraightRunner {
         ctx.eeg
             .draw(Drawable::ghost_car_ground(target_loc, me.Physics.rot()));
 
+        let throttle = self.throttle(ctx);
         SegmentRunAction::Yield(common::halfway_house::PlayerInput {
-            Throttle: 1.0,
+            Throttle: throttle,
             Steer: simple_steer_towards(&me.Physics, target_loc),
             Boost: self.plan.allow_boost
+                && throttle == 1.0
                 && me.Physics.vel().norm() < rl::CAR_ALMOST_MAX_SPEED
                 && me.Boost > 0,
             ..Default::default()
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        models::{CarState2D, SegmentPlan},
+        segments::{straight::launch_throttle, Straight, StraightMode},
+    };
+    use nalgebra::{Point2, UnitComplex, Vector2};
+
+    #[test]
+    fn position_at() {
+        let start = CarState2D {
+            loc: Point2::origin(),
+            rot: UnitComplex::identity(),
+            vel: Vector2::new(1000.0, 0.0),
+            boost: 0.0,
+        };
+        let segment = Straight::new(
+            start,
+            Point2::new(2000.0, 0.0),
+            0.0,
+            StraightMode::Asap,
+            false,
+        );
+
+        assert_eq!(segment.position_at(0.0).x, 0.0);
+        let halfway = segment.position_at(segment.duration() / 2.0);
+        // We're accelerating, so we should cover less than half the distance in half
+        // the time.
+        assert!(halfway.x > 500.0);
+        assert!(halfway.x < 1000.0);
+        assert!((segment.position_at(segment.duration()).x - 2000.0).abs() < 1.0);
+        assert!((segment.position_at(99.0).x - 2000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn launch_throttle_ramps_up() {
+        assert_eq!(launch_throttle(0.0, 0.0), 1.0);
+        assert_eq!(launch_throttle(0.0, 0.1), 0.0);
+        assert!((launch_throttle(0.05, 0.1) - 0.5).abs() < 1e-6);
+        assert_eq!(launch_throttle(0.5, 0.1), 1.0);
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        integration_tests::{TestRunner, TestScenario},
+        routing::{
+            models::{CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+            segments::{Straight, StraightMode},
+        },
+        strategy::{Action, Behavior, Context},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point2, Point3, UnitComplex, Vector2};
+    use nameof::name_of_type;
+    use std::f32::consts::PI;
+
+    struct RunSegment(Box<dyn SegmentRunner>);
+
+    impl Behavior for RunSegment {
+        fn name(&self) -> &str {
+            name_of_type!(RunSegment)
+        }
+
+        fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+            match self.0.execute_old(ctx) {
+                SegmentRunAction::Yield(input) => Action::Yield(input),
+                SegmentRunAction::Success => Action::Return,
+                SegmentRunAction::Failure => Action::Abort,
+            }
+        }
+    }
+
+    fn launch_distance(ramp_time: f32) -> f32 {
+        let start = CarState2D {
+            loc: Point2::origin(),
+            rot: UnitComplex::new(PI / 2.0),
+            vel: Vector2::zeros(),
+            boost: 0.0,
+        };
+        let straight = Straight::new(
+            start,
+            Point2::new(0.0, 5000.0),
+            0.0,
+            StraightMode::Asap,
+            false,
+        )
+        .launch_ramp(ramp_time);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(3000.0, 0.0, 92.74),
+                ..Default::default()
+            })
+            .behavior(RunSegment(straight.run()))
+            .run_for_millis(1000);
+        let packet = test.sniff_packet();
+        packet.GameCars[0].Physics.loc().y - TestScenario::default().car_loc.y
+    }
+
+    /// This is why the ramp is off by default.
+    #[test]
+    fn instant_throttle_is_no_slower_than_ramp() {
+        let instant = launch_distance(0.0);
+        let ramp = launch_distance(0.1);
+        println!("instant = {:.0}, ramp = {:.0}", instant, ramp);
+        assert!(instant >= ramp - 10.0);
+    }
+}
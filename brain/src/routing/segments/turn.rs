@@ -4,8 +4,8 @@ use crate::{
     routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
     strategy::Context,
 };
-use common::prelude::*;
-use nalgebra::{Point2, UnitComplex, Vector2};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Point3, UnitComplex, Vector2};
 use nameof::name_of_type;
 use std::f32::consts::PI;
 
@@ -83,6 +83,16 @@ impl SegmentPlan for Turn {
         self.radius * self.sweep.abs() / assume_speed
     }
 
+    fn position_at(&self, t: f32) -> Point3<f32> {
+        let duration = self.duration();
+        if duration <= 0.0 {
+            return self.start.loc.to_3d(rl::OCTANE_NEUTRAL_Z);
+        }
+        let progress = (t / duration).max(0.0).min(1.0);
+        let rotation = UnitComplex::new(self.sweep * progress);
+        (self.center + rotation * (self.start.loc - self.center)).to_3d(rl::OCTANE_NEUTRAL_Z)
+    }
+
     fn run(&self) -> Box<dyn SegmentRunner> {
         Box::new(Turner::new(self.clone()))
     }
@@ -5,10 +5,17 @@ use nameof::name_of_type;
 
 const ERROR_THRESHOLD: f32 = 50.0;
 
+/// How much extra error to allow per uu/s of ball speed, per second of
+/// lookahead. The framework's re-predictions drift more for a fast ball.
+const ERROR_SPEED_FACTOR: f32 = 0.2;
+
 /// Track the ball's trajectory vs. our prediction, and if they differ by too
 /// much, abort.
 pub struct SameBallTrajectory {
     prediction: Option<Prediction>,
+    /// Whether the last check was over the threshold. We only abort if it
+    /// happens twice in a row, so one noisy frame doesn't throw away a shot.
+    exceeded: bool,
 }
 
 struct Prediction {
@@ -18,16 +25,25 @@ struct Prediction {
 
 impl SameBallTrajectory {
     pub fn new() -> SameBallTrajectory {
-        SameBallTrajectory { prediction: None }
+        SameBallTrajectory {
+            prediction: None,
+            exceeded: false,
+        }
     }
 
     pub fn execute_old(&mut self, ctx: &mut Context<'_>) -> Option<Action> {
-        if self.eval_vel_changed(ctx) {
-            Some(Action::Abort)
-        } else {
+        if !self.eval_vel_changed(ctx) {
+            self.exceeded = false;
             self.update_snapshot(ctx);
-            None
+            return None;
         }
+        if self.exceeded {
+            return Some(Action::Abort);
+        }
+        // Keep the old snapshot so the next frame is checked against the same
+        // prediction. A real change will still be there; a blip won't.
+        self.exceeded = true;
+        None
     }
 
     fn update_snapshot(&mut self, ctx: &mut Context<'_>) {
@@ -52,10 +68,14 @@ impl SameBallTrajectory {
         };
 
         let error = (prediction.loc - frame.loc).to_2d().norm();
-        if error >= ERROR_THRESHOLD {
+        let threshold = error_threshold(frame.vel.norm(), rel_time);
+        if error >= threshold {
             ctx.eeg.log(
                 name_of_type!(SameBallTrajectory),
-                format!("perturbance detected with error {:.2}", error),
+                format!(
+                    "perturbance detected with error {:.2} (threshold {:.2})",
+                    error, threshold,
+                ),
             );
             true
         } else {
@@ -63,3 +83,22 @@ impl SameBallTrajectory {
         }
     }
 }
+
+fn error_threshold(ball_speed: f32, rel_time: f32) -> f32 {
+    ERROR_THRESHOLD + ball_speed * rel_time.max(0.0) * ERROR_SPEED_FACTOR
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rules::same_ball_trajectory::{error_threshold, ERROR_THRESHOLD};
+
+    #[test]
+    fn threshold_grows_with_speed_and_time() {
+        assert_eq!(error_threshold(0.0, 0.1), ERROR_THRESHOLD);
+        assert_eq!(error_threshold(3000.0, 0.0), ERROR_THRESHOLD);
+        assert!(error_threshold(3000.0, 0.1) > error_threshold(1000.0, 0.1));
+        assert!(error_threshold(3000.0, 0.1) > error_threshold(3000.0, 0.05));
+        // Don't go below the floor if the snapshot is already in the past.
+        assert_eq!(error_threshold(3000.0, -0.1), ERROR_THRESHOLD);
+    }
+}
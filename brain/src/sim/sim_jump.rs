@@ -1,8 +1,12 @@
 use crate::routing::models::CarState;
 use common::{kinematics::kinematic, rl};
+use derive_new::new;
 use nalgebra::{UnitQuaternion, Vector3};
 
-pub struct SimJump;
+#[derive(new)]
+pub struct SimJump {
+    gravity: f32,
+}
 
 impl SimJump {
     pub fn simulate(
@@ -15,13 +19,13 @@ impl SimJump {
         // the jump button down for the maximum 0.2 seconds.
         let force_time = time.min(rl::CAR_JUMP_FORCE_TIME);
         let v_0 = start.vel + start.roof_axis().into_inner() * rl::CAR_JUMP_IMPULSE_SPEED;
-        let a = start.roof_axis().into_inner() + Vector3::z() * rl::GRAVITY;
+        let a = start.roof_axis().into_inner() + Vector3::z() * self.gravity;
         let (d, vel) = kinematic(v_0, a, force_time);
         let loc = start.loc + d;
 
         // Phase 2: simple freefall.
         let coast_time = time - force_time;
-        let a = Vector3::z() * rl::GRAVITY;
+        let a = Vector3::z() * self.gravity;
         let (d, vel) = kinematic(vel, a, coast_time);
         let loc = loc + d;
 
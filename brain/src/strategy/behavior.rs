@@ -17,7 +17,7 @@ pub trait Behavior: Send {
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action;
 }
 
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
 pub enum Priority {
     Idle,
     Defense,
@@ -1,8 +1,9 @@
 use crate::{
     eeg::EEG,
-    strategy::{game::Game, scenario::Scenario, Team},
+    strategy::{game::Game, scenario::Scenario, scratch::Scratch, Team},
 };
 use common::prelude::*;
+use nalgebra::Point3;
 
 pub struct Context<'a> {
     pub packet: &'a common::halfway_house::LiveDataPacket,
@@ -10,15 +11,32 @@ pub struct Context<'a> {
     pub scenario: &'a Scenario<'a>,
     pub eeg: &'a mut EEG,
     pub last_quick_chat: &'a mut f32,
+    /// Points along the route we're currently following, if any. This is
+    /// cleared at the start of every tick.
+    pub planned_path: &'a mut Option<Vec<Point3<f32>>>,
+    /// The game time of the most recent kickoff we saw, if any.
+    pub last_kickoff: Option<f32>,
+    /// The game time of the last tick where our wheels were on the ground, if
+    /// any.
+    pub last_on_ground: Option<f32>,
+    /// The controls we sent on the previous tick.
+    pub last_input: common::halfway_house::PlayerInput,
+    /// Values computed earlier this tick, for reuse.
+    pub scratch: Scratch,
 }
 
 impl<'a> Context<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         game: &'a Game<'_>,
         packet: &'a common::halfway_house::LiveDataPacket,
         scenario: &'a Scenario<'a>,
         eeg: &'a mut EEG,
         last_quick_chat: &'a mut f32,
+        planned_path: &'a mut Option<Vec<Point3<f32>>>,
+        last_kickoff: Option<f32>,
+        last_on_ground: Option<f32>,
+        last_input: common::halfway_house::PlayerInput,
     ) -> Self {
         Self {
             packet,
@@ -26,6 +44,11 @@ impl<'a> Context<'a> {
             scenario,
             eeg,
             last_quick_chat,
+            planned_path,
+            last_kickoff,
+            last_on_ground,
+            last_input,
+            scratch: Scratch::new(),
         }
     }
 
@@ -42,6 +65,25 @@ impl<'a> Context<'a> {
         self.game.cars(self.game.enemy_team)
     }
 
+    /// Returns the other cars on our team, not including us.
+    pub fn teammates(&self) -> impl Iterator<Item = &common::halfway_house::PlayerInfo> {
+        self.game.teammates()
+    }
+
+    /// Returns the number of seconds since the ball was last sitting at center
+    /// field for a kickoff, or `None` if we haven't seen a kickoff yet.
+    pub fn time_since_kickoff(&self) -> Option<f32> {
+        self.last_kickoff
+            .map(|t| self.packet.GameInfo.TimeSeconds - t)
+    }
+
+    /// Returns the number of seconds since our wheels last touched the ground,
+    /// or `None` if they never have.
+    pub fn time_since_on_ground(&self) -> Option<f32> {
+        self.last_on_ground
+            .map(|t| self.packet.GameInfo.TimeSeconds - t)
+    }
+
     /// I should not have mixed immumtable and mutable values in the `Context`.
     /// This is part of the pathway towards fixing that mistake.
     pub fn split<'s>(&'s mut self) -> (Context2<'a, 's>, &'s mut EEG) {
@@ -92,4 +134,9 @@ impl<'c, 's> Context2<'c, 's> {
     pub fn me(&self) -> &common::halfway_house::PlayerInfo {
         self.game.me()
     }
+
+    /// Returns the other cars on our team, not including us.
+    pub fn teammates(&self) -> impl Iterator<Item = &common::halfway_house::PlayerInfo> {
+        self.game.teammates()
+    }
 }
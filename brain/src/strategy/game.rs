@@ -15,7 +15,9 @@ pub struct Game<'a> {
     pub team: Team,
     pub enemy_team: Team,
     boost_dollars: Box<[BoostPickup]>,
+    boost_pads: Box<[BoostPad]>,
     me_vehicle: &'a Vehicle,
+    boost_conservation_threshold: f32,
 }
 
 impl<'a> Game<'a> {
@@ -39,10 +41,33 @@ impl<'a> Game<'a> {
                 })
                 .collect::<Vec<_>>()
                 .into_boxed_slice(),
+            boost_pads: vector_iter(field_info.boostPads().unwrap())
+                .map(|info| BoostPad {
+                    loc: point3(info.location().unwrap()).to_2d(),
+                    is_full_boost: info.isFullBoost(),
+                })
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
             me_vehicle: &OCTANE,
+            boost_conservation_threshold: Self::DEFAULT_BOOST_CONSERVATION_THRESHOLD,
         }
     }
 
+    pub const DEFAULT_BOOST_CONSERVATION_THRESHOLD: f32 = 20.0;
+
+    /// Below this much boost, behaviors should stop spending boost on marginal
+    /// speed gains. Pass `0.0` to disable conservation.
+    pub fn boost_conservation_threshold(mut self, threshold: f32) -> Self {
+        self.boost_conservation_threshold = threshold;
+        self
+    }
+
+    /// Returns true if boost is scarce enough that we should save it for when
+    /// it really matters.
+    pub fn conserve_boost(&self) -> bool {
+        (self.me().Boost as f32) < self.boost_conservation_threshold
+    }
+
     pub fn pitch(&self) -> &Pitch {
         self.pitch
     }
@@ -83,6 +108,17 @@ impl<'a> Game<'a> {
             .filter(move |p| Team::from_ffi(p.Team) == team)
     }
 
+    /// Returns the other cars on our team, not including us.
+    pub fn teammates(&self) -> impl Iterator<Item = &common::halfway_house::PlayerInfo> {
+        let player_index = self.player_index;
+        let team = self.team;
+        self.packet
+            .cars()
+            .enumerate()
+            .filter(move |&(i, p)| i != player_index && Team::from_ffi(p.Team) == team)
+            .map(|(_, p)| p)
+    }
+
     pub fn own_goal(&self) -> &Goal {
         match self.mode {
             rlbot::GameMode::Soccer => Goal::soccar(self.team),
@@ -115,9 +151,33 @@ impl<'a> Game<'a> {
         &*self.boost_dollars
     }
 
+    /// Every boost pad on the field, big and small, with its state as of this
+    /// tick.
+    pub fn boost_pads(&self) -> impl Iterator<Item = BoostPadState> + '_ {
+        self.boost_pads.iter().enumerate().map(move |(i, pad)| {
+            // If the packet didn't tell us, assume the pad is up.
+            let info = self.packet.GameBoosts.get(i).cloned().unwrap_or(
+                common::halfway_house::BoostInfo {
+                    IsActive: true,
+                    Timer: 0.0,
+                },
+            );
+            pad.state(&info)
+        })
+    }
+
     pub fn ball_radius(&self) -> f32 {
         rl::BALL_RADIUS
     }
+
+    /// The game time of the packet, in seconds.
+    pub fn time(&self) -> f32 {
+        self.packet.GameInfo.TimeSeconds
+    }
+
+    pub fn gravity(&self) -> f32 {
+        self.packet.GameInfo.gravity()
+    }
 }
 
 pub fn infer_game_mode(field_info: rlbot::flat::FieldInfo<'_>) -> rlbot::GameMode {
@@ -197,6 +257,21 @@ impl Goal {
         Point2::new(target.x.max(-self.max_x).min(self.max_x), self.center_2d.y)
     }
 
+    /// Returns both goalposts.
+    pub fn posts(&self) -> [Point2<f32>; 2] {
+        [
+            Point2::new(-self.max_x, self.center_2d.y),
+            Point2::new(self.max_x, self.center_2d.y),
+        ]
+    }
+
+    /// Returns the goalpost on the opposite side of the goal from the given
+    /// point.
+    pub fn far_post(&self, target: Point2<f32>) -> Point2<f32> {
+        let side = if target.x < 0.0 { 1.0 } else { -1.0 };
+        Point2::new(side * self.max_x, self.center_2d.y)
+    }
+
     /// Returns true if the given y value is less than `range.end` uu in front
     /// of the goal.
     ///
@@ -236,6 +311,45 @@ pub struct BoostPickup {
     pub loc: Point2<f32>,
 }
 
+/// A boost pad, as described by the field info.
+#[derive(Clone)]
+pub struct BoostPad {
+    pub loc: Point2<f32>,
+    pub is_full_boost: bool,
+}
+
+impl BoostPad {
+    fn state(&self, info: &common::halfway_house::BoostInfo) -> BoostPadState {
+        let respawn_duration = if self.is_full_boost {
+            rl::BOOST_DOLLAR_RESPAWN_TIME
+        } else {
+            rl::BOOST_PENNY_RESPAWN_TIME
+        };
+        let respawn_time = if info.IsActive {
+            0.0
+        } else {
+            (respawn_duration - info.Timer).max(0.0)
+        };
+        BoostPadState {
+            loc: self.loc,
+            is_full_boost: self.is_full_boost,
+            is_active: info.IsActive,
+            respawn_time,
+        }
+    }
+}
+
+/// A boost pad, and whether it can be picked up right now.
+#[derive(Clone, Debug)]
+pub struct BoostPadState {
+    pub loc: Point2<f32>,
+    pub is_full_boost: bool,
+    pub is_active: bool,
+    /// Roughly how many seconds until the pad is back. This is 0 if it's
+    /// active.
+    pub respawn_time: f32,
+}
+
 pub struct Vehicle {
     half_size: Vector3<f32>,
     pivot_offset: Vector3<f32>,
@@ -277,3 +391,34 @@ lazy_static! {
         pivot_offset: Vector3::new(13.87566, 0.0, 20.75499),
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::game::BoostPad;
+    use common::halfway_house::BoostInfo;
+    use nalgebra::Point2;
+
+    #[test]
+    fn respawn_time() {
+        let dollar = BoostPad {
+            loc: Point2::new(3072.0, 4096.0),
+            is_full_boost: true,
+        };
+        let penny = BoostPad {
+            loc: Point2::new(0.0, 4240.0),
+            is_full_boost: false,
+        };
+        let taken = BoostInfo {
+            IsActive: false,
+            Timer: 3.0,
+        };
+        let active = BoostInfo {
+            IsActive: true,
+            Timer: 0.0,
+        };
+        assert_eq!(dollar.state(&taken).respawn_time, 7.0);
+        assert_eq!(penny.state(&taken).respawn_time, 1.0);
+        assert_eq!(dollar.state(&active).respawn_time, 0.0);
+        assert!(dollar.state(&active).is_active);
+    }
+}
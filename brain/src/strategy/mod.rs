@@ -3,8 +3,8 @@ pub use crate::strategy::{
     context::{Context, Context2},
     dropshot::Dropshot,
     game::{
-        infer_game_mode, BoostPickup, Game, Goal, Team, Vehicle, SOCCAR_GOAL_BLUE,
-        SOCCAR_GOAL_ORANGE,
+        infer_game_mode, BoostPadState, BoostPickup, Game, Goal, Team, Vehicle,
+        SOCCAR_GOAL_BLUE, SOCCAR_GOAL_ORANGE,
     },
     pitch::Pitch,
     runner::Runner,
@@ -19,8 +19,10 @@ mod game;
 #[cfg(test)]
 pub mod null;
 mod pitch;
+pub mod positioning;
 mod runner;
 mod scenario;
+mod scratch;
 mod soccar;
 #[allow(clippy::module_inception)]
 mod strategy;
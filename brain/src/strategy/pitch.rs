@@ -16,6 +16,14 @@ impl Pitch {
             .unwrap()
     }
 
+    /// Like `closest_plane`, but ignores the floor and ceiling.
+    pub fn closest_wall(&self, point: &Point3<f32>) -> &Plane {
+        self.planes[2..]
+            .iter()
+            .min_by_key(|plane| NotNan::new(plane.distance_to_point(point)).unwrap())
+            .unwrap()
+    }
+
     pub fn ground(&self) -> &Plane {
         &self.planes[0]
     }
@@ -0,0 +1,68 @@
+use crate::strategy::{Game, Goal};
+use common::prelude::*;
+use nalgebra::{clamp, Point2, UnitComplex};
+use std::f32::consts::PI;
+
+/// Never stray further than this from straight out of the goal. Past this, the
+/// far post is left wide open, and we start drifting towards the back wall.
+const MAX_ANGLE: f32 = PI / 3.0;
+
+/// Always stay at least this far out, so we never end up parked in the net.
+const MIN_STANDOFF: f32 = 200.0;
+
+/// The spot `standoff` uu out from the center of our goal, on the line towards
+/// the ball. This is where to wait when defending.
+pub fn defensive_spot(game: &Game<'_>, ball_loc: Point2<f32>, standoff: f32) -> Point2<f32> {
+    goal_side_spot(game.own_goal(), ball_loc, standoff)
+}
+
+fn goal_side_spot(goal: &Goal, ball_loc: Point2<f32>, standoff: f32) -> Point2<f32> {
+    let normal = goal.normal_2d.into_inner();
+    let goal_to_ball = ball_loc - goal.center_2d;
+    let angle = if goal_to_ball.norm() < 1.0 {
+        0.0
+    } else {
+        clamp(normal.angle_to(&goal_to_ball), -MAX_ANGLE, MAX_ANGLE)
+    };
+    goal.center_2d + UnitComplex::new(angle) * normal * standoff.max(MIN_STANDOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::{positioning::goal_side_spot, SOCCAR_GOAL_BLUE};
+    use common::prelude::*;
+    use nalgebra::Point2;
+
+    const EPS: f32 = 1e-3;
+
+    #[test]
+    fn on_the_line_towards_the_ball() {
+        let goal = &SOCCAR_GOAL_BLUE;
+        for &ball_loc in &[
+            Point2::new(0.0, 0.0),
+            Point2::new(1500.0, -1000.0),
+            Point2::new(-2000.0, 3000.0),
+        ] {
+            let spot = goal_side_spot(goal, ball_loc, 800.0);
+            let to_spot = spot - goal.center_2d;
+            let to_ball = ball_loc - goal.center_2d;
+            assert!((to_spot.norm() - 800.0).abs() < EPS);
+            assert!(to_spot.angle_to(&to_ball).abs() < EPS);
+        }
+    }
+
+    #[test]
+    fn stay_in_front_of_goal_when_ball_is_in_corner() {
+        let goal = &SOCCAR_GOAL_BLUE;
+        let spot = goal_side_spot(goal, Point2::new(-4000.0, -5000.0), 800.0);
+        assert!(spot.x < 0.0);
+        assert!((spot - goal.center_2d).dot(&goal.normal_2d) >= 800.0 * 0.4);
+    }
+
+    #[test]
+    fn never_inside_the_net() {
+        let goal = &SOCCAR_GOAL_BLUE;
+        let spot = goal_side_spot(goal, goal.center_2d, 0.0);
+        assert!((spot - goal.center_2d).dot(&goal.normal_2d) >= 200.0 - EPS);
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(test)]
+use crate::strategy::Priority;
 use crate::{
     eeg::{color, Drawable},
     strategy::{strategy::Strategy, Action, Behavior, Context},
@@ -28,6 +30,28 @@ impl Runner {
     pub fn execute_old(&mut self, ctx: &mut Context<'_>) -> common::halfway_house::PlayerInput {
         self.exec(0, ctx)
     }
+
+    /// Returns true if a behavior with the given priority would be allowed to
+    /// interrupt the one that's currently running.
+    #[cfg(test)]
+    pub fn would_interrupt(&self, priority: Priority) -> bool {
+        match self.current {
+            Some(ref current) => current.priority() < priority,
+            None => true,
+        }
+    }
+
+    /// Offer `behavior` as an interruption, as if the strategy had proposed
+    /// it. It only takes over if `would_interrupt` says its priority is high
+    /// enough. Returns whether it did.
+    #[cfg(test)]
+    pub fn interrupt(&mut self, behavior: Box<dyn Behavior>) -> bool {
+        if !self.would_interrupt(behavior.priority()) {
+            return false;
+        }
+        self.current = Some(behavior);
+        true
+    }
 }
 
 impl Behavior for Runner {
@@ -97,3 +121,90 @@ impl Runner {
         &mut **self.current.as_mut().unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::{Action, Behavior, Context, Priority, Runner};
+    use nameof::name_of_type;
+
+    const PRIORITIES: [Priority; 6] = [
+        Priority::Idle,
+        Priority::Defense,
+        Priority::Save,
+        Priority::Strike,
+        Priority::Taunt,
+        Priority::Force,
+    ];
+
+    struct Prioritized(Priority);
+
+    impl Behavior for Prioritized {
+        fn name(&self) -> &str {
+            name_of_type!(Prioritized)
+        }
+
+        fn priority(&self) -> Priority {
+            self.0
+        }
+
+        fn execute_old(&mut self, _ctx: &mut Context<'_>) -> Action {
+            Action::Yield(Default::default())
+        }
+    }
+
+    #[test]
+    fn anything_interrupts_nothing() {
+        let runner = Runner::new(crate::strategy::null::NullStrategy::new());
+        for &priority in PRIORITIES.iter() {
+            assert!(runner.would_interrupt(priority));
+        }
+    }
+
+    #[test]
+    fn only_higher_priorities_interrupt() {
+        for (i, &current) in PRIORITIES.iter().enumerate() {
+            let runner = Runner::with_current(Prioritized(current));
+            for (j, &incoming) in PRIORITIES.iter().enumerate() {
+                assert_eq!(
+                    runner.would_interrupt(incoming),
+                    j > i,
+                    "{:?} interrupting {:?}",
+                    incoming,
+                    current,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn interrupt_replaces_current() {
+        for (i, &current) in PRIORITIES.iter().enumerate() {
+            for (j, &incoming) in PRIORITIES.iter().enumerate() {
+                let mut runner = Runner::with_current(Prioritized(current));
+                assert_eq!(runner.interrupt(Box::new(Prioritized(incoming))), j > i);
+                let running = runner.current.as_ref().unwrap().priority();
+                assert_eq!(running, if j > i { incoming } else { current });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{behavior::movement::Yielder, integration_tests::TestRunner, strategy::Priority};
+
+    #[test]
+    fn inject_respects_priority() {
+        let test = TestRunner::new()
+            .behavior(Yielder::new(9999.0, Default::default()).priority(Priority::Strike))
+            .run();
+        test.step_ticks(1);
+
+        let defend = Yielder::new(9999.0, Default::default()).priority(Priority::Defense);
+        assert!(!test.inject_behavior(defend));
+        let force = Yielder::new(9999.0, Default::default()).priority(Priority::Force);
+        assert!(test.inject_behavior(force));
+        let taunt = Yielder::new(9999.0, Default::default()).priority(Priority::Taunt);
+        assert!(!test.inject_behavior(taunt));
+    }
+}
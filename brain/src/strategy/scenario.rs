@@ -1,17 +1,84 @@
 use crate::{
+    behavior::{strike::GroundedHit, PreKickoff},
     helpers::{
         ball::{BallFrame, BallPredictor, BallTrajectory},
+        drive::rough_time_drive_to_loc,
         intercept::{naive_intercept_penalty, NaiveIntercept},
     },
-    strategy::{game::Game, Goal},
+    routing::models::SegmentPlan,
+    strategy::{game::Game, BoostPadState, Goal, Pitch, Team},
     utils::{Wall, WallRayCalculator},
 };
-use common::prelude::*;
+use common::{prelude::*, rl};
 use lazycell::LazyCell;
-use nalgebra::Vector2;
+use nalgebra::{Point2, Vector2};
 use ordered_float::NotNan;
 use simulate::{linear_interpolate, Car1D};
-use std::f32::{self, consts::PI};
+use std::{
+    f32::{self, consts::PI},
+    ptr,
+};
+
+/// How close the car's center can get to the ball's surface before we consider
+/// it a touch. This is roughly half the length of the car, plus some slack.
+const INCIDENTAL_CONTACT_MARGIN: f32 = 100.0;
+
+/// How far a defender can be from the ball's path and still get a piece of
+/// it. This is the ball's radius plus about half a car's width, with a little
+/// extra for a jump or a dodge.
+const SAVE_REACH: f32 = 200.0;
+
+/// A rough guess at how fast a typical shot travels, used to estimate when it
+/// arrives.
+const TYPICAL_SHOT_SPEED: f32 = 2500.0;
+
+/// With at least this much boost, a car can go up for a ball that's out of
+/// reach from the ground.
+const AERIAL_MIN_BOOST: f32 = 30.0;
+
+/// A coarse description of what's going on in the game right now.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GamePhase {
+    /// The ball is sitting at center field, waiting for someone to touch it.
+    Kickoff,
+    /// Nobody is clearly in control of the play.
+    Open,
+    /// The enemy has the ball in our half.
+    Defending,
+    /// We have the ball in the enemy half.
+    Attacking,
+}
+
+/// The numbers behind `Scenario::possession`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PossessionDetail {
+    /// How long until I can reach the ball. `None` means I can't reach it
+    /// within the prediction window.
+    pub me_time: Option<f32>,
+    /// How long until the first enemy can reach the ball. `None` means no
+    /// enemy can reach it within the prediction window.
+    pub enemy_time: Option<f32>,
+    /// Same as `Scenario::possession`.
+    pub possession: f32,
+}
+
+impl PossessionDetail {
+    fn new(me_time: Option<f32>, enemy_time: Option<f32>) -> Self {
+        let possession = match (me_time, enemy_time) {
+            (Some(me), Some(enemy)) => enemy - me,
+            _ => {
+                // To avoid mexican standoffs, just pretend we have full possession so we go
+                // for the ball.
+                Scenario::POSSESSION_SATURATED
+            }
+        };
+        Self {
+            me_time,
+            enemy_time,
+            possession,
+        }
+    }
+}
 
 pub struct Scenario<'a> {
     packet: &'a common::halfway_house::LiveDataPacket,
@@ -19,14 +86,18 @@ pub struct Scenario<'a> {
     ball_predictor: &'a dyn BallPredictor,
     ball_prediction: LazyCell<BallTrajectory>,
     me_intercept: LazyCell<Option<NaiveIntercept>>,
-    enemy_intercept: LazyCell<Option<(&'a common::halfway_house::PlayerInfo, NaiveIntercept)>>,
-    possession: LazyCell<f32>,
+    enemy_intercepts: LazyCell<Vec<(&'a common::halfway_house::PlayerInfo, NaiveIntercept)>>,
+    teammate_intercept:
+        LazyCell<Option<(&'a common::halfway_house::PlayerInfo, NaiveIntercept)>>,
+    possession: LazyCell<PossessionDetail>,
     push_wall: LazyCell<Wall>,
     impending_score_conservative: LazyCell<Option<BallFrame>>,
     impending_concede: LazyCell<Option<BallFrame>>,
     enemy_shoot_score_seconds: LazyCell<f32>,
     slightly_panicky_retreat: LazyCell<bool>,
     very_panicky_retreat: LazyCell<bool>,
+    phase: LazyCell<GamePhase>,
+    height_aware_possession: bool,
 }
 
 impl<'a> Scenario<'a> {
@@ -44,7 +115,8 @@ impl<'a> Scenario<'a> {
             ball_predictor,
             ball_prediction: LazyCell::new(),
             me_intercept: LazyCell::new(),
-            enemy_intercept: LazyCell::new(),
+            enemy_intercepts: LazyCell::new(),
+            teammate_intercept: LazyCell::new(),
             possession: LazyCell::new(),
             push_wall: LazyCell::new(),
             impending_concede: LazyCell::new(),
@@ -52,9 +124,20 @@ impl<'a> Scenario<'a> {
             enemy_shoot_score_seconds: LazyCell::new(),
             slightly_panicky_retreat: LazyCell::new(),
             very_panicky_retreat: LazyCell::new(),
+            phase: LazyCell::new(),
+            height_aware_possession: false,
         }
     }
 
+    /// When racing to the ball, don't count frames where the ball is too high
+    /// for a car to reach from the ground, unless that car has the boost to go
+    /// up for it. This makes possession reflect who can actually touch the
+    /// ball, rather than who can get underneath it first.
+    pub fn height_aware_possession(mut self, height_aware_possession: bool) -> Self {
+        self.height_aware_possession = height_aware_possession;
+        self
+    }
+
     pub fn ball_prediction(&self) -> &BallTrajectory {
         self.ball_prediction
             .borrow_with(|| self.ball_predictor.predict(self.packet))
@@ -67,21 +150,70 @@ impl<'a> Scenario<'a> {
         self.me_intercept.borrow().unwrap().as_ref()
     }
 
+    /// The enemy who can reach the ball first, and their intercept.
     pub fn enemy_intercept(
         &self,
     ) -> Option<&(&'a common::halfway_house::PlayerInfo, NaiveIntercept)> {
+        self.enemy_intercepts().first()
+    }
+
+    /// Every enemy who can reach the ball, and their intercepts, soonest
+    /// first.
+    pub fn enemy_intercepts(&self) -> &[(&'a common::halfway_house::PlayerInfo, NaiveIntercept)] {
         if !self.me_intercept.filled() {
             self.race();
         }
-        self.enemy_intercept.borrow().unwrap().as_ref()
+        self.enemy_intercepts.borrow().unwrap()
     }
 
     pub fn primary_enemy(&self) -> Option<&'a common::halfway_house::PlayerInfo> {
         self.enemy_intercept().map(|&(enemy, ref _intercept)| enemy)
     }
 
+    /// The teammate (not counting me) who can reach the ball first, and their
+    /// intercept.
+    pub fn teammate_intercept(
+        &self,
+    ) -> Option<&(&'a common::halfway_house::PlayerInfo, NaiveIntercept)> {
+        self.teammate_intercept
+            .borrow_with(|| {
+                let me = self.game.me();
+                self.game
+                    .cars(self.game.team)
+                    .filter(|&car| !ptr::eq(car, me))
+                    .filter_map(|car| {
+                        simulate_ball_blitz(
+                            self.ball_prediction(),
+                            car,
+                            self.height_aware_possession,
+                        )
+                        .map(|i| (car, i))
+                    })
+                    .min_by_key(|(_car, intercept)| NotNan::new(intercept.time).unwrap())
+            })
+            .as_ref()
+    }
+
+    /// Returns true if none of my teammates can reach the ball before I can.
+    pub fn i_am_first_man(&self) -> bool {
+        let (_teammate, teammate_intercept) = some_or_else!(self.teammate_intercept(), {
+            return true;
+        });
+        match self.me_intercept() {
+            Some(intercept) => intercept.time <= teammate_intercept.time,
+            None => false,
+        }
+    }
+
     /// Number of seconds I can reach the ball before the opponent
     pub fn possession(&self) -> f32 {
+        self.possession_detail().possession
+    }
+
+    /// Like `possession`, but also says how long each side needs to reach the
+    /// ball, so "I have all the time in the world" can be told apart from
+    /// "nobody can get there".
+    pub fn possession_detail(&self) -> PossessionDetail {
         if !self.me_intercept.filled() {
             self.race();
         }
@@ -89,28 +221,48 @@ impl<'a> Scenario<'a> {
     }
 
     fn race(&self) {
-        let blitz_me = simulate_ball_blitz(self.ball_prediction(), self.game.me());
-        let blitz_enemy = self
+        let blitz_me = simulate_ball_blitz(
+            self.ball_prediction(),
+            self.game.me(),
+            self.height_aware_possession,
+        );
+        let mut blitz_enemies: Vec<_> = self
             .game
             .cars(self.game.enemy_team)
-            .map(|enemy| (enemy, simulate_ball_blitz(self.ball_prediction(), enemy)))
+            .map(|enemy| {
+                let intercept = simulate_ball_blitz(
+                    self.ball_prediction(),
+                    enemy,
+                    self.height_aware_possession,
+                );
+                (enemy, intercept)
+            })
             .filter_map(|(enemy, intercept)| intercept.map(|i| (enemy, i)))
-            .min_by_key(|(_enemy, intercept)| NotNan::new(intercept.time).unwrap());
+            .collect();
+        blitz_enemies.sort_by_key(|(_enemy, intercept)| NotNan::new(intercept.time).unwrap());
 
-        let possession = match (&blitz_me, &blitz_enemy) {
-            (Some(me), Some((_, enemy))) => enemy.time - me.time,
-            _ => {
-                // To avoid mexican standoffs, just pretend we have full possession so we go
-                // for the ball.
-                Self::POSSESSION_SATURATED
-            }
-        };
+        let possession = PossessionDetail::new(
+            blitz_me.as_ref().map(|me| me.time),
+            blitz_enemies.first().map(|(_, enemy)| enemy.time),
+        );
 
         self.me_intercept.fill(blitz_me).ok().unwrap();
-        self.enemy_intercept.fill(blitz_enemy).ok().unwrap();
+        self.enemy_intercepts.fill(blitz_enemies).ok().unwrap();
         self.possession.fill(possession).ok().unwrap();
     }
 
+    /// The active full boost pad closest to any car on `for_team`, with its
+    /// state. Pass our own team to find somewhere to refuel, or the enemy team
+    /// to guess where they're headed.
+    pub fn nearest_boost_pad(&self, for_team: Team) -> Option<BoostPadState> {
+        let cars: Vec<_> = self
+            .game
+            .cars(for_team)
+            .map(|car| car.Physics.loc_2d())
+            .collect();
+        nearest_active_dollar(self.game.boost_pads(), &cars)
+    }
+
     /// If I blitz to the ball and hit it straight-on, where will it go?
     pub fn push_wall(&self) -> Wall {
         *self.push_wall.borrow_with(|| {
@@ -124,6 +276,123 @@ impl<'a> Scenario<'a> {
         })
     }
 
+    /// Like `push_wall`, but if the enemy can get to the ball around the same
+    /// time, account for them pushing back. The closer the race, the more
+    /// their push counts.
+    pub fn contested_push_wall(&self) -> Wall {
+        let me_intercept = some_or_else!(self.me_intercept(), {
+            return self.push_wall();
+        });
+        let (enemy, enemy_intercept) = some_or_else!(self.enemy_intercept(), {
+            return self.push_wall();
+        });
+        let point = some_or_else!(
+            contested_push_point(
+                self.game.me().Physics.loc_2d(),
+                me_intercept.ball_loc.to_2d(),
+                enemy.Physics.loc_2d(),
+                enemy_intercept.ball_loc.to_2d(),
+                self.possession(),
+            ),
+            {
+                return self.push_wall();
+            }
+        );
+        WallRayCalculator::wall_for_point(self.game, point)
+    }
+
+    /// If the ball is sitting against a wall, returns which one. The ball won't
+    /// bounce off the wall in this case, so bounce-based logic doesn't apply.
+    pub fn ball_against_wall(&self) -> Option<Wall> {
+        const MAX_BALL_SPEED: f32 = 300.0;
+        const MARGIN: f32 = 50.0;
+
+        let ball = &self.packet.GameBall.Physics;
+        if ball.vel().norm() >= MAX_BALL_SPEED {
+            return None;
+        }
+
+        let ball_loc = ball.loc_2d();
+        let max_x = self.game.field_max_x() - self.game.ball_radius() - MARGIN;
+        let max_y = self.game.field_max_y() - self.game.ball_radius() - MARGIN;
+        if ball_loc.x.abs() < max_x && ball_loc.y.abs() < max_y {
+            return None;
+        }
+
+        Some(WallRayCalculator::wall_for_point(self.game, ball_loc))
+    }
+
+    /// If the ball is about to run up a wall and into the ceiling, returns the
+    /// frame where it gets pinched into the corner between them. That's a
+    /// chance for a ceiling pinch, but it's a big commitment, so this only
+    /// fires when the setup is clearly in our favor.
+    pub fn ceiling_opportunity(&self) -> Option<&BallFrame> {
+        const MIN_BOOST: i32 = 60;
+
+        if self.game.me().Boost < MIN_BOOST || self.possession() < Self::POSSESSION_CONTESTABLE {
+            return None;
+        }
+        find_ceiling_pinch(self.ball_prediction(), self.game.pitch())
+    }
+
+    /// If some car (friend or foe) is carrying the ball on its roof, returns
+    /// that car. The ball prediction assumes free flight, so it's not to be
+    /// trusted in this case.
+    pub fn ball_is_controlled_by(&self) -> Option<&'a common::halfway_house::PlayerInfo> {
+        let ball = &self.packet.GameBall.Physics;
+        self.packet.cars().find(|car| is_carrying(car, ball))
+    }
+
+    /// Are we sitting with our nose jammed into a wall? This only looks at the
+    /// current tick. If it stays true for a while, we're stuck, and driving
+    /// forward won't get us out.
+    pub fn am_i_stuck(&self) -> bool {
+        is_wedged(self.game.pitch(), self.game.me())
+    }
+
+    /// Roughly which phase of play we're in, judging by possession and where
+    /// the ball is.
+    pub fn phase(&self) -> GamePhase {
+        *self.phase.borrow_with(|| {
+            let kickoff = PreKickoff::is_kickoff(&self.packet.GameBall);
+            let ball_y = self.packet.GameBall.Physics.loc().y;
+            let forward = self.game.enemy_goal().center_2d.y.signum();
+            classify_phase(kickoff, self.possession(), ball_y * forward)
+        })
+    }
+
+    /// If we shoot from `shot_from` after `shot_time` seconds, and the ball
+    /// gets to `shot_target` after `arrival_time` seconds, can the primary
+    /// enemy get in the way and save it?
+    pub fn enemy_can_save(
+        &self,
+        shot_from: Point2<f32>,
+        shot_time: f32,
+        shot_target: Point2<f32>,
+        arrival_time: f32,
+    ) -> bool {
+        match self.primary_enemy() {
+            Some(enemy) => can_reach_shot(enemy, shot_from, shot_time, shot_target, arrival_time),
+            None => false,
+        }
+    }
+
+    /// A rough score in `[0, 1]` for how likely a shot at `shot_target` from
+    /// our intercept is to go in. It accounts for the angle we'd be shooting
+    /// from, how far the ball has to travel, whether the enemy can get there
+    /// to save it, and whether we'll even get to the ball first.
+    pub fn shot_quality(&self, shot_target: Point2<f32>) -> f32 {
+        let intercept = some_or_else!(self.me_intercept(), {
+            return 0.0;
+        });
+        let ball_loc = intercept.ball_loc.to_2d();
+        let shot_angle = self.game.enemy_goal().shot_angle_2d(ball_loc);
+        let shot_dist = (shot_target - ball_loc).norm();
+        let arrival_time = intercept.time + shot_dist / TYPICAL_SHOT_SPEED;
+        let saveable = self.enemy_can_save(ball_loc, intercept.time, shot_target, arrival_time);
+        score_shot(shot_angle, shot_dist, saveable, self.possession())
+    }
+
     /// If nobody touches the ball, will it end up in the enemy goal?
     pub fn impending_score_conservative(&self) -> Option<&BallFrame> {
         self.impending_score_conservative
@@ -182,6 +451,19 @@ impl<'a> Scenario<'a> {
         })
     }
 
+    /// If we follow `path`, will we bump into the ball along the way? Returns
+    /// the time of the first contact, relative to the start of the path.
+    pub fn will_i_hit_ball_incidentally(&self, path: &dyn SegmentPlan) -> Option<f32> {
+        let contact_dist = self.game.ball_radius() + INCIDENTAL_CONTACT_MARGIN;
+        let start_time = self.ball_prediction().start().t;
+        self.ball_prediction()
+            .iter_step_by(0.125)
+            .map(|ball| (ball.t - start_time, ball.loc))
+            .take_while(|&(t, _)| t <= path.duration())
+            .find(|&(t, ball_loc)| (path.position_at(t) - ball_loc).norm() < contact_dist)
+            .map(|(t, _)| t)
+    }
+
     /// Is the ball and everyone around it moving towards our goal?
     pub fn slightly_panicky_retreat(&self) -> bool {
         *self.slightly_panicky_retreat.borrow_with(|| {
@@ -243,6 +525,169 @@ impl<'a> Scenario<'a> {
     }
 }
 
+/// Combine the factors behind `Scenario::shot_quality` into one score.
+fn score_shot(shot_angle: f32, shot_dist: f32, saveable: bool, possession: f32) -> f32 {
+    // Straight on is ideal. By 45° the goal mouth looks pretty narrow, and
+    // from the side there's no shot at all.
+    let angle = linear_interpolate(&[0.0, PI / 4.0, PI / 2.0], &[1.0, 0.6, 0.0], shot_angle);
+    // Long shots give the defense time to react, even if they look open now.
+    let dist = linear_interpolate(&[1500.0, 6000.0], &[1.0, 0.3], shot_dist);
+    let keeper = if saveable { 0.3 } else { 1.0 };
+    // If the enemy gets to the ball first, we probably won't get the shot off.
+    let possession = linear_interpolate(
+        &[
+            -Scenario::POSSESSION_CONTESTABLE,
+            Scenario::POSSESSION_CONTESTABLE,
+        ],
+        &[0.3, 1.0],
+        possession,
+    );
+    angle * dist * keeper * possession
+}
+
+/// Where the ball ends up if both cars hit it at once. Each car pushes the ball
+/// along its line of approach, weighted by who gets there first. Returns `None`
+/// if the pushes cancel out, since then nobody can say where the ball goes.
+fn contested_push_point(
+    me_loc: Point2<f32>,
+    me_ball_loc: Point2<f32>,
+    enemy_loc: Point2<f32>,
+    enemy_ball_loc: Point2<f32>,
+    possession: f32,
+) -> Option<Point2<f32>> {
+    let me_push = (me_ball_loc - me_loc).try_normalize(1e-3)?;
+    let enemy_push = (enemy_ball_loc - enemy_loc).try_normalize(1e-3)?;
+    let me_weight = linear_interpolate(
+        &[
+            -Scenario::POSSESSION_CONTESTABLE,
+            Scenario::POSSESSION_CONTESTABLE,
+        ],
+        &[0.0, 1.0],
+        possession,
+    );
+    let push = (me_push * me_weight + enemy_push * (1.0 - me_weight)).try_normalize(0.1)?;
+    Some(WallRayCalculator::calculate(
+        me_ball_loc,
+        me_ball_loc + push,
+    ))
+}
+
+/// Can `car` get within reach of the shot's path before the ball goes by?
+/// The ball leaves `shot_from` after `shot_time` seconds, and travels in a
+/// straight line at a steady speed to reach `shot_target` after
+/// `arrival_time` seconds.
+fn can_reach_shot(
+    car: &common::halfway_house::PlayerInfo,
+    shot_from: Point2<f32>,
+    shot_time: f32,
+    shot_target: Point2<f32>,
+    arrival_time: f32,
+) -> bool {
+    // Check the point where the ball passes closest to the car, as well as the
+    // target itself.
+    let shot = shot_target - shot_from;
+    let u = if shot.norm() >= 1.0 {
+        let u = (car.Physics.loc_2d() - shot_from).dot(&shot) / shot.norm_squared();
+        nalgebra::clamp(u, 0.0, 1.0)
+    } else {
+        1.0
+    };
+    let block_loc = shot_from + shot * u;
+    let block_time = shot_time + (arrival_time - shot_time) * u;
+    can_reach_before(car, block_loc, block_time) || can_reach_before(car, shot_target, arrival_time)
+}
+
+/// Can `car` get within reach of `loc` within `time` seconds?
+fn can_reach_before(car: &common::halfway_house::PlayerInfo, loc: Point2<f32>, time: f32) -> bool {
+    let car_to_loc = loc - car.Physics.loc_2d();
+    if car_to_loc.norm() <= SAVE_REACH {
+        return true;
+    }
+    let save_loc = loc - car_to_loc.normalize() * SAVE_REACH;
+    rough_time_drive_to_loc(car, save_loc) < time
+}
+
+/// Is `car` on the ground, barely moving, and pointed into a wall it's
+/// touching?
+fn is_wedged(pitch: &Pitch, car: &common::halfway_house::PlayerInfo) -> bool {
+    // The distance from the car's center to its front bumper, plus some slack.
+    const MAX_WALL_DIST: f32 = 150.0;
+    const MAX_SPEED: f32 = 100.0;
+    // How directly the nose has to point into the wall.
+    const MIN_FACING: f32 = 0.7;
+
+    let loc = car.Physics.loc();
+    let wall = pitch.closest_wall(&loc);
+    car.OnGround
+        && car.Physics.vel().norm() < MAX_SPEED
+        && wall.distance_to_point(&loc) < MAX_WALL_DIST
+        && car.Physics.forward_axis().dot(&wall.normal) < -MIN_FACING
+}
+
+/// Find the first frame where the ball, on its way up, is tucked into the
+/// corner between a wall and the ceiling.
+fn find_ceiling_pinch<'p>(
+    ball_prediction: &'p BallTrajectory,
+    pitch: &Pitch,
+) -> Option<&'p BallFrame> {
+    // Past this, the prediction is too shaky to plan a pinch around.
+    const MAX_TIME: f32 = 3.0;
+    // How close the ball's surface needs to be to each surface.
+    const MARGIN: f32 = 150.0;
+    // A ball that's barely rising won't be pinched hard enough to matter.
+    const MIN_VEL_Z: f32 = 300.0;
+
+    ball_prediction
+        .iter()
+        .take_while(|f| f.t < MAX_TIME)
+        .find(|f| {
+            let ceiling_dist = rl::FIELD_MAX_Z - f.loc.z - rl::BALL_RADIUS;
+            let wall_dist = pitch.closest_wall(&f.loc).distance_to_point(&f.loc) - rl::BALL_RADIUS;
+            f.vel.z >= MIN_VEL_Z && ceiling_dist < MARGIN && wall_dist < MARGIN
+        })
+}
+
+/// Is the ball resting on top of `car` and moving along with it?
+fn is_carrying(
+    car: &common::halfway_house::PlayerInfo,
+    ball: &common::halfway_house::Physics,
+) -> bool {
+    // Measured from the car's center. An Octane's roof is about 40uu above its
+    // center, so the ball's center sits around 130uu up when it's resting there.
+    const MIN_HEIGHT: f32 = 100.0;
+    const MAX_HEIGHT: f32 = 200.0;
+    // The hitbox is offset towards the front of the car.
+    const MIN_FORWARD: f32 = -60.0;
+    const MAX_FORWARD: f32 = 90.0;
+    const MAX_SIDEWAYS: f32 = 60.0;
+    // A ball bouncing off the roof isn't being carried.
+    const MAX_RELATIVE_SPEED: f32 = 500.0;
+
+    let car_to_ball = ball.loc() - car.Physics.loc();
+    let height = car_to_ball.dot(&car.Physics.roof_axis());
+    let forward = car_to_ball.dot(&car.Physics.forward_axis());
+    let sideways = car_to_ball.dot(&car.Physics.right_axis());
+    let relative_speed = (ball.vel() - car.Physics.vel()).norm();
+    (MIN_HEIGHT..=MAX_HEIGHT).contains(&height)
+        && (MIN_FORWARD..=MAX_FORWARD).contains(&forward)
+        && sideways.abs() <= MAX_SIDEWAYS
+        && relative_speed < MAX_RELATIVE_SPEED
+}
+
+/// `ball_progress` is the ball's distance past midfield, towards the enemy
+/// goal (so it's negative in our half).
+fn classify_phase(kickoff: bool, possession: f32, ball_progress: f32) -> GamePhase {
+    if kickoff {
+        GamePhase::Kickoff
+    } else if possession >= Scenario::POSSESSION_CONTESTABLE && ball_progress >= 0.0 {
+        GamePhase::Attacking
+    } else if possession <= -Scenario::POSSESSION_CONTESTABLE && ball_progress <= 0.0 {
+        GamePhase::Defending
+    } else {
+        GamePhase::Open
+    }
+}
+
 fn blitz_start(car: &common::halfway_house::PlayerInfo, ball_prediction: &BallTrajectory) -> Car1D {
     let ball_loc = ball_prediction.start().loc.to_2d();
     let ball_vel = ball_prediction.start().vel.to_2d();
@@ -256,15 +701,25 @@ fn blitz_start(car: &common::halfway_house::PlayerInfo, ball_prediction: &BallTr
 }
 
 // Basically simulate a "race to the ball" (poorly) and guesstimate where our
-// first possible intercept might be.
+// first possible intercept might be. If `height_aware`, skip over frames where
+// the ball is out of the car's reach.
 fn simulate_ball_blitz(
     ball_prediction: &BallTrajectory,
     car: &common::halfway_house::PlayerInfo,
+    height_aware: bool,
 ) -> Option<NaiveIntercept> {
     let mut sim = blitz_start(car, ball_prediction);
     let mut naive_result = None;
+    let can_aerial = car.Boost as f32 >= AERIAL_MIN_BOOST;
 
     for ball in ball_prediction.iter_step_by(0.125) {
+        if !ball.is_in_bounds() {
+            break;
+        }
+        if height_aware && !can_aerial && ball.loc.z >= GroundedHit::MAX_BALL_Z {
+            sim.advance(ball.dt(), 1.0, true);
+            continue;
+        }
         let dist_to_ball = (car.Physics.loc() - ball.loc).to_2d().norm();
         if sim.distance() >= dist_to_ball {
             naive_result = Some(ball);
@@ -285,3 +740,413 @@ fn simulate_ball_blitz(
         data: (),
     })
 }
+
+/// The active full-boost pad closest to any of `cars`.
+fn nearest_active_dollar(
+    pads: impl Iterator<Item = BoostPadState>,
+    cars: &[Point2<f32>],
+) -> Option<BoostPadState> {
+    pads.filter(|pad| pad.is_full_boost && pad.is_active)
+        .filter_map(|pad| {
+            let dist = cars
+                .iter()
+                .map(|&car| NotNan::new((pad.loc - car).norm()).unwrap())
+                .min()?;
+            Some((pad, dist))
+        })
+        .min_by_key(|&(_, dist)| dist)
+        .map(|(pad, _)| pad)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::ball::{BallFrame, BallTrajectory},
+        strategy::{
+            pitch::DFH_STADIUM,
+            scenario::{
+                can_reach_shot, classify_phase, contested_push_point, find_ceiling_pinch,
+                is_carrying, is_wedged, nearest_active_dollar, score_shot, simulate_ball_blitz,
+                GamePhase, PossessionDetail, Scenario,
+            },
+            BoostPadState,
+        },
+    };
+    use common::{
+        halfway_house::{Physics, PlayerInfo, Vector3},
+        rl,
+    };
+    use nalgebra::Point2;
+    use std::f32::consts::PI;
+
+    fn pad(x: f32, y: f32, is_full_boost: bool, is_active: bool) -> BoostPadState {
+        BoostPadState {
+            loc: Point2::new(x, y),
+            is_full_boost,
+            is_active,
+            respawn_time: if is_active { 0.0 } else { 5.0 },
+        }
+    }
+
+    #[test]
+    fn nearest_active_dollar_skips_taken_and_small_pads() {
+        let pads = vec![
+            pad(-3072.0, -4096.0, true, false),
+            pad(-1000.0, -2000.0, false, true),
+            pad(3072.0, -4096.0, true, true),
+            pad(-3584.0, 0.0, true, true),
+        ];
+        let cars = [Point2::new(-2500.0, -3500.0)];
+        let nearest = nearest_active_dollar(pads.into_iter(), &cars).unwrap();
+        assert_eq!(nearest.loc, Point2::new(-3584.0, 0.0));
+    }
+
+    #[test]
+    fn nearest_active_dollar_without_cars() {
+        let pads = vec![pad(3072.0, -4096.0, true, true)];
+        assert!(nearest_active_dollar(pads.into_iter(), &[]).is_none());
+    }
+
+    #[test]
+    fn possession_detail() {
+        let detail = PossessionDetail::new(Some(1.0), Some(2.5));
+        assert_eq!(detail.possession, 1.5);
+
+        // Whoever can't reach the ball, possession saturates, but the detail
+        // still says who it was.
+        let me_stranded = PossessionDetail::new(None, Some(1.0));
+        let enemy_stranded = PossessionDetail::new(Some(1.0), None);
+        assert_eq!(me_stranded.possession, Scenario::POSSESSION_SATURATED);
+        assert_eq!(enemy_stranded.possession, Scenario::POSSESSION_SATURATED);
+        assert_eq!(me_stranded.me_time, None);
+        assert_eq!(enemy_stranded.enemy_time, None);
+        assert_eq!(enemy_stranded.me_time, Some(1.0));
+    }
+
+    #[test]
+    fn contested_push_point_bends_towards_enemy_push() {
+        // I'm driving straight upfield. The enemy comes in from the side.
+        let me_loc = Point2::new(0.0, -1000.0);
+        let enemy_loc = Point2::new(-1000.0, 0.0);
+        let ball_loc = Point2::origin();
+
+        // If I'm way ahead, the ball goes straight.
+        let point = contested_push_point(me_loc, ball_loc, enemy_loc, ball_loc, 5.0).unwrap();
+        assert!(point.x.abs() < 1.0);
+        assert!((point.y - rl::FIELD_MAX_Y).abs() < 1.0);
+
+        // In a close race, the enemy bends the ball towards their side.
+        let point = contested_push_point(me_loc, ball_loc, enemy_loc, ball_loc, 0.0).unwrap();
+        assert!(point.x >= 1000.0);
+
+        // If they're way ahead, it goes where they send it.
+        let point = contested_push_point(me_loc, ball_loc, enemy_loc, ball_loc, -5.0).unwrap();
+        assert!((point.x - rl::FIELD_MAX_X).abs() < 1.0);
+        assert!(point.y.abs() < 1.0);
+    }
+
+    #[test]
+    fn contested_push_point_head_on() {
+        let ball_loc = Point2::origin();
+        let point = contested_push_point(
+            Point2::new(0.0, -1000.0),
+            ball_loc,
+            Point2::new(0.0, 1000.0),
+            ball_loc,
+            0.0,
+        );
+        assert!(point.is_none());
+    }
+
+    #[test]
+    fn kickoff_trumps_everything() {
+        assert_eq!(classify_phase(true, 5.0, 0.0), GamePhase::Kickoff);
+        assert_eq!(classify_phase(true, -5.0, 0.0), GamePhase::Kickoff);
+    }
+
+    #[test]
+    fn attacking_and_defending() {
+        assert_eq!(classify_phase(false, 2.0, 1000.0), GamePhase::Attacking);
+        assert_eq!(classify_phase(false, -2.0, -1000.0), GamePhase::Defending);
+    }
+
+    #[test]
+    fn open_play() {
+        // Contested ball.
+        assert_eq!(classify_phase(false, 0.0, 1000.0), GamePhase::Open);
+        assert_eq!(classify_phase(false, 0.0, -1000.0), GamePhase::Open);
+        // Possession, but the ball is at the wrong end of the field.
+        assert_eq!(classify_phase(false, 2.0, -1000.0), GamePhase::Open);
+        assert_eq!(classify_phase(false, -2.0, 1000.0), GamePhase::Open);
+    }
+
+    fn keeper() -> PlayerInfo {
+        let mut car = PlayerInfo::default();
+        car.Physics.Location.Y = rl::FIELD_MAX_Y - 100.0;
+        car.Physics.Rotation.Yaw = -PI / 2.0;
+        car.Boost = 33;
+        car
+    }
+
+    #[test]
+    fn keeper_saves_center_shot() {
+        let from = Point2::origin();
+        let target = Point2::new(0.0, rl::FIELD_MAX_Y);
+        assert!(can_reach_shot(&keeper(), from, 0.0, target, 0.5));
+    }
+
+    #[test]
+    fn keeper_misses_far_post_shot() {
+        let from = Point2::origin();
+        let target = Point2::new(rl::GOALPOST_X - 100.0, rl::FIELD_MAX_Y);
+        assert!(!can_reach_shot(&keeper(), from, 0.0, target, 0.5));
+        // Given enough time, they'd get there.
+        assert!(can_reach_shot(&keeper(), from, 0.0, target, 3.0));
+    }
+
+    #[test]
+    fn defender_blocks_shot_in_transit() {
+        // The defender is nowhere near the goal, but they're sitting right in
+        // the path of a long cross-field shot.
+        let mut defender = keeper();
+        defender.Physics.Location.X = -1500.0;
+        defender.Physics.Location.Y = 2500.0;
+        let from = Point2::new(-3000.0, 0.0);
+        let target = Point2::new(0.0, rl::FIELD_MAX_Y);
+        assert!(can_reach_shot(&defender, from, 0.5, target, 1.5));
+        // A shot that stays well clear of them gets past.
+        let from = Point2::new(3000.0, 0.0);
+        assert!(!can_reach_shot(&defender, from, 0.5, target, 1.5));
+    }
+
+    fn carrier() -> PlayerInfo {
+        let mut car = PlayerInfo::default();
+        car.Team = 1;
+        car.Physics.Location.Z = rl::OCTANE_NEUTRAL_Z;
+        car.Physics.Velocity.X = 1000.0;
+        car
+    }
+
+    fn ball(x: f32, z: f32, vel_x: f32) -> Physics {
+        Physics {
+            Location: Vector3 { X: x, Y: 0.0, Z: z },
+            Velocity: Vector3 {
+                X: vel_x,
+                Y: 0.0,
+                Z: 0.0,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn enemy_carrying_ball() {
+        let car = carrier();
+        assert!(is_carrying(&car, &ball(20.0, 150.0, 1000.0)));
+    }
+
+    #[test]
+    fn ball_not_on_roof() {
+        let car = carrier();
+        // In front of the car.
+        assert!(!is_carrying(&car, &ball(200.0, 92.74, 1000.0)));
+        // Above the roof, but on its way somewhere else.
+        assert!(!is_carrying(&car, &ball(20.0, 150.0, -500.0)));
+    }
+
+    /// The ball drops straight down from high above center field.
+    fn falling_ball() -> BallTrajectory {
+        let frames = (0..600)
+            .map(|i| {
+                let t = i as f32 / 120.0;
+                let z = (1500.0 - 325.0 * t * t).max(92.74);
+                let vel_z = if z > 92.74 { -650.0 * t } else { 0.0 };
+                BallFrame::new(
+                    t,
+                    1.0 / 120.0,
+                    nalgebra::Point3::new(0.0, 0.0, z),
+                    nalgebra::Vector3::new(0.0, 0.0, vel_z),
+                )
+            })
+            .collect();
+        BallTrajectory::new(frames)
+    }
+
+    fn grounded_car(y: f32, yaw: f32) -> PlayerInfo {
+        let mut car = PlayerInfo::default();
+        car.Physics.Location.Y = y;
+        car.Physics.Location.Z = rl::OCTANE_NEUTRAL_Z;
+        car.Physics.Rotation.Yaw = yaw;
+        car.OnGround = true;
+        car
+    }
+
+    #[test]
+    fn open_shot_from_in_front_is_good() {
+        let quality = score_shot(0.1, 1500.0, false, 2.0);
+        assert!(quality >= 0.9);
+    }
+
+    #[test]
+    fn shot_from_the_side_is_hopeless() {
+        let quality = score_shot(PI * 0.45, 3000.0, false, 2.0);
+        assert!(quality < 0.2);
+    }
+
+    #[test]
+    fn contested_or_saveable_shots_are_worse() {
+        let open = score_shot(0.3, 3000.0, false, 2.0);
+        assert!(score_shot(0.3, 3000.0, true, 2.0) < open);
+        assert!(score_shot(0.3, 3000.0, false, 0.0) < open);
+        assert!(score_shot(0.3, 3000.0, false, -2.0) < score_shot(0.3, 3000.0, false, 0.0));
+    }
+
+    /// The ball rolls up the right side wall and keeps climbing.
+    fn ball_up_the_wall(vel_z: f32) -> BallTrajectory {
+        let x = rl::FIELD_MAX_X - rl::BALL_RADIUS;
+        let frames = (0..360)
+            .map(|i| {
+                let t = i as f32 / 120.0;
+                let z = (500.0 + vel_z * t).min(rl::FIELD_MAX_Z - rl::BALL_RADIUS);
+                BallFrame::new(
+                    t,
+                    1.0 / 120.0,
+                    nalgebra::Point3::new(x, 1000.0, z),
+                    nalgebra::Vector3::new(0.0, 0.0, vel_z),
+                )
+            })
+            .collect();
+        BallTrajectory::new(frames)
+    }
+
+    #[test]
+    fn ceiling_pinch_up_the_wall() {
+        let ball = ball_up_the_wall(1200.0);
+        let frame = find_ceiling_pinch(&ball, &DFH_STADIUM).unwrap();
+        assert!(frame.t > 1.0 && frame.t < 1.5, "t = {:.2}", frame.t);
+        assert!(
+            frame.loc.z >= rl::FIELD_MAX_Z - rl::BALL_RADIUS - 150.0,
+            "loc = {:?}",
+            frame.loc,
+        );
+    }
+
+    #[test]
+    fn no_ceiling_pinch_for_slow_ball() {
+        // It creeps up the wall so slowly that it never gets there.
+        let ball = ball_up_the_wall(200.0);
+        assert!(find_ceiling_pinch(&ball, &DFH_STADIUM).is_none());
+    }
+
+    #[test]
+    fn nose_into_wall_is_wedged() {
+        let mut car = grounded_car(0.0, 0.0);
+        car.Physics.Location.X = rl::FIELD_MAX_X - 100.0;
+        assert!(is_wedged(&DFH_STADIUM, &car));
+    }
+
+    #[test]
+    fn not_wedged_when_facing_away_or_moving() {
+        let mut car = grounded_car(0.0, PI);
+        car.Physics.Location.X = rl::FIELD_MAX_X - 100.0;
+        assert!(!is_wedged(&DFH_STADIUM, &car));
+
+        let mut car = grounded_car(0.0, 0.0);
+        car.Physics.Location.X = rl::FIELD_MAX_X - 100.0;
+        car.Physics.Velocity.Y = 1000.0;
+        assert!(!is_wedged(&DFH_STADIUM, &car));
+
+        let car = grounded_car(0.0, 0.0);
+        assert!(!is_wedged(&DFH_STADIUM, &car));
+    }
+
+    fn possession(height_aware: bool) -> f32 {
+        let ball = falling_ball();
+        let me = grounded_car(-800.0, PI / 2.0);
+        let enemy = grounded_car(1600.0, -PI / 2.0);
+        let me = simulate_ball_blitz(&ball, &me, height_aware).unwrap();
+        let enemy = simulate_ball_blitz(&ball, &enemy, height_aware).unwrap();
+        enemy.time - me.time
+    }
+
+    #[test]
+    fn high_ball_is_contested_when_height_aware() {
+        // Naively, whoever is closer gets there first.
+        assert!(possession(false) >= Scenario::POSSESSION_CONTESTABLE);
+        // But neither car can touch the ball until it comes down.
+        assert!(possession(true).abs() < Scenario::POSSESSION_CONTESTABLE);
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        integration_tests::{TestRunner, TestScenario},
+        strategy::{Action, Behavior, Context},
+        utils::Wall,
+    };
+    use common::halfway_house::PlayerInfo;
+    use nalgebra::{Point3, Vector3};
+    use nameof::name_of_type;
+    use std::sync::{Arc, Mutex};
+
+    struct RecordBallAgainstWall(Arc<Mutex<Option<Wall>>>);
+
+    impl Behavior for RecordBallAgainstWall {
+        fn name(&self) -> &str {
+            name_of_type!(RecordBallAgainstWall)
+        }
+
+        fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+            *self.0.lock().unwrap() = ctx.scenario.ball_against_wall();
+            Action::Yield(Default::default())
+        }
+    }
+
+    #[test]
+    fn ball_resting_against_side_wall() {
+        let wall = Arc::new(Mutex::new(None));
+        let _test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(4000.0, 0.0, 92.74),
+                ball_vel: Vector3::zeros(),
+                car_loc: Point3::new(0.0, -2000.0, 17.01),
+                ..Default::default()
+            })
+            .behavior(RecordBallAgainstWall(wall.clone()))
+            .run_for_millis(500);
+
+        assert_eq!(*wall.lock().unwrap(), Some(Wall::Midfield));
+    }
+
+    struct RecordBallCarrier(Arc<Mutex<Option<PlayerInfo>>>);
+
+    impl Behavior for RecordBallCarrier {
+        fn name(&self) -> &str {
+            name_of_type!(RecordBallCarrier)
+        }
+
+        fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+            *self.0.lock().unwrap() = ctx.scenario.ball_is_controlled_by().cloned();
+            Action::Yield(Default::default())
+        }
+    }
+
+    #[test]
+    fn enemy_carrying_ball() {
+        let carrier = Arc::new(Mutex::new(None));
+        let _test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, 2000.0, 150.0),
+                ball_vel: Vector3::zeros(),
+                car_loc: Point3::new(0.0, -2000.0, 17.01),
+                enemy_loc: Point3::new(0.0, 2000.0, 17.01),
+                ..Default::default()
+            })
+            .behavior(RecordBallCarrier(carrier.clone()))
+            .run_for_ticks(12);
+
+        let carrier = carrier.lock().unwrap();
+        assert_eq!(carrier.map(|car| car.Team), Some(1));
+    }
+}
@@ -0,0 +1,81 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+};
+
+/// A place for behaviors and helpers to stash values they've computed, so
+/// others can reuse them later in the same tick. A fresh one is created every
+/// tick, so nothing carries over.
+///
+/// Values are keyed by their type. To store something, give it a newtype
+/// whose name says what it is, e.g. `struct DesiredHitDir(Vector2<f32>)`.
+///
+/// This is like the `LazyCell`s in `Scenario`, but for values that don't
+/// belong there.
+pub struct Scratch {
+    values: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl Scratch {
+    pub fn new() -> Self {
+        Self {
+            values: HashMap::new(),
+        }
+    }
+
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .map(|value| value.downcast_ref().unwrap())
+    }
+
+    pub fn insert<T: Any>(&mut self, value: T) {
+        self.values.insert(TypeId::of::<T>(), Box::new(value));
+    }
+
+    /// Returns the stored value of type `T`, calling `f` to compute it if this
+    /// is the first time anyone has asked this tick. If computing it needs the
+    /// `Context` itself, use `get` and `insert` instead, to keep the borrow
+    /// checker happy.
+    pub fn get_or_insert_with<T: Any>(&mut self, f: impl FnOnce() -> T) -> &T {
+        self.values
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(f()))
+            .downcast_ref()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::scratch::Scratch;
+
+    struct Answer(i32);
+
+    struct OtherAnswer(i32);
+
+    #[test]
+    fn compute_once_and_reuse() {
+        let mut scratch = Scratch::new();
+        let mut calls = 0;
+        let mut compute = || {
+            calls += 1;
+            Answer(42)
+        };
+
+        assert_eq!(scratch.get_or_insert_with(&mut compute).0, 42);
+        assert_eq!(scratch.get_or_insert_with(&mut compute).0, 42);
+        assert_eq!(scratch.get::<Answer>().unwrap().0, 42);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn keyed_by_type() {
+        let mut scratch = Scratch::new();
+        scratch.insert(Answer(1));
+        assert!(scratch.get::<OtherAnswer>().is_none());
+        scratch.insert(OtherAnswer(2));
+        assert_eq!(scratch.get::<Answer>().unwrap().0, 1);
+        assert_eq!(scratch.get::<OtherAnswer>().unwrap().0, 2);
+    }
+}
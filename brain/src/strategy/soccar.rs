@@ -2,27 +2,73 @@ use crate::{
     behavior::{
         defense::Defense,
         higher_order::{Chain, Predicate, TryChoose, While},
-        movement::{GetToFlatGround, Land, Yielder},
+        movement::{GetToFlatGround, HoldPosition, Land, Unstick, Yielder},
         offense::Offense,
-        strike::{FiftyFifty, WallHit},
+        strike::{FiftyFifty, GroundedHitTarget, WallHit},
         taunt::{PodiumBlastoff, PodiumSpew, SaltWhileDemolished, TurtleSpin},
         PreKickoff,
     },
+    eeg::Event,
     routing::{
         behavior::FollowRoute,
         plan::WallIntercept,
-        recover::{IsSkidding, MatchIsEnded, RoundIsNotActive},
+        recover::{IsSkidding, MatchIsEnded, RoundIsNotActive, WeDontWinTheRace},
     },
     strategy::{scenario::Scenario, strategy::Strategy, Behavior, Context, Priority},
     utils::Wall,
 };
 use common::prelude::*;
-use derive_new::new;
 use nameof::name_of_type;
 use vec_box::vec_box;
 
-#[derive(new)]
-pub struct Soccar;
+pub struct Soccar {
+    max_shot_z: f32,
+    /// The game time when we first found ourselves wedged against a wall with
+    /// the throttle down, if we still are.
+    stuck_since: Option<f32>,
+    /// How many ticks in a row we've spent in that state.
+    stuck_ticks: u32,
+}
+
+impl Soccar {
+    /// If we've been wedged against a wall this long, assume whatever we're
+    /// doing isn't going to get us out.
+    const STUCK_TIME: f32 = 0.5;
+
+    /// Also require this many ticks, so a single hitch in the frame rate can't
+    /// make a brief bump look like we've been pinned for a while.
+    const STUCK_TICKS: u32 = 30;
+
+    pub fn new() -> Self {
+        Self {
+            max_shot_z: GroundedHitTarget::MAX_BALL_Z,
+            stuck_since: None,
+            stuck_ticks: 0,
+        }
+    }
+
+    /// Cap how high a ball we'll leave the ground for when shooting. Anything
+    /// higher gets played once it comes back down. Lower caps mean fewer
+    /// risky commitments, at the cost of slower shots.
+    pub fn max_shot_z(mut self, max_shot_z: f32) -> Self {
+        self.max_shot_z = max_shot_z;
+        self
+    }
+
+    /// Record whether we're pushing into a wall this tick, and return true
+    /// once we've been doing so, without getting anywhere, for long enough to
+    /// call it stuck.
+    fn update_stuck(&mut self, now: f32, pushing: bool) -> bool {
+        if !pushing {
+            self.stuck_since = None;
+            self.stuck_ticks = 0;
+            return false;
+        }
+        let since = *self.stuck_since.get_or_insert(now);
+        self.stuck_ticks += 1;
+        now - since >= Self::STUCK_TIME && self.stuck_ticks >= Self::STUCK_TICKS
+    }
+}
 
 impl Strategy for Soccar {
     fn baseline(&mut self, ctx: &mut Context<'_>) -> Box<dyn Behavior> {
@@ -67,7 +113,19 @@ impl Strategy for Soccar {
             return Box::new(Defense::new());
         }
 
-        Box::new(Offense::new())
+        // The enemy will get there first, but we're already goal-side. Chasing
+        // would only give up that position, so wait for a better chance.
+        if ctx.scenario.possession() < -Scenario::POSSESSION_CONTESTABLE
+            && Defense::is_between_ball_and_own_goal(ctx.game, ctx.me(), ctx.scenario)
+        {
+            ctx.eeg
+                .log(name_of_type!(Soccar), "out-possessed, holding position");
+            ctx.eeg.track(Event::HoldPosition);
+            let spot = ctx.me().Physics.loc_2d();
+            return Box::new(While::new(WeDontWinTheRace, HoldPosition::at(spot)));
+        }
+
+        Box::new(Offense::new().max_shot_z(self.max_shot_z))
     }
 
     fn interrupt(
@@ -88,6 +146,19 @@ impl Strategy for Soccar {
             return None;
         }
 
+        // Only count it if we're actually trying to drive forward. Parking
+        // against a wall on purpose is fine.
+        let pushing = ctx.scenario.am_i_stuck() && ctx.last_input.Throttle > 0.0;
+        let stuck = self.update_stuck(ctx.packet.GameInfo.TimeSeconds, pushing);
+        if current.priority() < Priority::Force && stuck {
+            ctx.eeg.log(name_of_type!(Soccar), "stuck against a wall");
+            self.stuck_since = None;
+            self.stuck_ticks = 0;
+            return Some(Box::new(Chain::new(Priority::Force, vec![Box::new(
+                Unstick::new(),
+            )])));
+        }
+
         // Force kickoff behavior. We can't rely on the normal routing, because it
         // doesn't account for boost pads that you pick up on the way, so it dodges and
         // goes too slow.
@@ -236,6 +307,35 @@ fn commanding_lead(ctx: &mut Context<'_>) -> bool {
     us - them >= minutes_remaining
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::strategy::Soccar;
+
+    const DT: f32 = 1.0 / 120.0;
+
+    #[test]
+    fn stuck_only_after_pushing_for_a_while() {
+        let mut soccar = Soccar::new();
+        for i in 0..Soccar::STUCK_TICKS - 1 {
+            assert!(!soccar.update_stuck(i as f32 * DT, true));
+        }
+        // A big frame hitch shouldn't skip straight past the tick count.
+        assert!(!soccar.update_stuck(0.0, false));
+        assert!(!soccar.update_stuck(0.0, true));
+        assert!(!soccar.update_stuck(Soccar::STUCK_TIME, true));
+
+        let mut soccar = Soccar::new();
+        let mut stuck = false;
+        for i in 0..=Soccar::STUCK_TICKS * 3 {
+            stuck = soccar.update_stuck(i as f32 * DT, true);
+            if stuck {
+                break;
+            }
+        }
+        assert!(stuck);
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use crate::{
@@ -245,6 +345,7 @@ mod integration_tests {
     use brain_test_data::recordings;
     use common::prelude::*;
     use nalgebra::{Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
 
     #[test]
     fn dont_panic_when_no_intercept() {
@@ -266,6 +367,30 @@ mod integration_tests {
         });
     }
 
+    #[test]
+    fn hold_position_when_out_possessed_goal_side() {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                // The enemy is about to reach the ball from the side, and we're
+                // already sitting between it and our net.
+                ball_loc: Point3::new(0.0, 1500.0, 92.74),
+                car_loc: Point3::new(0.0, -3500.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                enemy_loc: Point3::new(-800.0, 1500.0, 17.01),
+                enemy_rot: Rotation3::from_unreal_angles(0.0, 0.0, 0.0),
+                enemy_vel: Vector3::new(500.0, 0.0, 0.0),
+                ..Default::default()
+            })
+            .starting_boost(0.0)
+            .soccar()
+            .run_for_millis(500);
+
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::HoldPosition));
+            assert!(!events.contains(&Event::Offense));
+        });
+    }
+
     #[test]
     fn get_boost_on_defense_if_we_have_time() {
         let test = TestRunner::new()
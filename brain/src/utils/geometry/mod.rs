@@ -10,24 +10,37 @@ pub mod flattener;
 const EPSILON: f32 = 0.001;
 
 pub trait ExtendF32 {
-    /// Normalize an angle to between -PI and PI.
+    /// Normalize an angle to between -PI and PI, inclusive.
+    ///
+    /// This is symmetric around zero: `(-a).normalize_angle()` is always
+    /// `-a.normalize_angle()`, so an angle of exactly PI keeps its sign rather
+    /// than flipping to -PI.
     fn normalize_angle(self) -> Self;
+    /// Normalize an angle to within PI of `center`. Use this to keep an angle
+    /// continuous with a previous one across the wraparound.
+    #[allow(dead_code)]
+    fn normalize_angle_to(self, center: Self) -> Self;
     /// Assert that a number is almost integral, then return it as an integer.
     fn into_almost_int(self) -> i32;
 }
 
 impl ExtendF32 for f32 {
     fn normalize_angle(self) -> Self {
+        // `%` keeps the sign of `self`, so this stays symmetric.
         let result = self % (PI * 2.0);
         if result < -PI {
             result + (PI * 2.0)
-        } else if result >= PI {
+        } else if result > PI {
             result - (PI * 2.0)
         } else {
             result
         }
     }
 
+    fn normalize_angle_to(self, center: Self) -> Self {
+        (self - center).normalize_angle() + center
+    }
+
     fn into_almost_int(self) -> i32 {
         assert!(fractionality(self) <= 1e-5);
         self.round() as i32
@@ -52,6 +65,9 @@ impl Line2 {
         }
     }
 
+    /// Returns the point where the two lines cross. Both lines are treated as
+    /// infinite, so the point may lie far outside the points they were built
+    /// from. See `intersect_segment` for the bounded version.
     pub fn intersect(&self, other: Self) -> Option<Point2<f32>> {
         // http://www.ambrsoft.com/MathCalc/Line/TwoLinesIntersection/TwoLinesIntersection.htm
 
@@ -68,6 +84,24 @@ impl Line2 {
         let y = ((x2 * y1 - x1 * y2) * (y4 - y3) - (x4 * y3 - x3 * y4) * (y2 - y1)) / d;
         Some(Point2::new(x, y))
     }
+
+    /// Returns the point where the segments `[p, q]` of the two lines cross,
+    /// if they do.
+    pub fn intersect_segment(&self, other: Self) -> Option<Point2<f32>> {
+        let point = self.intersect(other)?;
+        let span = -EPSILON..=1.0 + EPSILON;
+        if span.contains(&self.segment_param(point)) && span.contains(&other.segment_param(point)) {
+            Some(point)
+        } else {
+            None
+        }
+    }
+
+    /// Where `point` falls along the segment, where `p` is 0 and `q` is 1.
+    fn segment_param(&self, point: Point2<f32>) -> f32 {
+        let span = self.q - self.p;
+        (point - self.p).dot(&span) / span.norm_squared()
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -119,6 +153,12 @@ impl Plane {
         vector - self.normal.into_inner() * self.normal.dot(&vector)
     }
 
+    /// Mirrors a vector across this plane, e.g. to find the direction of a
+    /// perfectly elastic bounce.
+    pub fn reflect_vector(&self, vector: &Vector3<f32>) -> Vector3<f32> {
+        vector - self.normal.into_inner() * (2.0 * self.normal.dot(vector))
+    }
+
     pub fn intersect(&self, other: &Self) -> Option<Line> {
         let plane = TypedPlane::from(*self);
         let other = TypedPlane::from(*other);
@@ -128,14 +168,16 @@ impl Plane {
     /// Returns a transformation which "unfolds" this plane along its
     /// intersection with another plane, such that the two planes are coplanar.
     ///
-    /// Returns `Err(())` if the planes are parallel.
-    pub fn unfold(&self, target: &Plane) -> Result<Isometry3<f32>, ()> {
+    /// If the planes are identical, returns the identity transformation. If
+    /// they are parallel but offset, there is no seam to unfold along, so
+    /// returns `Err(UnfoldError::Parallel)`.
+    pub fn unfold(&self, target: &Plane) -> Result<Isometry3<f32>, UnfoldError> {
         // Special-case the identity transformation.
         if abs_diff_eq!(target, self, epsilon = EPSILON) {
             return Ok(Isometry3::identity());
         }
 
-        let seam = self.intersect(target).ok_or(())?;
+        let seam = self.intersect(target).ok_or(UnfoldError::Parallel)?;
         Ok(Isometry3::rotation_wrt_point(
             self.normal.rotation_to(&target.normal),
             seam.origin,
@@ -153,6 +195,12 @@ impl Plane {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum UnfoldError {
+    /// The planes are parallel but not the same plane.
+    Parallel,
+}
+
 impl AbsDiffEq for Plane {
     type Epsilon = f32;
 
@@ -206,12 +254,20 @@ fn point3_to_euclid(point: &Point3<f32>) -> TypedPoint3D<f32, ()> {
 
 /// Returns the two points on a circle that form a tangent with the given point.
 ///
-/// If the point is inside the circle, returns `None`.
+/// If the point is on the circle, both tangent points are the point itself. If
+/// the point is inside the circle, returns `None`.
 pub fn circle_point_tangents(
     center: Point2<f32>,
     radius: f32,
     point: Point2<f32>,
 ) -> Option<[Point2<f32>; 2]> {
+    // The formula below would take the sqrt of a tiny negative number due to
+    // rounding error, and lose the tangent.
+    let dist = (point - center).norm();
+    if (dist - radius).abs() <= EPSILON {
+        return Some([point, point]);
+    }
+
     // I'm so glad the internet exists
     // http://www.ambrsoft.com/TrigoCalc/Circles2/CirclePoint/CirclePointDistance.htm
 
@@ -236,6 +292,41 @@ pub fn circle_point_tangents(
     }
 }
 
+/// Returns the distances along the ray at which it enters and exits the
+/// sphere, or `None` if it misses.
+///
+/// If the ray only grazes the sphere, both distances are equal. Only the part
+/// of the ray in front of `origin` counts, so if `origin` is inside the sphere,
+/// the ray enters at 0.
+#[allow(dead_code)]
+pub fn ray_sphere_intersect(
+    origin: Point3<f32>,
+    dir: Unit<Vector3<f32>>,
+    center: Point3<f32>,
+    radius: f32,
+) -> Option<(f32, f32)> {
+    // Solve |origin + dir * t - center|² = radius². Since `dir` is a unit
+    // vector, the quadratic's leading coefficient is 1.
+    let to_origin = origin - center;
+    let b = dir.dot(&to_origin);
+    let c = to_origin.norm_squared() - radius.powi(2);
+    let discriminant = b.powi(2) - c;
+
+    let (near, far) = if discriminant.abs() <= EPSILON {
+        (-b, -b)
+    } else if discriminant < 0.0 {
+        return None;
+    } else {
+        let root = discriminant.sqrt();
+        (-b - root, -b + root)
+    };
+
+    if far < 0.0 {
+        return None;
+    }
+    Some((near.max(0.0), far))
+}
+
 pub struct RayCoordinateSystem {
     origin: Point2<f32>,
     direction: Unit<Vector2<f32>>,
@@ -252,4 +343,190 @@ impl RayCoordinateSystem {
     pub fn project(&self, p: Point2<f32>) -> f32 {
         (p - self.origin).dot(&self.direction)
     }
+
+    /// The inverse of `project`. Returns the point `t` units along the ray.
+    #[allow(dead_code)]
+    pub fn unproject(&self, t: f32) -> Point2<f32> {
+        self.origin + self.direction.into_inner() * t
+    }
+
+    /// Returns the point on the (infinite) line nearest to `p`.
+    #[allow(dead_code)]
+    pub fn closest_point(&self, p: Point2<f32>) -> Point2<f32> {
+        self.unproject(self.project(p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::geometry::{
+        circle_point_tangents, ray_sphere_intersect, ExtendF32, Line2, Plane,
+        RayCoordinateSystem, UnfoldError,
+    };
+    use nalgebra::{Isometry3, Point2, Point3, Vector2, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn segments_that_cross() {
+        let a = Line2::from_points(Point2::new(-1.0, 0.0), Point2::new(1.0, 0.0));
+        let b = Line2::from_points(Point2::new(0.0, -1.0), Point2::new(0.0, 1.0));
+        let point = a.intersect_segment(b).unwrap();
+        assert!((point - Point2::origin()).norm() < 1e-5);
+    }
+
+    #[test]
+    fn segments_that_only_cross_when_extended() {
+        let a = Line2::from_points(Point2::new(-1.0, 0.0), Point2::new(1.0, 0.0));
+        let b = Line2::from_points(Point2::new(5.0, -1.0), Point2::new(5.0, 1.0));
+        assert!(a.intersect(b).is_some());
+        assert!(a.intersect_segment(b).is_none());
+        assert!(b.intersect_segment(a).is_none());
+    }
+
+    #[test]
+    fn segments_that_touch_at_an_end() {
+        let a = Line2::from_points(Point2::new(0.0, 0.0), Point2::new(1.0, 0.0));
+        let b = Line2::from_points(Point2::new(1.0, -1.0), Point2::new(1.0, 1.0));
+        let point = a.intersect_segment(b).unwrap();
+        assert!((point - Point2::new(1.0, 0.0)).norm() < 1e-5);
+    }
+
+    #[test]
+    fn tangents_from_point_on_circle() {
+        let center = Point2::new(100.0, -50.0);
+        let point = center + Vector2::new(10.0, 0.0);
+        let [a, b] = circle_point_tangents(center, 10.0, point).unwrap();
+        assert_eq!(a, point);
+        assert_eq!(b, point);
+    }
+
+    #[test]
+    fn tangents_from_point_barely_outside_circle() {
+        let center = Point2::new(100.0, -50.0);
+        let point = center + Vector2::new(10.0 + 1e-4, 0.0);
+        let [a, b] = circle_point_tangents(center, 10.0, point).unwrap();
+        assert!((a - point).norm() < 0.1);
+        assert!((b - point).norm() < 0.1);
+    }
+
+    #[test]
+    fn no_tangents_from_inside_circle() {
+        let center = Point2::new(100.0, -50.0);
+        let point = center + Vector2::new(5.0, 0.0);
+        assert!(circle_point_tangents(center, 10.0, point).is_none());
+    }
+
+    #[test]
+    fn unproject_round_trips() {
+        let rays = [
+            RayCoordinateSystem::segment(Point2::origin(), Point2::new(1.0, 0.0)),
+            RayCoordinateSystem::segment(Point2::new(100.0, 200.0), Point2::new(-300.0, 500.0)),
+            RayCoordinateSystem::segment(Point2::new(0.0, -5120.0), Point2::new(0.0, 5120.0)),
+        ];
+        for ray in &rays {
+            for &t in &[-1000.0, 0.0, 1.0, 2500.0] {
+                assert!((ray.project(ray.unproject(t)) - t).abs() < 1e-2);
+            }
+        }
+    }
+
+    #[test]
+    fn closest_point_on_ray() {
+        let ray = RayCoordinateSystem::segment(Point2::new(0.0, 100.0), Point2::new(10.0, 100.0));
+        let point = ray.closest_point(Point2::new(50.0, 300.0));
+        assert!((point - Point2::new(50.0, 100.0)).norm() < 1e-3);
+    }
+
+    #[test]
+    fn ray_through_sphere() {
+        let (near, far) = ray_sphere_intersect(
+            Point3::origin(),
+            Vector3::x_axis(),
+            Point3::new(1000.0, 0.0, 0.0),
+            100.0,
+        )
+        .unwrap();
+        assert!((near - 900.0).abs() < 1e-2);
+        assert!((far - 1100.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn ray_grazes_sphere() {
+        let (near, far) = ray_sphere_intersect(
+            Point3::origin(),
+            Vector3::x_axis(),
+            Point3::new(10.0, 1.0, 0.0),
+            1.0,
+        )
+        .unwrap();
+        assert_eq!(near, far);
+        assert!((near - 10.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn ray_misses_sphere() {
+        let center = Point3::new(1000.0, 200.0, 0.0);
+        assert!(ray_sphere_intersect(Point3::origin(), Vector3::x_axis(), center, 100.0).is_none());
+    }
+
+    #[test]
+    fn sphere_behind_ray() {
+        let center = Point3::new(-1000.0, 0.0, 0.0);
+        assert!(ray_sphere_intersect(Point3::origin(), Vector3::x_axis(), center, 100.0).is_none());
+    }
+
+    #[test]
+    fn ray_from_inside_sphere() {
+        let (near, far) =
+            ray_sphere_intersect(Point3::origin(), Vector3::x_axis(), Point3::origin(), 100.0)
+                .unwrap();
+        assert_eq!(near, 0.0);
+        assert!((far - 100.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn normalize_angle_boundaries() {
+        assert_eq!(PI.normalize_angle(), PI);
+        assert_eq!((-PI).normalize_angle(), -PI);
+        assert!(((3.0 * PI).normalize_angle() - PI).abs() < 1e-5);
+        assert!(((-3.0 * PI).normalize_angle() + PI).abs() < 1e-5);
+        assert!(((PI * 1.5).normalize_angle() + PI * 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn normalize_angle_is_symmetric() {
+        for &a in &[0.1, 1.0, PI, 2.0 * PI, 3.0 * PI, 10.0] {
+            assert_eq!((-a).normalize_angle(), -a.normalize_angle());
+        }
+    }
+
+    #[test]
+    fn normalize_angle_to_center() {
+        assert!(((-PI * 0.9).normalize_angle_to(PI) - PI * 1.1).abs() < 1e-5);
+        assert!((PI.normalize_angle_to(PI) - PI).abs() < 1e-5);
+        assert!(((3.0 * PI).normalize_angle_to(PI) - PI).abs() < 1e-5);
+        assert!((0.5.normalize_angle_to(0.0) - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn unfold_identical_planes() {
+        let plane = Plane::point_normal(Point3::new(0.0, 0.0, 100.0), Vector3::z_axis());
+        assert_eq!(plane.unfold(&plane), Ok(Isometry3::identity()));
+    }
+
+    #[test]
+    fn unfold_parallel_planes() {
+        let floor = Plane::point_normal(Point3::origin(), Vector3::z_axis());
+        let ceiling = Plane::point_normal(Point3::new(0.0, 0.0, 2044.0), -Vector3::z_axis());
+        assert_eq!(floor.unfold(&ceiling), Err(UnfoldError::Parallel));
+    }
+
+    #[test]
+    fn unfold_wall_onto_floor() {
+        let floor = Plane::point_normal(Point3::origin(), Vector3::z_axis());
+        let wall = Plane::point_normal(Point3::new(4096.0, 0.0, 0.0), -Vector3::x_axis());
+        let unfold = wall.unfold(&floor).unwrap();
+        let unfolded = unfold * Point3::new(4096.0, 0.0, 500.0);
+        assert!((unfolded - Point3::new(4596.0, 0.0, 0.0)).norm() < 1e-2);
+    }
 }
@@ -3,13 +3,12 @@
 #![warn(clippy::all)]
 
 use crate::{
-    collector::Collector,
-    rlbot_ext::get_packet_and_inject_rigid_body_tick,
-    scenarios::{Scenario, ScenarioStepResult},
+    recorder::ScenarioRecorder, rlbot_ext::get_packet_and_inject_rigid_body_tick,
+    scenarios::Scenario,
 };
-use std::{error::Error, fs::File, thread::sleep, time::Duration};
+use std::{error::Error, thread::sleep, time::Duration};
 
-mod collector;
+mod recorder;
 mod rlbot_ext;
 mod scenarios;
 
@@ -26,11 +25,10 @@ pub fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn run_scenario(rlbot: &rlbot::RLBot, mut scenario: impl Scenario) -> Result<(), Box<dyn Error>> {
+fn run_scenario(rlbot: &rlbot::RLBot, scenario: impl Scenario) -> Result<(), Box<dyn Error>> {
     stabilize_scenario(&rlbot, &scenario.initial_state());
 
-    let f = File::create(format!("oven/data/{}.csv", scenario.name()))?;
-    let mut collector = Collector::new(f);
+    let mut recorder = ScenarioRecorder::create("oven/data", scenario)?;
 
     let mut packets = rlbot.packeteer();
     let start = packets.next()?.game_info.seconds_elapsed;
@@ -42,10 +40,9 @@ fn run_scenario(rlbot: &rlbot::RLBot, mut scenario: impl Scenario) -> Result<(),
         let packet = get_packet_and_inject_rigid_body_tick(&rlbot, tick)?;
 
         let time = packet.GameInfo.TimeSeconds - start;
-        match scenario.step(&rlbot, time, &packet)? {
-            ScenarioStepResult::Ignore => {}
-            ScenarioStepResult::Write => collector.write(tick)?,
-            ScenarioStepResult::Finish => break,
+        let input = applied_input(tick.players().unwrap().get(0).input().unwrap());
+        if !recorder.step(&rlbot, time, &packet, &input)? {
+            break;
         }
     }
 
@@ -55,6 +52,19 @@ fn run_scenario(rlbot: &rlbot::RLBot, mut scenario: impl Scenario) -> Result<(),
     Ok(())
 }
 
+fn applied_input(state: rlbot::flat::ControllerState<'_>) -> common::halfway_house::PlayerInput {
+    common::halfway_house::PlayerInput {
+        Throttle: state.throttle(),
+        Steer: state.steer(),
+        Pitch: state.pitch(),
+        Yaw: state.yaw(),
+        Roll: state.roll(),
+        Jump: state.jump(),
+        Boost: state.boost(),
+        Handbrake: state.handbrake(),
+    }
+}
+
 fn start_match(rlbot: &rlbot::RLBot) -> Result<(), Box<dyn Error>> {
     rlbot.start_match(
         &rlbot::MatchSettings::new()
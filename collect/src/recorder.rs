@@ -0,0 +1,140 @@
+use crate::scenarios::{Scenario, ScenarioStepResult};
+use common::prelude::*;
+use std::{error::Error, fs::File, io::Write, iter::once, path::Path};
+
+/// Runs a scenario, and writes a CSV row for every tick it asks to keep. The
+/// rows are in the same format as the `Collector`'s, so `RecordingTick::parse`
+/// can read them back. There's one extra column at the end with the car's boost
+/// amount, which the `Collector` has no way to know.
+pub struct ScenarioRecorder<S, W: Write> {
+    scenario: S,
+    /// This becomes `None` once the scenario finishes and the file is closed.
+    w: Option<csv::Writer<W>>,
+    wrote_header: bool,
+}
+
+impl<S: Scenario> ScenarioRecorder<S, File> {
+    /// Record to a file in `dir` named after the scenario.
+    pub fn create(dir: impl AsRef<Path>, scenario: S) -> Result<Self, Box<dyn Error>> {
+        let path = dir.as_ref().join(format!("{}.csv", scenario.name()));
+        Ok(Self::new(scenario, File::create(path)?))
+    }
+}
+
+impl<S: Scenario, W: Write> ScenarioRecorder<S, W> {
+    pub fn new(scenario: S, w: W) -> Self {
+        Self {
+            scenario,
+            w: Some(csv::Writer::from_writer(w)),
+            wrote_header: false,
+        }
+    }
+
+    /// Step the scenario. `input` is whatever the game applied to our car this
+    /// tick. Returns `false` once the scenario is finished.
+    pub fn step(
+        &mut self,
+        rlbot: &rlbot::RLBot,
+        time: f32,
+        packet: &common::halfway_house::LiveDataPacket,
+        input: &common::halfway_house::PlayerInput,
+    ) -> Result<bool, Box<dyn Error>> {
+        match self.scenario.step(rlbot, time, packet)? {
+            ScenarioStepResult::Ignore => Ok(true),
+            ScenarioStepResult::Write => {
+                self.write(packet, input)?;
+                Ok(true)
+            }
+            ScenarioStepResult::Finish => {
+                self.finish()?;
+                Ok(false)
+            }
+        }
+    }
+
+    fn write(
+        &mut self,
+        packet: &common::halfway_house::LiveDataPacket,
+        input: &common::halfway_house::PlayerInput,
+    ) -> csv::Result<()> {
+        let w = self.w.as_mut().expect("the scenario already finished");
+
+        if !self.wrote_header {
+            self.wrote_header = true;
+            w.write_record(
+                once(String::from("time"))
+                    .chain(rigid_body_header("ball"))
+                    .chain(controller_header("player0"))
+                    .chain(rigid_body_header("player0"))
+                    .chain(once(String::from("player0_boost_amount"))),
+            )?;
+        }
+
+        w.write_record(
+            once(packet.GameInfo.TimeSeconds.to_string())
+                .chain(rigid_body(&packet.GameBall.Physics))
+                .chain(controller(input))
+                .chain(rigid_body(&packet.GameCars[0].Physics))
+                .chain(once(packet.GameCars[0].Boost.to_string())),
+        )
+    }
+
+    /// Flush any buffered rows and close the file.
+    fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+        if let Some(mut w) = self.w.take() {
+            w.flush()?;
+        }
+        Ok(())
+    }
+}
+
+fn rigid_body_header(prefix: &str) -> impl Iterator<Item = String> + '_ {
+    [
+        "_loc_x", "_loc_y", "_loc_z", "_rot_x", "_rot_y", "_rot_z", "_rot_w", "_vel_x", "_vel_y",
+        "_vel_z", "_ang_x", "_ang_y", "_ang_z",
+    ]
+    .iter()
+    .map(move |s| format!("{}{}", prefix, s))
+}
+
+fn rigid_body(physics: &common::halfway_house::Physics) -> impl Iterator<Item = String> {
+    let loc = physics.loc();
+    let quat = physics.quat();
+    let vel = physics.vel();
+    let ang_vel = physics.ang_vel();
+    vec![
+        loc.x, loc.y, loc.z, quat.i, quat.j, quat.k, quat.w, vel.x, vel.y, vel.z, ang_vel.x,
+        ang_vel.y, ang_vel.z,
+    ]
+    .into_iter()
+    .map(|x| x.to_string())
+}
+
+fn controller_header(prefix: &str) -> impl Iterator<Item = String> + '_ {
+    [
+        "_throttle",
+        "_steer",
+        "_pitch",
+        "_yaw",
+        "_roll",
+        "_jump",
+        "_boost",
+        "_handbrake",
+    ]
+    .iter()
+    .map(move |s| format!("{}{}", prefix, s))
+}
+
+fn controller(input: &common::halfway_house::PlayerInput) -> impl Iterator<Item = String> {
+    vec![
+        input.Throttle.to_string(),
+        input.Steer.to_string(),
+        input.Pitch.to_string(),
+        input.Yaw.to_string(),
+        input.Roll.to_string(),
+        input.Jump.to_string(),
+        input.Boost.to_string(),
+        input.Handbrake.to_string(),
+    ]
+    .into_iter()
+}
@@ -3,7 +3,7 @@
 
 #![allow(dead_code)]
 
-use common::{halfway_house::translate_player_input, prelude::*, rl};
+use common::{control::level_out, halfway_house::translate_player_input, prelude::*, rl};
 use nalgebra::{Point3, Vector3};
 use std::{error::Error, f32::consts::PI, fmt};
 
@@ -145,6 +145,43 @@ impl SimpleScenario for Throttle {
     }
 }
 
+/// Accelerate from a standstill, easing the throttle in over `ramp_time`
+/// seconds instead of flooring it right away. Compare against `Throttle` to see
+/// if the ramp is worth it.
+pub struct LaunchRamp {
+    ramp_time: f32,
+}
+
+impl LaunchRamp {
+    pub fn new(ramp_time: f32) -> Self {
+        Self { ramp_time }
+    }
+}
+
+impl SimpleScenario for LaunchRamp {
+    fn name(&self) -> String {
+        format!("launch_ramp_{}", self.ramp_time)
+    }
+
+    fn step(
+        &mut self,
+        time: f32,
+        _packet: &common::halfway_house::LiveDataPacket,
+    ) -> SimpleScenarioStepResult {
+        if time < 2.0 {
+            SimpleScenarioStepResult::Ignore(Default::default())
+        } else if time < 3.0 {
+            let throttle = ((time - 2.0) / self.ramp_time).min(1.0);
+            SimpleScenarioStepResult::Write(common::halfway_house::PlayerInput {
+                Throttle: throttle,
+                ..Default::default()
+            })
+        } else {
+            SimpleScenarioStepResult::Finish
+        }
+    }
+}
+
 pub struct Coast;
 
 impl Coast {
@@ -189,6 +226,56 @@ impl SimpleScenario for Coast {
     }
 }
 
+/// Hold boost from a standstill until the tank runs dry. Along with
+/// `Throttle` and `Coast`, this shows how acceleration depends on boost, and
+/// how quickly it burns.
+pub struct BoostBurn;
+
+impl BoostBurn {
+    /// Don't drive into the far wall if the boost somehow never runs out.
+    const MAX_TIME: f32 = 6.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SimpleScenario for BoostBurn {
+    fn name(&self) -> String {
+        "boost_burn".to_string()
+    }
+
+    fn initial_state(&self) -> rlbot::DesiredGameState {
+        let mut state = game_state_default();
+        state.car_states[0]
+            .as_mut()
+            .unwrap()
+            .physics
+            .as_mut()
+            .unwrap()
+            .location = Some(rlbot::Vector3Partial::new().x(0.0).y(-5000.0).z(17.01));
+        state
+    }
+
+    fn step(
+        &mut self,
+        time: f32,
+        packet: &common::halfway_house::LiveDataPacket,
+    ) -> SimpleScenarioStepResult {
+        if time < 1.0 {
+            SimpleScenarioStepResult::Ignore(Default::default())
+        } else if packet.GameCars[0].Boost > 0 && time < Self::MAX_TIME {
+            SimpleScenarioStepResult::Write(common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                Boost: true,
+                ..Default::default()
+            })
+        } else {
+            SimpleScenarioStepResult::Finish
+        }
+    }
+}
+
 pub struct Turn {
     start_speed: f32,
     start_time: Option<f32>,
@@ -660,3 +747,65 @@ impl Scenario for AirRotateCounter {
         }
     }
 }
+
+/// Start out airborne and tumbling, then try to get the wheels pointed back at
+/// the ground before landing. The orientation error over time is the angle
+/// between the roof and straight up, which can be worked out from the
+/// recorded rotation.
+///
+/// The recovery uses the same `level_out` controller as `GetToFlatGround` in
+/// `brain`, so this measures how well that controller does.
+pub struct RecoverOrientation;
+
+impl RecoverOrientation {
+    /// Don't record forever if we somehow never land.
+    const MAX_TIME: f32 = 3.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SimpleScenario for RecoverOrientation {
+    fn name(&self) -> String {
+        "recover_orientation".to_string()
+    }
+
+    fn initial_state(&self) -> rlbot::DesiredGameState {
+        let mut state = game_state_default_air();
+        let physics = state.car_states[0]
+            .as_mut()
+            .unwrap()
+            .physics
+            .as_mut()
+            .unwrap();
+        // Nose up and mostly upside down, with a spin on every axis.
+        physics.rotation = Some(rlbot::RotatorPartial::new().pitch(0.5).yaw(0.0).roll(2.5));
+        physics.angular_velocity = Some(rlbot::Vector3Partial::new().x(2.0).y(-3.0).z(1.5));
+        state
+    }
+
+    fn step(
+        &mut self,
+        time: f32,
+        packet: &common::halfway_house::LiveDataPacket,
+    ) -> SimpleScenarioStepResult {
+        let car = &packet.GameCars[0];
+        if car.OnGround || time >= Self::MAX_TIME {
+            return SimpleScenarioStepResult::Finish;
+        }
+
+        let (pitch, yaw, roll) = level_out(
+            car.Physics.forward_axis(),
+            car.Physics.right_axis(),
+            car.Physics.roof_axis(),
+            car.Physics.ang_vel(),
+        );
+        SimpleScenarioStepResult::Write(common::halfway_house::PlayerInput {
+            Pitch: pitch,
+            Yaw: yaw,
+            Roll: roll,
+            ..Default::default()
+        })
+    }
+}
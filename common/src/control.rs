@@ -0,0 +1,103 @@
+use nalgebra::{clamp, Unit, Vector3};
+use std::f32::consts::PI;
+
+/// A PD controller that turns the roof towards the sky while damping out any
+/// spin, including yaw, which we otherwise don't care about. The signs and
+/// gains follow `dom::get_pitch_yaw_roll`. Returns `(pitch, yaw, roll)`.
+pub fn level_out(
+    forward: Unit<Vector3<f32>>,
+    right: Unit<Vector3<f32>>,
+    roof: Unit<Vector3<f32>>,
+    ang_vel: Vector3<f32>,
+) -> (f32, f32, f32) {
+    const KP: f32 = 3.0;
+    const KD_PITCH: f32 = 0.9;
+    const KD_YAW: f32 = 0.7;
+    const KD_ROLL: f32 = 0.3;
+
+    // The rotation that takes the roof to straight up, as an axis scaled by
+    // the angle. When we're exactly upside down, any axis will do, so pick the
+    // roll axis since that's the fastest to turn about.
+    let angle = roof.angle(&Vector3::z_axis());
+    let error = match (-roof.cross(&Vector3::z())).try_normalize(1e-4) {
+        Some(axis) => axis * angle,
+        None if angle > PI / 2.0 => forward.into_inner() * angle,
+        None => Vector3::zeros(),
+    };
+
+    let pitch = KP * error.dot(&right) + KD_PITCH * ang_vel.dot(&right);
+    let yaw = KP * -error.dot(&roof) + KD_YAW * -ang_vel.dot(&roof);
+    let roll = KP * error.dot(&forward) + KD_ROLL * ang_vel.dot(&forward);
+    (
+        clamp(pitch, -1.0, 1.0),
+        clamp(yaw, -1.0, 1.0),
+        clamp(roll, -1.0, 1.0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        control::level_out,
+        physics::{car_forward_axis, car_right_axis, car_roof_axis},
+        prelude::*,
+    };
+    use nalgebra::{Rotation3, UnitQuaternion, Vector3};
+    use std::f32::consts::PI;
+
+    fn level_out_from(rot: UnitQuaternion<f32>, ang_vel: Vector3<f32>) -> (f32, f32, f32) {
+        level_out(
+            car_forward_axis(rot),
+            car_right_axis(rot),
+            car_roof_axis(rot),
+            ang_vel,
+        )
+    }
+
+    #[test]
+    fn already_level() {
+        let rot =
+            UnitQuaternion::from_rotation_matrix(&Rotation3::from_unreal_angles(0.0, 1.0, 0.0));
+        let (pitch, yaw, roll) = level_out_from(rot, Vector3::zeros());
+        assert!(pitch.abs() < 1e-3);
+        assert!(yaw.abs() < 1e-3);
+        assert!(roll.abs() < 1e-3);
+    }
+
+    #[test]
+    fn upside_down_rolls() {
+        let rot =
+            UnitQuaternion::from_rotation_matrix(&Rotation3::from_unreal_angles(0.0, 0.0, PI));
+        let (pitch, _yaw, roll) = level_out_from(rot, Vector3::zeros());
+        assert!(pitch.abs() < 1e-3);
+        assert!(roll.abs() >= 1.0 - 1e-3);
+    }
+
+    #[test]
+    fn damp_spin_while_level() {
+        let rot = UnitQuaternion::identity();
+        let (pitch, yaw, roll) = level_out_from(rot, Vector3::new(0.0, 0.0, 5.0));
+        assert!(pitch.abs() < 1e-3);
+        assert!(roll.abs() < 1e-3);
+        assert!(yaw.abs() >= 1.0 - 1e-3);
+
+        // Opposite spin, opposite input.
+        let (_, yaw2, _) = level_out_from(rot, Vector3::new(0.0, 0.0, -5.0));
+        assert_eq!(yaw2, -yaw);
+    }
+
+    #[test]
+    fn roll_harder_the_more_we_tilt() {
+        // Rolled a bit to one side: the roll input should fight the tilt, and
+        // rolling further the same way should make it fight harder.
+        let rot =
+            UnitQuaternion::from_rotation_matrix(&Rotation3::from_unreal_angles(0.0, 0.0, 0.2));
+        let (_, _, roll) = level_out_from(rot, Vector3::zeros());
+        let rot =
+            UnitQuaternion::from_rotation_matrix(&Rotation3::from_unreal_angles(0.0, 0.0, 0.3));
+        let (_, _, roll2) = level_out_from(rot, Vector3::zeros());
+        assert!(roll != 0.0);
+        assert_eq!(roll.signum(), roll2.signum());
+        assert!(roll2.abs() > roll.abs());
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(non_snake_case)] // TODO: fix this
 
-use crate::flatbuffers::vector_iter;
+use crate::{flatbuffers::vector_iter, rl};
 use smallvec::SmallVec;
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -46,12 +46,21 @@ pub struct BallInfo {
     pub Physics: Physics,
 }
 
+#[derive(Debug, Copy, Clone)]
+pub struct BoostInfo {
+    pub IsActive: bool,
+    /// Seconds since the pad was picked up, or 0 if it's active.
+    pub Timer: f32,
+}
+
 #[derive(Debug, Default, Copy, Clone)]
 pub struct GameInfo {
     pub TimeSeconds: f32,
     pub GameTimeRemaining: f32,
     pub RoundActive: bool,
     pub MatchEnded: bool,
+    /// `None` if the packet didn't include it.
+    pub WorldGravityZ: Option<f32>,
 }
 
 #[derive(Clone)]
@@ -59,6 +68,9 @@ pub struct LiveDataPacket {
     pub GameCars: SmallVec<[PlayerInfo; 4]>,
     pub NumCars: ::std::os::raw::c_int,
     pub GameBall: BallInfo,
+    /// One per boost pad, in the same order as `FieldInfo::boostPads`. Soccar
+    /// has 34 pads, so they all fit inline.
+    pub GameBoosts: SmallVec<[BoostInfo; 36]>,
     pub GameInfo: GameInfo,
     pub Teams: SmallVec<[TeamInfo; 2usize]>,
     pub NumTeams: ::std::os::raw::c_int,
@@ -76,6 +88,14 @@ pub struct PlayerInput {
     pub Handbrake: bool,
 }
 
+impl GameInfo {
+    /// The gravity for this match, which mutators can change. Falls back to
+    /// the standard value if the game didn't tell us.
+    pub fn gravity(&self) -> f32 {
+        self.WorldGravityZ.unwrap_or(rl::GRAVITY)
+    }
+}
+
 impl LiveDataPacket {
     pub fn cars(&self) -> impl Iterator<Item = &PlayerInfo> {
         self.GameCars.iter().take(self.NumCars as usize)
@@ -90,6 +110,10 @@ pub fn deserialize_game_tick_packet(packet: rlbot::flat::GameTickPacket<'_>) ->
             .unwrap_or_default(),
         NumCars: packet.players().map(|ps| ps.len() as i32).unwrap_or(0),
         GameBall: packet.ball().map(deserialize_ball_info).unwrap_or_default(),
+        GameBoosts: packet
+            .boostPadStates()
+            .map(|bs| vector_iter(bs).map(deserialize_boost_info).collect())
+            .unwrap_or_default(),
         GameInfo: packet
             .gameInfo()
             .map(deserialize_game_info)
@@ -119,12 +143,24 @@ fn deserialize_ball_info(info: rlbot::flat::BallInfo<'_>) -> BallInfo {
     }
 }
 
+fn deserialize_boost_info(info: rlbot::flat::BoostPadState<'_>) -> BoostInfo {
+    BoostInfo {
+        IsActive: info.isActive(),
+        Timer: info.timer(),
+    }
+}
+
 fn deserialize_game_info(info: rlbot::flat::GameInfo<'_>) -> GameInfo {
     GameInfo {
         TimeSeconds: info.secondsElapsed(),
         GameTimeRemaining: info.gameTimeRemaining(),
         RoundActive: info.isRoundActive(),
         MatchEnded: info.isMatchEnded(),
+        // `worldGravityZ()` reads a missing field as zero, which would look
+        // like a zero-gravity mutator, so check for the field ourselves.
+        WorldGravityZ: info
+            ._tab
+            .get::<f32>(rlbot::flat::GameInfo::VT_WORLDGRAVITYZ, None),
     }
 }
 
@@ -184,3 +220,18 @@ pub fn translate_player_input(input: &PlayerInput) -> rlbot::ControllerState {
         handbrake: input.Handbrake,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{halfway_house::GameInfo, rl};
+
+    #[test]
+    fn gravity_falls_back_only_when_missing() {
+        assert_eq!(GameInfo::default().gravity(), rl::GRAVITY);
+        let zero_g = GameInfo {
+            WorldGravityZ: Some(0.0),
+            ..Default::default()
+        };
+        assert_eq!(zero_g.gravity(), 0.0);
+    }
+}
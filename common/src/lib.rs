@@ -11,6 +11,7 @@ pub use crate::{
     },
 };
 
+pub mod control;
 pub mod ext;
 mod flatbuffers;
 pub mod halfway_house;
@@ -50,6 +50,10 @@ pub const PHYSICS_TICK_FREQ: f32 = 120.0;
 /// The number of seconds between physics ticks.
 pub const PHYSICS_DT: f32 = 1.0 / PHYSICS_TICK_FREQ;
 
+/// The number of seconds between slices of the framework's ball prediction.
+/// It runs at half the physics tick rate.
+pub const FRAMEWORK_PREDICTION_DT: f32 = 2.0 * PHYSICS_DT;
+
 /// The max speed a car can reach using only the throttle.
 ///
 /// This value was observed in data from `collect`.
@@ -119,3 +123,9 @@ pub const BOOST_PENNY_RADIUS: f32 = 144.0;
 ///
 /// Source: https://youtu.be/xgfa-qZyInw?t=31
 pub const BOOST_PENNY_HEIGHT: f32 = 165.0;
+
+/// How long a full boost pad takes to come back after it's picked up.
+pub const BOOST_DOLLAR_RESPAWN_TIME: f32 = 10.0;
+
+/// How long a small boost pad takes to come back after it's picked up.
+pub const BOOST_PENNY_RESPAWN_TIME: f32 = 4.0;
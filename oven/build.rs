@@ -118,6 +118,9 @@ fn compile_csv(name: &str, mut csv: csv::Reader<impl Read>, w: &mut impl Write,
         "f32",
         col!("player0_vel_y").rev().map(floatify),
     );
+    write_array!("CAR_ANG_VEL_X", "f32", col!("player0_ang_x").map(floatify));
+    write_array!("CAR_ANG_VEL_Y", "f32", col!("player0_ang_y").map(floatify));
+    write_array!("CAR_ANG_VEL_Z", "f32", col!("player0_ang_z").map(floatify));
     write_array!("CAR_ROT_2D_ANGLE_CUM", "f32", player0_rot_2d_angle_cum);
     writeln!(w, "    lazy_static! {{
         pub static ref CAR_LOC_2D: Vec<Point2<f32>> = CAR_LOC_X.iter().zip(CAR_LOC_Y.iter()).map(|(&x, &y)| Point2::new(x, y)).collect();
@@ -130,7 +130,7 @@ fn run_bot(
     show_window: bool,
 ) {
     let field_info = wait_for_field_info(rlbot);
-    let brain = match Brain::infer_game_mode(field_info) {
+    let mut brain = match Brain::infer_game_mode(field_info) {
         rlbot::GameMode::Soccer => Brain::soccar(),
         rlbot::GameMode::Dropshot => Brain::dropshot(rlbot),
         rlbot::GameMode::Hoops => Brain::hoops(rlbot),
@@ -148,6 +148,7 @@ fn run_bot(
     }
     if show_window {
         eeg.show_window();
+        brain.set_draw_ball_prediction(true);
     }
     let mut bot = FormulaNone::new(rlbot, field_info, collector, eeg, brain);
     bot.set_player_index(player_index);
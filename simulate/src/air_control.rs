@@ -0,0 +1,186 @@
+use common::halfway_house::PlayerInput;
+use nalgebra::{UnitQuaternion, Vector3};
+
+/// A model of how the pitch, yaw and roll inputs rotate a car in the air.
+///
+/// Each vector is in the car's local frame, ordered (roll, pitch, yaw), i.e.,
+/// rotation around the forward, right and roof axes. The constants were fit
+/// to the `air_rotate_*` data from the `collect` crate.
+pub struct AirControl {
+    /// Angular acceleration from a full input on each axis.
+    pub torque: Vector3<f32>,
+    /// Angular deceleration in proportion to angular velocity. Roll damping
+    /// always applies; pitch and yaw damping fade out as the input on that
+    /// axis approaches full.
+    pub damping: Vector3<f32>,
+    /// The game caps the magnitude of angular velocity at this value.
+    pub max_ang_vel: f32,
+}
+
+impl Default for AirControl {
+    fn default() -> Self {
+        Self {
+            torque: Vector3::new(-38.33, -12.47, 9.11),
+            damping: Vector3::new(-4.80, -2.88, -1.92),
+            max_ang_vel: 5.5,
+        }
+    }
+}
+
+impl AirControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance by `dt` seconds with the given controller input. Angular
+    /// velocity is in world coordinates, as it comes from the game. Returns
+    /// the new rotation and angular velocity.
+    pub fn step(
+        &self,
+        rot: UnitQuaternion<f32>,
+        ang_vel: Vector3<f32>,
+        input: &PlayerInput,
+        dt: f32,
+    ) -> (UnitQuaternion<f32>, Vector3<f32>) {
+        let local = rot.inverse() * ang_vel;
+        let stick = Vector3::new(input.Roll, input.Pitch, input.Yaw);
+        let damping_scale = Vector3::new(1.0, 1.0 - input.Pitch.abs(), 1.0 - input.Yaw.abs());
+
+        let accel = self.torque.component_mul(&stick)
+            + self
+                .damping
+                .component_mul(&damping_scale)
+                .component_mul(&local);
+        let mut ang_vel = rot * (local + accel * dt);
+        if ang_vel.norm() > self.max_ang_vel {
+            ang_vel = ang_vel.normalize() * self.max_ang_vel;
+        }
+
+        let rot = UnitQuaternion::from_scaled_axis(ang_vel * dt) * rot;
+        (rot, ang_vel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::AirControl;
+    use common::halfway_house::PlayerInput;
+    use nalgebra::{UnitQuaternion, Vector3};
+    use oven::data;
+
+    /// The recordings each rotate around a single axis, so that axis is the
+    /// same in the local and world frames, and the car's actual rotation
+    /// doesn't affect the angular velocity. Start from identity for
+    /// simplicity.
+    fn replay(
+        time: &[f32],
+        ang_vel: impl Fn(usize) -> Vector3<f32>,
+        input: PlayerInput,
+        max_error: f32,
+    ) {
+        let control = AirControl::new();
+        let mut rot = UnitQuaternion::identity();
+        let mut sim_ang_vel = ang_vel(0);
+        for i in 1..time.len() {
+            let dt = time[i] - time[i - 1];
+            let (r, a) = control.step(rot, sim_ang_vel, &input, dt);
+            rot = r;
+            sim_ang_vel = a;
+            let error = (sim_ang_vel - ang_vel(i)).norm();
+            assert!(
+                error < max_error,
+                "frame {}: {:?} vs {:?}",
+                i,
+                sim_ang_vel,
+                ang_vel(i),
+            );
+        }
+    }
+
+    fn pitch(x: f32) -> PlayerInput {
+        PlayerInput {
+            Pitch: x,
+            ..Default::default()
+        }
+    }
+
+    fn yaw(x: f32) -> PlayerInput {
+        PlayerInput {
+            Yaw: x,
+            ..Default::default()
+        }
+    }
+
+    fn roll(x: f32) -> PlayerInput {
+        PlayerInput {
+            Roll: x,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pitch_coast() {
+        use data::air_rotate_pitch_coast::*;
+        replay(
+            TIME,
+            |i| Vector3::new(0.0, CAR_ANG_VEL_Y[i], 0.0),
+            pitch(0.0),
+            0.05,
+        );
+    }
+
+    #[test]
+    fn pitch_counter() {
+        use data::air_rotate_pitch_counter::*;
+        replay(
+            TIME,
+            |i| Vector3::new(0.0, CAR_ANG_VEL_Y[i], 0.0),
+            pitch(-1.0),
+            0.05,
+        );
+    }
+
+    #[test]
+    fn yaw_coast() {
+        use data::air_rotate_yaw_coast::*;
+        replay(
+            TIME,
+            |i| Vector3::new(0.0, 0.0, CAR_ANG_VEL_Z[i]),
+            yaw(0.0),
+            0.05,
+        );
+    }
+
+    #[test]
+    fn yaw_counter() {
+        use data::air_rotate_yaw_counter::*;
+        replay(
+            TIME,
+            |i| Vector3::new(0.0, 0.0, CAR_ANG_VEL_Z[i]),
+            yaw(-1.0),
+            0.05,
+        );
+    }
+
+    #[test]
+    fn roll_coast() {
+        use data::air_rotate_roll_coast::*;
+        replay(
+            TIME,
+            |i| Vector3::new(CAR_ANG_VEL_X[i], 0.0, 0.0),
+            roll(0.0),
+            0.05,
+        );
+    }
+
+    #[test]
+    fn roll_counter() {
+        use data::air_rotate_roll_counter::*;
+        replay(
+            TIME,
+            |i| Vector3::new(CAR_ANG_VEL_X[i], 0.0, 0.0),
+            roll(-1.0),
+            0.05,
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use crate::car1d::Car1D;
+
+/// If even coasting would overshoot by more than this, hit the brakes.
+const BRAKE_MARGIN: f32 = 100.0;
+
+/// If even boosting the whole way falls short by more than this, we're not
+/// going to make it.
+const LATE_MARGIN: f32 = 100.0;
+
+/// What to do with the pedals (right now) in order to cover a distance in a
+/// given amount of time.
+///
+/// This is meant to be re-solved every frame. Each plan is the strongest input
+/// that won't get us there early, so as the situation evolves, the car ends up
+/// alternating between adjacent plans and arrives about on time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ArrivalPlan {
+    /// Even coasting would get there way too early.
+    Brake,
+    /// Coasting gets there early, or throttle would.
+    Coast,
+    /// Throttle gets there late, but boost would get there early.
+    Throttle,
+    /// We need to boost the whole way.
+    Boost,
+    /// We'll be late even if we boost the whole way. Go as fast as we can and
+    /// hope for the best.
+    Impossible,
+}
+
+impl ArrivalPlan {
+    /// Returns the `(throttle, boost)` inputs for this plan.
+    pub fn controls(self) -> (f32, bool) {
+        match self {
+            ArrivalPlan::Brake => (-1.0, false),
+            ArrivalPlan::Coast => (0.0, false),
+            ArrivalPlan::Throttle => (1.0, false),
+            ArrivalPlan::Boost | ArrivalPlan::Impossible => (1.0, true),
+        }
+    }
+}
+
+/// Decides how to drive straight ahead so that we've covered `distance` right
+/// at `target_time`, without getting there early.
+pub fn solve_arrival(start_speed: f32, boost: f32, distance: f32, target_time: f32) -> ArrivalPlan {
+    solve_arrival_then_cruise(start_speed, boost, distance, target_time, 0.0)
+}
+
+/// Like [`solve_arrival`], except that after `drive_time`, the car keeps
+/// moving at whatever speed it had for another `cruise_time` (e.g., during a
+/// jump).
+pub fn solve_arrival_then_cruise(
+    start_speed: f32,
+    boost: f32,
+    distance: f32,
+    drive_time: f32,
+    cruise_time: f32,
+) -> ArrivalPlan {
+    assert!(drive_time >= 0.0);
+    assert!(cruise_time >= 0.0);
+
+    let overshoot = |throttle, use_boost| {
+        let mut car = Car1D::new().with_speed(start_speed).with_boost(boost);
+        car.advance(drive_time, throttle, use_boost);
+        car.distance() + car.speed() * cruise_time - distance
+    };
+
+    let coast = overshoot(0.0, false);
+    if coast > BRAKE_MARGIN {
+        return ArrivalPlan::Brake;
+    }
+    if coast > 0.0 || overshoot(1.0, false) > 0.0 {
+        return ArrivalPlan::Coast;
+    }
+    let blitz = overshoot(1.0, true);
+    if blitz > 0.0 {
+        ArrivalPlan::Throttle
+    } else if blitz > -LATE_MARGIN {
+        ArrivalPlan::Boost
+    } else {
+        ArrivalPlan::Impossible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        arrival::{solve_arrival, ArrivalPlan},
+        car1d::Car1D,
+    };
+
+    fn distance(start_speed: f32, time: f32, throttle: f32, boost: bool) -> f32 {
+        let mut car = Car1D::new().with_speed(start_speed);
+        car.advance(time, throttle, boost);
+        car.distance()
+    }
+
+    #[test]
+    fn brake() {
+        assert_eq!(solve_arrival(2000.0, 0.0, 500.0, 1.0), ArrivalPlan::Brake);
+    }
+
+    #[test]
+    fn coast() {
+        let coast = distance(1000.0, 1.0, 0.0, false);
+        assert_eq!(
+            solve_arrival(1000.0, 100.0, coast - 50.0, 1.0),
+            ArrivalPlan::Coast,
+        );
+        assert_eq!(
+            solve_arrival(1000.0, 100.0, coast + 50.0, 1.0),
+            ArrivalPlan::Coast,
+        );
+    }
+
+    #[test]
+    fn throttle() {
+        let throttle = distance(1000.0, 1.0, 1.0, false);
+        assert_eq!(
+            solve_arrival(1000.0, 100.0, throttle + 50.0, 1.0),
+            ArrivalPlan::Throttle,
+        );
+    }
+
+    #[test]
+    fn boost_needed() {
+        let blitz = distance(0.0, 2.0, 1.0, true);
+        assert_eq!(
+            solve_arrival(0.0, 100.0, blitz + 50.0, 2.0),
+            ArrivalPlan::Boost,
+        );
+    }
+
+    #[test]
+    fn impossible() {
+        assert_eq!(
+            solve_arrival(0.0, 100.0, 5000.0, 1.0),
+            ArrivalPlan::Impossible,
+        );
+    }
+
+    #[test]
+    fn impossible_without_boost() {
+        let blitz = distance(0.0, 2.0, 1.0, true);
+        assert_eq!(solve_arrival(0.0, 0.0, blitz, 2.0), ArrivalPlan::Impossible);
+    }
+}
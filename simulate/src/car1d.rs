@@ -98,6 +98,17 @@ impl Car1D {
         self.advance(dt - curve.dt, throttle, boost);
     }
 
+    /// Advance by `duration` seconds, one tick of `dt` seconds at a time, the
+    /// way the game would. `dt` should be the real time between frames, since
+    /// that isn't always `rl::PHYSICS_DT`.
+    pub fn multi_step(&mut self, duration: f32, dt: f32, throttle: f32, boost: bool) {
+        assert!(dt > 0.0);
+        let end_time = self.time + duration;
+        while end_time - self.time > EPS {
+            self.advance(dt.min(end_time - self.time), throttle, boost);
+        }
+    }
+
     pub fn advance_by_distance(&mut self, distance: f32, throttle: f32, mut boost: bool) {
         assert!(distance > 0.0);
 
@@ -481,6 +492,22 @@ mod tests {
         assert_eq!(car.boost(), 0.0);
     }
 
+    #[test]
+    fn multi_step_converges_across_tick_rates() {
+        let distances: Vec<_> = [1.0 / 120.0, 1.0 / 60.0, 1.0 / 30.0]
+            .iter()
+            .map(|&dt| {
+                let mut car = Car1D::new().with_speed(500.0).with_boost(20.0);
+                car.multi_step(2.0, dt, 1.0, true);
+                assert!((car.time() - 2.0).abs() <= 1e-3);
+                car.distance()
+            })
+            .collect();
+        for &distance in &distances[1..] {
+            assert!((distance - distances[0]).abs() < 5.0);
+        }
+    }
+
     #[test]
     fn advance_coast_rest() {
         let mut car = Car1D::new().with_speed(0.0);
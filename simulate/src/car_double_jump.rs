@@ -0,0 +1,113 @@
+//! A simple model of a double jump: hold the first jump for as long as it
+//! keeps pushing, let go briefly, then jump again.
+
+use common::{kinematics::kinematic_time, rl};
+
+/// How long to let go of the button between the two jumps. Each input needs
+/// to be held for a few frames to register.
+pub const RELEASE_TIME: f32 = 0.05;
+
+/// When the second jump fires, relative to the first.
+pub const SECOND_JUMP_TIME: f32 = rl::CAR_JUMP_FORCE_TIME + RELEASE_TIME;
+
+/// Returns the car's height and vertical velocity `t` seconds after starting a
+/// double jump from the ground. `gravity` is the match's, e.g. from
+/// `GameInfo::gravity`.
+pub fn z_at(t: f32, gravity: f32) -> (f32, f32) {
+    let hold_accel = rl::CAR_JUMP_ACCEL + gravity;
+    let (z, vz) = (rl::OCTANE_NEUTRAL_Z, rl::CAR_JUMP_IMPULSE_SPEED);
+
+    let hold = t.min(rl::CAR_JUMP_FORCE_TIME);
+    let (z, vz) = advance(z, vz, hold_accel, hold);
+    if t <= rl::CAR_JUMP_FORCE_TIME {
+        return (z, vz);
+    }
+
+    let release = t.min(SECOND_JUMP_TIME) - rl::CAR_JUMP_FORCE_TIME;
+    let (z, vz) = advance(z, vz, gravity, release);
+    if t <= SECOND_JUMP_TIME {
+        return (z, vz);
+    }
+
+    let vz = vz + rl::CAR_JUMP_IMPULSE_SPEED;
+    advance(z, vz, gravity, t - SECOND_JUMP_TIME)
+}
+
+/// The highest the car gets during a double jump.
+pub fn max_z(gravity: f32) -> f32 {
+    let (z, vz) = z_at(SECOND_JUMP_TIME, gravity);
+    let vz = vz + rl::CAR_JUMP_IMPULSE_SPEED;
+    z + vz * vz / (-2.0 * gravity)
+}
+
+/// Returns how long it takes a double jump to reach height `z` on the way up,
+/// or `None` if it can't get that high.
+pub fn time_to_z(z: f32, gravity: f32) -> Option<f32> {
+    if z <= rl::OCTANE_NEUTRAL_Z {
+        return Some(0.0);
+    }
+    if z > max_z(gravity) {
+        return None;
+    }
+
+    // Check each phase in turn, since within each one the acceleration is
+    // constant.
+    let (second_z, second_vz) = z_at(SECOND_JUMP_TIME, gravity);
+    if z > second_z {
+        let vz = second_vz + rl::CAR_JUMP_IMPULSE_SPEED;
+        return Some(SECOND_JUMP_TIME + kinematic_time(z - second_z, vz, gravity)?);
+    }
+    let (held_z, held_vz) = z_at(rl::CAR_JUMP_FORCE_TIME, gravity);
+    if z > held_z {
+        return Some(rl::CAR_JUMP_FORCE_TIME + kinematic_time(z - held_z, held_vz, gravity)?);
+    }
+    kinematic_time(
+        z - rl::OCTANE_NEUTRAL_Z,
+        rl::CAR_JUMP_IMPULSE_SPEED,
+        rl::CAR_JUMP_ACCEL + gravity,
+    )
+}
+
+fn advance(z: f32, vz: f32, accel: f32, t: f32) -> (f32, f32) {
+    (z + vz * t + 0.5 * accel * t * t, vz + accel * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{car_double_jump, car_single_jump};
+    use common::rl;
+
+    #[test]
+    fn higher_than_a_single_jump() {
+        let max_z = car_double_jump::max_z(rl::GRAVITY);
+        assert!(max_z > car_single_jump::JUMP_MAX_Z + 200.0);
+        assert!(max_z < rl::CROSSBAR_Z);
+    }
+
+    #[test]
+    fn time_to_z_round_trips() {
+        for &z in &[50.0, 100.0, 200.0, 300.0, 400.0] {
+            let t = car_double_jump::time_to_z(z, rl::GRAVITY).unwrap();
+            let (actual, vz) = car_double_jump::z_at(t, rl::GRAVITY);
+            assert!((actual - z).abs() < 0.1, "{} {}", z, actual);
+            assert!(vz > 0.0);
+        }
+    }
+
+    #[test]
+    fn higher_in_low_gravity() {
+        let normal = car_double_jump::max_z(rl::GRAVITY);
+        let low = car_double_jump::max_z(rl::GRAVITY / 2.0);
+        assert!(low > normal + 200.0);
+        assert!(car_double_jump::time_to_z(normal + 100.0, rl::GRAVITY / 2.0).is_some());
+    }
+
+    #[test]
+    fn too_high() {
+        assert_eq!(
+            car_double_jump::time_to_z(car_double_jump::max_z(rl::GRAVITY) + 1.0, rl::GRAVITY),
+            None
+        );
+        assert_eq!(car_double_jump::time_to_z(0.0, rl::GRAVITY), Some(0.0));
+    }
+}
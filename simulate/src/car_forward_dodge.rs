@@ -8,6 +8,9 @@ const DODGE_IMPULSE: f32 = 500.0;
 pub struct CarForwardDodge;
 
 impl CarForwardDodge {
+    /// The speed a dodge adds, in the direction of the dodge.
+    pub const IMPULSE: f32 = DODGE_IMPULSE;
+
     pub fn calc_1d(start_speed: f32) -> CarForwardDodge1D {
         let dodge_loc = start_speed * (JUMP_TIME + WAIT_TIME);
         let dodge_vel = (start_speed + DODGE_IMPULSE).min(rl::CAR_MAX_SPEED);
@@ -5,13 +5,13 @@ use common::{
 };
 use nalgebra::{UnitQuaternion, Vector3};
 
-/// With the given car rotation, how long would it take to jump a certain
-/// distance along the car's roof axis?
+/// With the given car rotation and gravity, how long would it take to jump a
+/// certain distance along the car's roof axis?
 ///
 /// Note that this can return degenerate results, e.g., if the car is sideways,
 /// you can jump an infinite distance to the side if you're willing to wait long
 /// enough.
-pub fn jump_duration(rot: &UnitQuaternion<f32>, target_dist: f32) -> Option<f32> {
+pub fn jump_duration(rot: &UnitQuaternion<f32>, target_dist: f32, gravity: f32) -> Option<f32> {
     // A jump has two stages:
     //
     // 1. A period of constant force for up to 0.2s while the jump button is held
@@ -32,7 +32,7 @@ pub fn jump_duration(rot: &UnitQuaternion<f32>, target_dist: f32) -> Option<f32>
     let axis = car_roof_axis(*rot).into_inner();
 
     let v_0 = axis * rl::CAR_JUMP_IMPULSE_SPEED;
-    let a = axis * rl::CAR_JUMP_ACCEL + Vector3::z() * rl::GRAVITY;
+    let a = axis * rl::CAR_JUMP_ACCEL + Vector3::z() * gravity;
     if let Some(t) = kinematic_time(target_dist, v_0.dot(&axis), a.dot(&axis)) {
         if t < rl::CAR_JUMP_FORCE_TIME {
             return Some(t);
@@ -43,7 +43,7 @@ pub fn jump_duration(rot: &UnitQuaternion<f32>, target_dist: f32) -> Option<f32>
     // solve the kinematic equation while coasting for t.
 
     let (d_0, v_0) = kinematic(v_0, a, rl::CAR_JUMP_FORCE_TIME);
-    let a = Vector3::z() * rl::GRAVITY;
+    let a = Vector3::z() * gravity;
     if let Some(t) = kinematic_time(target_dist - d_0.dot(&axis), v_0.dot(&axis), a.dot(&axis)) {
         return Some(rl::CAR_JUMP_FORCE_TIME + t);
     }
@@ -56,15 +56,26 @@ pub fn jump_duration(rot: &UnitQuaternion<f32>, target_dist: f32) -> Option<f32>
 #[cfg(test)]
 mod tests {
     use crate::car_jump::jump_duration;
+    use common::rl;
     use nalgebra::UnitQuaternion;
 
     #[test]
     fn test_jump_duration() {
         let flat = UnitQuaternion::identity();
-        assert_eq!(jump_duration(&flat, 10.0).unwrap(), 0.03279536);
-        assert_eq!(jump_duration(&flat, 100.0).unwrap(), 0.25872213);
-        assert_eq!(jump_duration(&flat, 200.0).unwrap(), 0.5807926);
-        assert_eq!(jump_duration(&flat, 220.0).unwrap(), 0.70065045);
-        assert_eq!(jump_duration(&flat, 250.0), None);
+        let g = rl::GRAVITY;
+        assert_eq!(jump_duration(&flat, 10.0, g).unwrap(), 0.03279536);
+        assert_eq!(jump_duration(&flat, 100.0, g).unwrap(), 0.25872213);
+        assert_eq!(jump_duration(&flat, 200.0, g).unwrap(), 0.5807926);
+        assert_eq!(jump_duration(&flat, 220.0, g).unwrap(), 0.70065045);
+        assert_eq!(jump_duration(&flat, 250.0, g), None);
+    }
+
+    #[test]
+    fn jump_higher_in_low_gravity() {
+        let flat = UnitQuaternion::identity();
+        let normal = jump_duration(&flat, 200.0, rl::GRAVITY).unwrap();
+        let low = jump_duration(&flat, 200.0, rl::GRAVITY / 2.0).unwrap();
+        assert!(low < normal);
+        assert!(jump_duration(&flat, 250.0, rl::GRAVITY / 2.0).is_some());
     }
 }
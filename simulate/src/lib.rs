@@ -4,6 +4,8 @@
 #![allow(clippy::unreadable_literal)]
 
 pub use crate::{
+    air_control::AirControl,
+    arrival::{solve_arrival, solve_arrival_then_cruise, ArrivalPlan},
     car::{Car, CarSimulateError},
     car1d::Car1D,
     car_forward_dodge::{CarForwardDodge, CarForwardDodge1D},
@@ -12,8 +14,11 @@ pub use crate::{
     math::linear_interpolate,
 };
 
+mod air_control;
+mod arrival;
 mod car;
 mod car1d;
+pub mod car_double_jump;
 mod car_forward_dodge;
 pub mod car_jump;
 mod car_powerslide_turn;